@@ -1,36 +1,191 @@
 use std::sync::{Arc, RwLock};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering};
 use std::time::Duration;
 use ratatui::widgets::{TableState, ListState};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use bollard::models::ContainerInspectResponse;
 use futures::StreamExt;
 use tokio::sync::Semaphore;
 use chrono::Utc;
 
+use crate::audit::ExecSession;
 use crate::config::{load_config, save_config};
-use crate::docker::client::DockerClient;
-use crate::types::{ContainerInfo, ContainerStats, ImageInfo, Result, ContainerHealth, HealthStatus, AppConfig, RefreshRate, StatsView, PollStrategy, PerfMetrics};
-use crate::docker::containers::{list_containers, start_container, stop_container, restart_container, remove_container, inspect_container, pause_container, unpause_container};
+use crate::docker::client::{ApiFeature, DockerClient};
+use crate::docker::context::list_contexts;
+use crate::docker::diagnostics::{check_daemon, ping_host};
+use crate::search::{fuzzy_match, CaseMode};
+use crate::types::{ContainerInfo, ContainerStats, ImageInfo, ImageUsage, NodeInfo, RegistryInfo, with_registry_prefix, NodeAvailability, ServiceInfo, StackInfo, SystemReport, CleanupKind, Result, ContainerHealth, HealthStatus, AppConfig, RefreshRate, StatsView, PollStrategy, PerfMetrics, ContainerAction, Toast, Syntax, LifecycleEvent, ExecProfile, ErrorEntry, DockerContext, HostConfig, AppError, ScanSummary, RateLimit, NetemRule, ChaosAction, FileEntry, FsChange, FsChangeKind, ProcessList, Provenance, NotifyEvent, NotifyStyle, PublishedPort, Workspace, ProxyConfig, glob_match};
+use crate::docker::containers::{get_container, kill_container, list_containers, start_container, stop_container, restart_container, inspect_container, pause_container, unpause_container, apply_container_action, send_stdin, config_from_inspect, duplicate_container, parse_port_specs, recreate_container, recreate_warnings, rename_container, export_filesystem, root_fs_size, published_ports, filesystem_changes, top_processes};
 use sysinfo::{Pid, System};
 use crate::docker::health::{fetch_health_info, parse_health_status_from_string};
-use crate::docker::images::{list_images, pull_image, remove_image, inspect_image, prune_images};
-use crate::docker::logs::stream_logs;
+use crate::docker::templates::{create_network, create_volume};
+use crate::docker::files::{download_path, list_command, list_directory, upload_path};
+use crate::docker::images::{commit_container, image_usage, registry_info, list_images, pull_image, pull_image_throttled, remove_image, inspect_image, prune_images};
+use crate::docker::logs::{fetch_recent_logs, line_time, stream_logs};
 use crate::docker::stats::fetch_container_stats;
+use crate::docker::exec::{check_container_clock, exec_choices, ClockCheck, CLOCK_COMMAND};
+use crate::docker::events::{container_history, lifecycle_event, lifecycle_events_options};
+use crate::docker::compose::scale_service;
+use crate::docker::migrate::{migrate_container, MigrateOptions};
+use crate::docker::export::{compose_file, export_running_containers, port_specs, run_command, SNAPSHOT_SHARED_FILES};
+use crate::docker::system::{analyze_host, prune_build_cache, run_cleanup};
+use crate::docker::swarm::{list_nodes, list_services, list_stacks, remove_stack, rollback_service, set_node_availability, update_service_image, watch_service_update};
+use crate::ui::widgets::form::Form;
+use crate::ui::widgets::text_input::{TextInput, validate_command, validate_container_dir, validate_container_name, validate_local_path, validate_image_ref, validate_bulk_command, validate_label_assignments, validate_log_time, validate_minutes, validate_optional_number, validate_optional_percent, validate_seconds, validate_port_specs, validate_signal, validate_replicas};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
     ContainerList,
     Logs,
+    Details,
+    Graphs,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Owns the keyboard focus and the Tab/Shift+Tab cycle order of the visible panes.
+#[derive(Debug, Clone)]
+pub struct FocusManager {
+    order: Vec<Focus>,
+    current: Focus,
+}
+
+impl FocusManager {
+    pub fn new(order: Vec<Focus>) -> Self {
+        let current = order.first().copied().unwrap_or(Focus::ContainerList);
+        Self { order, current }
+    }
+
+    pub fn current(&self) -> Focus {
+        self.current
+    }
+
+    pub fn is(&self, pane: Focus) -> bool {
+        self.current == pane
+    }
+
+    pub fn set(&mut self, pane: Focus) {
+        if self.order.contains(&pane) {
+            self.current = pane;
+        }
+    }
+
+    /// Replaces the cycle order (e.g. when a pane is hidden), keeping the current
+    /// pane focused if it is still visible.
+    pub fn set_order(&mut self, order: Vec<Focus>) {
+        if !order.contains(&self.current) {
+            self.current = order.first().copied().unwrap_or(Focus::ContainerList);
+        }
+        self.order = order;
+    }
+
+    pub fn next(&mut self) {
+        self.step(1);
+    }
+
+    pub fn prev(&mut self) {
+        self.step(self.order.len().saturating_sub(1));
+    }
+
+    fn step(&mut self, by: usize) {
+        if self.order.is_empty() {
+            return;
+        }
+        let idx = self.order.iter().position(|f| *f == self.current).unwrap_or(0);
+        self.current = self.order[(idx + by) % self.order.len()];
+    }
+}
+
+/// Full container re-list interval; lifecycle events keep the list current in between.
+pub const CONTAINER_RECONCILE_SECS: u64 = 60;
+
+/// How far back the timeline backfills from the daemon's event history.
+pub const TIMELINE_BACKFILL_HOURS: i64 = 24;
+
+/// Entries kept in the error console.
+pub const ERROR_LOG_LEN: usize = 200;
+
+/// Poll interval for the container lists of the extra hosts in `hosts`.
+pub const REMOTE_POLL_SECS: u64 = 5;
+
+/// How far back the Images view looks in the event history for usage.
+pub const IMAGE_USAGE_DAYS: i64 = 7;
+
+/// How often the connection watchdog pings the daemon while it is reachable.
+pub const CONNECTION_CHECK_SECS: u64 = 5;
+/// Longest wait between reconnection attempts; the delay doubles up to this.
+pub const RECONNECT_MAX_BACKOFF_SECS: u64 = 30;
+
+/// Shown in the swarm views instead of calling APIs Podman doesn't implement.
+pub const PODMAN_NO_SWARM: &str = "Podman does not support swarm mode";
+
+/// How often the image scanner looks for images without a fresh result.
+pub const SCAN_CHECK_SECS: u64 = 60;
+
+/// How long the screen stays inverted for a `flash` notification.
+pub const FLASH_DURATION: Duration = Duration::from_millis(200);
+
+/// Log lines searched for errors when writing an incident report, and how many
+/// of the matches are kept.
+const INCIDENT_LOG_TAIL: usize = 500;
+const INCIDENT_MAX_ERRORS: usize = 20;
+/// Lowercase words that mark a log line as an error in incident reports.
+const ERROR_MARKERS: [&str; 6] = ["error", "exception", "fatal", "panic", "traceback", "critical"];
+
+/// Current labels listed in the label editor before the rest are summarized.
+const MAX_LISTED_LABELS: usize = 12;
+
+/// How long a remove or prune waits before it reaches the daemon, so it can
+/// still be undone with Ctrl+Z.
+pub const UNDO_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum View {
     Containers,
+    Top,
     Images,
+    Services,
+    Stacks,
+    Nodes,
+    System,
+}
+
+impl View {
+    /// Next view in the `v` cycle.
+    pub fn next(&self) -> View {
+        match self {
+            View::Containers => View::Top,
+            View::Top => View::Images,
+            View::Images => View::Services,
+            View::Services => View::Stacks,
+            View::Stacks => View::Nodes,
+            View::Nodes => View::System,
+            View::System => View::Containers,
+        }
+    }
+
+    pub fn prev(&self) -> View {
+        match self {
+            View::Containers => View::System,
+            View::Top => View::Containers,
+            View::Images => View::Top,
+            View::Services => View::Images,
+            View::Stacks => View::Services,
+            View::Nodes => View::Stacks,
+            View::System => View::Nodes,
+        }
+    }
+}
+
+/// What the Top view ranks containers by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TopSort {
+    #[default]
+    Cpu,
+    Memory,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum SortOrder {
     CreatedDesc,
     CreatedAsc,
@@ -38,15 +193,254 @@ pub enum SortOrder {
     SizeAsc,
     HealthDesc, // Unhealthy first
     HealthAsc,
+    // Containers only; CPU, memory and uptime put containers without them last
+    CpuDesc,
+    MemDesc,
+    NameAsc,
+    UptimeDesc,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HealthFilter {
     All,
     Unhealthy,
     Healthy,
 }
 
+impl SortOrder {
+    /// Short form for table titles, e.g. `created↓`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortOrder::CreatedDesc => "created↓",
+            SortOrder::CreatedAsc => "created↑",
+            SortOrder::SizeDesc => "size↓",
+            SortOrder::SizeAsc => "size↑",
+            // Both put unhealthy containers first
+            SortOrder::HealthDesc | SortOrder::HealthAsc => "health",
+            SortOrder::CpuDesc => "cpu↓",
+            SortOrder::MemDesc => "mem↓",
+            SortOrder::NameAsc => "name↑",
+            SortOrder::UptimeDesc => "uptime↓",
+        }
+    }
+}
+
+/// Container list filters behind the status bar badges (`F5`-`F8`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateFilter {
+    Running,
+    Stopped,
+    Paused,
+    Unhealthy,
+}
+
+impl StateFilter {
+    pub const ALL: [StateFilter; 4] = [StateFilter::Running, StateFilter::Stopped, StateFilter::Paused, StateFilter::Unhealthy];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StateFilter::Running => "running",
+            StateFilter::Stopped => "stopped",
+            StateFilter::Paused => "paused",
+            StateFilter::Unhealthy => "unhealthy",
+        }
+    }
+
+    /// Unhealthy is left to the health filter, which already has it.
+    fn matches(&self, container: &ContainerInfo) -> bool {
+        match self {
+            StateFilter::Running => container.state == "running",
+            StateFilter::Stopped => matches!(container.state.as_str(), "exited" | "created" | "dead"),
+            StateFilter::Paused => container.state == "paused",
+            StateFilter::Unhealthy => true,
+        }
+    }
+}
+
+impl HealthFilter {
+    /// Title tag for the active filter; `None` when nothing is hidden.
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            HealthFilter::All => None,
+            HealthFilter::Unhealthy => Some("unhealthy"),
+            HealthFilter::Healthy => Some("healthy"),
+        }
+    }
+}
+
+/// What the container list is grouped by, chosen with `g`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupBy {
+    Project,
+    Label(String),
+}
+
+impl GroupBy {
+    /// `project` for the compose project, anything else as a label key.
+    pub fn parse(value: &str) -> Option<GroupBy> {
+        match value.trim() {
+            "" => None,
+            "project" => Some(GroupBy::Project),
+            key => Some(GroupBy::Label(key.to_string())),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            GroupBy::Project => "project",
+            GroupBy::Label(key) => key,
+        }
+    }
+
+    /// The group `container` falls in; `None` when it has no such label.
+    fn key<'a>(&self, container: &'a ContainerInfo) -> Option<&'a str> {
+        match self {
+            GroupBy::Project => container.compose_project(),
+            GroupBy::Label(key) => container.labels.get(key).map(|s| s.as_str()),
+        }
+    }
+}
+
+/// Shown as the group of containers without the grouping label.
+pub const UNGROUPED: &str = "(none)";
+
+/// A row of the container table: a group header or an index into
+/// `filtered_containers`. Without grouping there are only containers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListRow {
+    Group { key: String, count: usize, running: usize, collapsed: bool },
+    Container(usize),
+}
+
+/// Dialogs and popups layered over the main view. The last entry in
+/// `App::modals` is on top and receives all key input.
+#[derive(Debug, Clone)]
+pub enum Modal {
+    Help,
+    PullImage,
+    DeleteImageConfirm { force: bool },
+    HealthLog(String),
+    ProjectMenu,
+    TextViewer { title: String, content: String, syntax: Syntax },
+    Timeline { container_id: String, container_name: String },
+    ScaleService { project: String, service: String, form: Form },
+    UpdateService { service_id: String, service_name: String, form: Form },
+    ExportStats { container_id: String, container_name: String, form: Form },
+    RemoveStackConfirm(String),
+    DrainNodeConfirm(NodeInfo),
+    /// Names of the unused volumes the cleanup checklist is about to remove.
+    CleanupVolumesConfirm(Vec<String>),
+    PruneWizard { step: PruneStep, estimates: Vec<(CleanupKind, u64)>, volumes: Vec<String> },
+    PruneBuildCache { form: Form },
+    CreateFromTemplate { form: Form },
+    SendSignal { container_id: String, container_name: String, form: Form },
+    SendStdin { container_id: String, container_name: String, form: Form },
+    EditLabels { container_id: String, container_name: String, form: Form },
+    /// `published` and `local` are what the ports are checked against as they're typed.
+    Duplicate { container_id: String, container_name: String, form: Form, published: Vec<PublishedPort>, local: bool },
+    Rename { container_id: String, container_name: String, form: Form },
+    Commit { container_id: String, container_name: String, form: Form },
+    Netem { container_id: String, container_name: String, form: Form },
+    Chaos { project: String, form: Form },
+    ExportFilesystem { container_id: String, container_name: String, form: Form },
+    IncidentReport { container_id: String, container_name: String, form: Form },
+    Files { container_id: String, container_name: String, path: String, entries: Vec<FileEntry>, selected: usize },
+    Upload { container_id: String, container_name: String, form: Form },
+    /// Commands to exec; the entry after the last one asks for a custom command.
+    ExecChooser { container_id: String, container_name: String, commands: Vec<String>, selected: usize },
+    ExecCustom { container_id: String, container_name: String, form: Form },
+    Migrate { container_id: String, container_name: String, form: Form },
+    /// Confirmation before removing the marked containers.
+    RemoveMarkedConfirm(usize),
+    /// Asked when quitting with this many actions still waiting out the undo window.
+    QuitConfirm(usize),
+    BulkCommand,
+    JumpToTime { form: Form },
+    GroupBy { form: Form },
+    /// Containers a bulk command resolved to, as (id, name), waiting for a yes.
+    BulkCommandConfirm { command: String, action: ContainerAction, targets: Vec<(String, String)> },
+    EditContainer { container_id: String, container_name: String, form: Form },
+    /// Live `docker top` of a container. The refresh task holds only a weak
+    /// reference to `list`, so it stops once the modal is closed.
+    Processes { container_id: String, container_name: String, list: Arc<RwLock<Option<std::result::Result<ProcessList, String>>>>, selected: usize },
+    /// `docker diff` of a container; `filter` limits the list to one kind of change.
+    FsDiff { container_id: String, container_name: String, changes: Vec<FsChange>, filter: Option<FsChangeKind>, selected: usize },
+    UploadOverwriteConfirm { container_id: String, container_name: String, source: String, dest: String },
+    ContextPicker { contexts: Vec<DockerContext>, selected: usize },
+    WorkspacePicker { workspaces: Vec<Workspace>, selected: usize },
+    Diagnostics(String),
+    /// Typing into `App::container_filter`; the list filters as you type.
+    ContainerSearch,
+}
+
+/// Changes from the edit dialog (`F4`), applied by recreating the container.
+#[derive(Debug, Clone)]
+pub struct ContainerEdit {
+    pub image: String,
+    pub env_set: Vec<(String, String)>,
+    pub env_remove: Vec<String>,
+    /// `docker run -p` style specs; replaces every published port.
+    pub ports: String,
+}
+
+/// A destructive operation held back for the undo window.
+#[derive(Debug, Clone)]
+pub enum DeferredAction {
+    RemoveContainers { label: String, ids: Vec<String> },
+    RemoveImage { id: String, name: String, force: bool },
+    Cleanup(CleanupKind),
+    Prune(Vec<CleanupKind>),
+    PruneBuildCache { keep_mb: Option<u64>, older_than_hours: Option<u64> },
+    RemoveStack(String),
+}
+
+/// A queued [`DeferredAction`] and when it runs.
+#[derive(Debug, Clone)]
+pub struct PendingAction {
+    /// What the toast says is about to happen, e.g. "Removing nginx".
+    pub description: String,
+    pub due: std::time::Instant,
+    pub action: DeferredAction,
+}
+
+/// Steps of the guided prune dialog: pick classes, confirm, then show what was
+/// removed. The summary fills in once the prune, running in the background, is done.
+#[derive(Debug, Clone)]
+pub enum PruneStep {
+    Select(Form),
+    Confirm(Vec<CleanupKind>),
+    Summary(Arc<RwLock<Option<Vec<String>>>>),
+}
+
+/// Reachability of the primary daemon, tracked by the connection watchdog.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConnectionState {
+    /// Unix time the daemon stopped answering; `None` while connected.
+    pub down_since: Option<i64>,
+    pub attempts: u32,
+    /// Unix time of the next reconnection attempt.
+    pub next_retry: i64,
+    /// Set when the daemon answers again; the event loop then restarts background work.
+    pub recovered: bool,
+}
+
+/// Progress of the manual refresh (`R`) of one view.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ViewRefresh {
+    pub started: Option<std::time::Instant>, // Set while the refresh is in flight
+    pub finished_at: Option<i64>,
+}
+
+/// Quick-info popup for a container row, shown on hover or with `o`
+/// without moving the main selection.
+#[derive(Debug, Clone)]
+pub struct Preview {
+    pub container_id: String,
+    pub row: usize, // index into list_rows
+    pub logs: Arc<RwLock<Vec<String>>>,
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct ViewportState {
     pub offset: usize,
@@ -55,37 +449,122 @@ pub struct ViewportState {
 
 pub struct App {
     pub docker: DockerClient,
+    /// Name of the Docker context the client is connected to.
+    pub docker_context: String,
+    /// Refresh tasks bound to the current connection, by name; aborted on context switch.
+    background_tasks: Vec<(String, tokio::task::JoinHandle<()>)>,
+    pub connection: Arc<RwLock<ConnectionState>>,
+    /// Clients for the extra hosts from the config, by host name.
+    pub remote_clients: HashMap<String, DockerClient>,
     pub containers: Arc<RwLock<Vec<ContainerInfo>>>,
     pub filtered_containers: Vec<ContainerInfo>, // Cache for UI
+    /// Rows of the container table, which `table_state` indexes.
+    pub list_rows: Vec<ListRow>,
+    pub group_by: Option<GroupBy>,
+    /// Keys of the groups collapsed with Enter.
+    pub collapsed_groups: HashSet<String>,
+    /// Set with `.` to list the containers `hidden` in the config too.
+    pub show_hidden: bool,
+    /// Containers the hide list left out of the last filtering.
+    pub hidden_count: usize,
     pub container_stats: Arc<RwLock<HashMap<String, ContainerStats>>>,
+    /// Top view ranking, and the selected container's ID, which the
+    /// selection follows as rows move.
+    pub top_sort: TopSort,
+    pub top_selected: Option<String>,
+    /// Set while the Top view is open, so every running container's stats are
+    /// polled even with `VisibleOnly` polling.
+    pub poll_all_stats: Arc<AtomicBool>,
     pub container_health: Arc<RwLock<HashMap<String, ContainerHealth>>>,
+    /// Unix time the container list was last synced with the daemon (0 = never).
+    pub containers_updated: Arc<AtomicI64>,
+    /// Lifecycle events per container ID, recorded live and backfilled on demand.
+    pub container_timelines: Arc<RwLock<HashMap<String, VecDeque<LifecycleEvent>>>>,
     pub perf_metrics: Arc<RwLock<PerfMetrics>>,
     pub table_state: TableState,
     pub viewport_state: Arc<RwLock<ViewportState>>,
     pub config: Arc<RwLock<AppConfig>>,
     pub show_all: Arc<AtomicBool>,
     pub health_filter: HealthFilter,
+    pub state_filter: Option<StateFilter>,
+    /// Columns of the status bar badges as last drawn, for clicks.
+    pub status_badges: Vec<(std::ops::Range<u16>, StateFilter)>,
+    pub status_bar_row: u16,
+    /// Name/image search from `/`; rows must contain it (see `search_case`).
+    pub container_filter: TextInput,
+    /// Listed containers' IDs by how well they match the search, best first; `n`/`N` step through them.
+    pub search_matches: Vec<String>,
+    /// Bulk command typed at the `:` prompt, e.g. `stop api-*`.
+    pub bulk_command: TextInput,
     pub container_sort: SortOrder,
     
     // Image State
     pub images: Arc<RwLock<Vec<ImageInfo>>>,
+    pub image_usage: Arc<RwLock<HashMap<String, ImageUsage>>>,
+    /// Vulnerability scan results by image ID, when `[scan]` is enabled.
+    pub image_scans: Arc<RwLock<HashMap<String, ScanSummary>>>,
+    /// Docker Hub pulls left, once checked; stays None with `[hub_rate_limit]` disabled.
+    pub hub_rate_limit: Arc<RwLock<Option<RateLimit>>>,
+    /// Traffic shaping applied from this session, by container ID, with when it was applied.
+    pub netem_rules: Arc<RwLock<HashMap<String, (NetemRule, i64)>>>,
+    pub images_updated: Arc<AtomicI64>, // Same as containers_updated, for the image list
     pub table_state_images: TableState,
     pub current_view: View,
+    pub view_refresh: Arc<RwLock<HashMap<View, ViewRefresh>>>,
+    /// Set by `R`; the event loop draws the spinner once before running the refresh.
+    pub pending_refresh: bool,
+    /// Asks the stats poller for one round even in Manual refresh mode.
+    stats_refresh: Arc<AtomicBool>,
     pub show_dangling: Arc<AtomicBool>,
     pub total_images: usize,
     pub total_image_size: u64,
     pub image_sort: SortOrder,
     pub selected_image_details: Arc<RwLock<Option<String>>>,
+    /// "Pull size" lines by image ID, once looked up in the registry.
+    pub image_pull_sizes: Arc<RwLock<HashMap<String, String>>>,
+
+    // Swarm Service State
+    pub services: Vec<ServiceInfo>,
+    pub table_state_services: TableState,
+    pub services_error: Option<String>,
+
+    // Swarm Stack State
+    pub stacks: Vec<StackInfo>,
+    pub table_state_stacks: TableState,
+    pub stacks_error: Option<String>,
+
+    // Swarm Node State
+    pub nodes: Vec<NodeInfo>,
+    pub table_state_nodes: TableState,
+    pub nodes_error: Option<String>, // Set when the daemon is not a swarm manager
     
+    // System / Cleanup State
+    pub system_report: Arc<RwLock<Option<SystemReport>>>,
+    pub system_error: Arc<RwLock<Option<String>>>,
+    pub cleanup_state: ListState,
+
     // Pull Image State
-    pub show_pull_dialog: bool,
-    pub pull_input: String,
+    pub pull_input: TextInput,
+    pub registry_info: RegistryInfo,
+    /// Index into `registry_info.targets()` shifted by one; 0 pulls the reference as typed.
+    pub pull_target: usize,
+    /// Extra host whose daemon runs the pull; None for the primary.
+    pub pull_host: Option<String>,
     pub is_pulling: Arc<AtomicBool>,
-    pub show_health_log_dialog: bool,
-    pub health_log_content: String,
-    pub pull_progress: Arc<RwLock<Vec<String>>>, // Store recent progress lines
-    pub show_delete_confirm: bool, // For image deletion
-    pub pending_delete_force: bool,
+    pub output_log: Arc<RwLock<Vec<String>>>, // Recent lines for the Output pane (pull progress, scaling steps)
+    /// Background task failures, newest last; shown in the error console ('!').
+    pub error_log: Arc<RwLock<VecDeque<ErrorEntry>>>,
+    pub show_error_console: bool,
+    /// Number of errors logged when the console was last open, for the unread badge.
+    pub errors_seen: usize,
+    /// Last key press or mouse event, for the idle privacy cover.
+    pub last_input: std::time::Instant,
+    /// Whether the privacy cover is up; the next key only takes it down.
+    pub privacy_cover: bool,
+    /// Events raised by background tasks, waiting for the event loop to ring or flash.
+    pub notifications: Arc<RwLock<Vec<NotifyEvent>>>,
+    /// End of the current screen flash.
+    pub flash_until: Option<std::time::Instant>,
 
     // Selection state
     pub selected_container_details: Arc<RwLock<Option<String>>>,
@@ -96,6 +575,7 @@ pub struct App {
     pub logs_state: ListState,
     pub auto_scroll: bool,
     pub log_stream_task: Option<tokio::task::JoinHandle<()>>,
+    pub project_logs: bool, // Merge logs of every container in the selected compose project
 
     // Metrics
     pub total_containers: usize,
@@ -104,17 +584,55 @@ pub struct App {
     pub paused_count: usize,
 
     // UI State
-    pub show_help: bool,
+    pub modals: Vec<Modal>,
     pub current_help_tab: crate::types::HelpTab,
     pub help_scroll: u16,
-    pub should_exec: Option<String>,
-    pub focus: Focus,
+    pub viewer_scroll: u16,
+    /// Container ID and command of an exec session for the event loop to start.
+    pub should_exec: Option<(String, String)>,
+    /// `--read-only` or `read_only` in the config: actions that change daemon state are refused.
+    pub read_only: bool,
+    /// `read_only` as the session started, before any workspace changed it.
+    base_read_only: Option<bool>,
+    /// `--low-memory`: small buffers for this session, whatever the config says.
+    pub low_memory: bool,
+    /// `--refresh` and the configured rate it replaced. Saving the config
+    /// keeps the configured rate until the rate is changed in the UI.
+    refresh_flag: Option<(RefreshRate, RefreshRate)>,
+    /// Active workspace, if one was switched to.
+    pub workspace: Option<String>,
+    /// Container name/image patterns kept at the top of the list.
+    pub pinned: Vec<String>,
+    /// IDs of containers marked with Space; r/s/S/d act on all of them.
+    pub marked: HashSet<String>,
+    /// Removes and prunes waiting out [`UNDO_WINDOW`], oldest first.
+    pub pending_actions: VecDeque<PendingAction>,
+    pub focus: FocusManager,
+    pub preview: Option<Preview>,
+    pub container_list_area: ratatui::layout::Rect,
+    pub details_scroll: u16,
+    pub toast: Arc<RwLock<Option<Toast>>>,
+}
+
+/// How well the search matches a container: fuzzily on its name or image, or
+/// as a prefix of its ID.
+fn search_score(container: &ContainerInfo, query: &str, case: CaseMode) -> Option<i64> {
+    let id_match = container.id.trim_start_matches("sha256:").starts_with(query).then_some(1000);
+    [&container.name, &container.image].into_iter()
+        .filter_map(|text| fuzzy_match(text, query, case).map(|(score, _)| score))
+        .chain(id_match)
+        .max()
 }
 
 impl App {
-    pub async fn new(_stats_interval_arg: u64) -> Result<Self> {
-        let docker = DockerClient::new()?;
+    pub async fn new(_stats_interval_arg: u64, host: Option<String>, context: Option<String>, low_memory: bool) -> Result<Self> {
         let config = load_config().unwrap_or_default();
+        // An explicitly requested context beats the configured host
+        let host = host.or_else(|| config.host.clone().filter(|_| context.is_none()));
+        let (docker, docker_context) = DockerClient::resolve(host.as_deref(), context.as_deref(), config.tls.as_ref())?;
+        let mut docker = docker.with_timeouts(config.timeouts);
+        docker.detect_engine().await;
+        let read_only = config.read_only;
         let containers = Arc::new(RwLock::new(Vec::new()));
         let container_stats = Arc::new(RwLock::new(HashMap::new()));
         let container_health = Arc::new(RwLock::new(HashMap::new()));
@@ -123,35 +641,81 @@ impl App {
         
         let mut app = Self {
             docker,
+            docker_context,
+            background_tasks: Vec::new(),
+            remote_clients: HashMap::new(),
+            connection: Arc::new(RwLock::new(ConnectionState::default())),
             containers: containers.clone(),
             filtered_containers: Vec::new(),
+            list_rows: Vec::new(),
+            group_by: None,
+            collapsed_groups: HashSet::new(),
+            show_hidden: false,
+            hidden_count: 0,
             container_stats: container_stats.clone(),
+            top_sort: TopSort::default(),
+            top_selected: None,
+            poll_all_stats: Arc::new(AtomicBool::new(false)),
             container_health: container_health.clone(),
+            containers_updated: Arc::new(AtomicI64::new(0)),
+            container_timelines: Arc::new(RwLock::new(HashMap::new())),
             perf_metrics: perf_metrics.clone(),
             table_state: TableState::default(),
             viewport_state: viewport_state.clone(),
             config: Arc::new(RwLock::new(config)),
             show_all: Arc::new(AtomicBool::new(true)),
             health_filter: HealthFilter::All,
+            state_filter: None,
+            status_badges: Vec::new(),
+            status_bar_row: 0,
+            container_filter: TextInput::new(),
+            bulk_command: TextInput::with_validator(validate_bulk_command),
+            search_matches: Vec::new(),
             container_sort: SortOrder::CreatedDesc,
             
             // Image init
             images: Arc::new(RwLock::new(Vec::new())),
+            image_usage: Arc::new(RwLock::new(HashMap::new())),
+            image_scans: Arc::new(RwLock::new(HashMap::new())),
+            hub_rate_limit: Arc::new(RwLock::new(None)),
+            netem_rules: Arc::new(RwLock::new(HashMap::new())),
+            images_updated: Arc::new(AtomicI64::new(0)),
             table_state_images: TableState::default(),
             current_view: View::Containers,
+            view_refresh: Arc::new(RwLock::new(HashMap::new())),
+            pending_refresh: false,
+            stats_refresh: Arc::new(AtomicBool::new(false)),
             show_dangling: Arc::new(AtomicBool::new(false)),
             total_images: 0,
             total_image_size: 0,
             image_sort: SortOrder::CreatedDesc,
             selected_image_details: Arc::new(RwLock::new(None)),
-            show_pull_dialog: false,
-            pull_input: String::new(),
+            image_pull_sizes: Arc::new(RwLock::new(HashMap::new())),
+            services: Vec::new(),
+            table_state_services: TableState::default(),
+            services_error: None,
+            stacks: Vec::new(),
+            table_state_stacks: TableState::default(),
+            stacks_error: None,
+            nodes: Vec::new(),
+            table_state_nodes: TableState::default(),
+            nodes_error: None,
+            system_report: Arc::new(RwLock::new(None)),
+            system_error: Arc::new(RwLock::new(None)),
+            cleanup_state: ListState::default(),
+            pull_input: TextInput::with_validator(validate_image_ref),
+            registry_info: RegistryInfo::default(),
+            pull_target: 0,
+            pull_host: None,
             is_pulling: Arc::new(AtomicBool::new(false)),
-            show_health_log_dialog: false,
-            health_log_content: String::new(),
-            pull_progress: Arc::new(RwLock::new(Vec::new())),
-            show_delete_confirm: false,
-            pending_delete_force: false,
+            error_log: Arc::new(RwLock::new(VecDeque::new())),
+            show_error_console: false,
+            errors_seen: 0,
+            last_input: std::time::Instant::now(),
+            privacy_cover: false,
+            notifications: Arc::new(RwLock::new(Vec::new())),
+            flash_until: None,
+            output_log: Arc::new(RwLock::new(Vec::new())),
 
             selected_container_details: Arc::new(RwLock::new(None)),
             selected_container_logs: Arc::new(RwLock::new(Vec::new())),
@@ -159,19 +723,34 @@ impl App {
             logs_state: ListState::default(),
             auto_scroll: true,
             log_stream_task: None,
+            project_logs: false,
             total_containers: 0,
             running_count: 0,
             stopped_count: 0,
             paused_count: 0,
-            show_help: false,
+            modals: Vec::new(),
             current_help_tab: crate::types::HelpTab::default(),
             help_scroll: 0,
+            viewer_scroll: 0,
             should_exec: None,
-            focus: Focus::ContainerList,
+            read_only,
+            base_read_only: None,
+            low_memory,
+            refresh_flag: None,
+            workspace: None,
+            pinned: Vec::new(),
+            marked: HashSet::new(),
+            pending_actions: VecDeque::new(),
+            focus: FocusManager::new(vec![Focus::ContainerList, Focus::Logs, Focus::Details, Focus::Graphs]),
+            details_scroll: 0,
+            preview: None,
+            container_list_area: ratatui::layout::Rect::default(),
+            toast: Arc::new(RwLock::new(None)),
         };
         
         app.refresh_containers().await?;
         app.refresh_images().await?;
+        app.registry_info = registry_info(&app.docker).await.unwrap_or_default();
         if app.total_containers > 0 {
             app.table_state.select(Some(0));
             // Trigger initial fetch
@@ -180,66 +759,200 @@ impl App {
             }
         }
         
-        // --- Background Task 1: List Containers (every 10s) ---
-        let docker_clone_list = app.docker.clone();
-        let containers_clone_list = containers.clone();
-        let show_all_clone = app.show_all.clone();
-        let health_map_list = container_health.clone();
-        let docker_health_list = app.docker.clone();
+        // --- Background Task 4: Performance Monitoring ---
+        let perf_metrics_clone = perf_metrics.clone();
         
-        tokio::spawn(async move {
+        std::thread::spawn(move || {
+            let mut sys = System::new();
+            let pid = Pid::from(std::process::id() as usize);
+            
+            loop {
+                sys.refresh_process(pid);
+                if let Some(process) = sys.process(pid) {
+                    let cpu = process.cpu_usage();
+                    let mem = process.memory();
+                    
+                    if let Ok(mut metrics) = perf_metrics_clone.write() {
+                        metrics.cpu_usage = cpu as f64;
+                        metrics.memory_usage = mem;
+                    }
+                }
+                std::thread::sleep(Duration::from_secs(2));
+            }
+        });
+
+        let hosts = app.config.read().unwrap().hosts.clone();
+        app.connect_remote_hosts(hosts).await;
+        app.spawn_background_tasks();
+
+        Ok(app)
+    }
+
+    /// Spawns the Docker-backed refresh tasks against the current connection.
+    /// The handles are kept so a context switch can abort and respawn them.
+    fn spawn_background_tasks(&mut self) {
+        // --- Background Task 1: Container List (lifecycle events, reconciled every 60s) ---
+        let docker_clone_list = self.docker.clone();
+        let containers_clone_list = self.containers.clone();
+        let show_all_clone = self.show_all.clone();
+        let health_map_list = self.container_health.clone();
+        let errors_list = self.error_log.clone();
+        let updated_list = self.containers_updated.clone();
+        
+        self.background_tasks.push(("container list".to_string(), tokio::spawn(async move {
             loop {
-                tokio::time::sleep(Duration::from_secs(10)).await;
+                tokio::time::sleep(Duration::from_secs(CONTAINER_RECONCILE_SECS)).await;
                 let show_all = show_all_clone.load(Ordering::Relaxed);
                 match list_containers(&docker_clone_list, show_all).await {
                     Ok(containers_result) => {
-                         // Check for health changes
-                         {
-                             let health_map = health_map_list.write().unwrap();
-                             for c in &containers_result {
-                                 if c.state != "running" { continue; }
-                                 
-                                 let new_status = parse_health_status_from_string(&c.status);
-                                 let needs_update = match health_map.get(&c.id) {
-                                     Some(current) => current.status != new_status,
-                                     None => true,
-                                 };
-
-                                 if needs_update {
-                                     let docker = docker_health_list.clone();
-                                     let health_map_inner = health_map_list.clone();
-                                     let id = c.id.clone();
-                                     tokio::spawn(async move {
-                                         if let Ok(health) = fetch_health_info(&docker, &id).await {
-                                             health_map_inner.write().unwrap().insert(id, health);
-                                         }
-                                     });
-                                 }
-                             }
-                         }
-
-                         let mut containers = containers_clone_list.write().unwrap();
-                         *containers = containers_result;
+                         tracing::debug!(containers = containers_result.len(), "container list reconciled");
+                         App::sync_health(&docker_clone_list, &health_map_list, &containers_result);
+                         App::replace_host_containers(&containers_clone_list, None, containers_result);
+                         updated_list.store(Utc::now().timestamp(), Ordering::Relaxed);
                     }
                     Err(e) => {
-                        eprintln!("Failed to refresh containers: {}", e);
+                        App::log_error(&errors_list, "container list", e.to_string());
                     }
                 }
             }
-        });
+        })));
+
+        let docker_list_events = self.docker.clone();
+        let containers_list_events = self.containers.clone();
+        let show_all_events = self.show_all.clone();
+        let health_map_list_events = self.container_health.clone();
+        let errors_list_events = self.error_log.clone();
+        let updated_list_events = self.containers_updated.clone();
+
+        self.background_tasks.push(("container events".to_string(), tokio::spawn(async move {
+            use bollard::query_parameters::EventsOptions;
+            loop {
+                let mut filters = HashMap::new();
+                filters.insert("type".to_string(), vec!["container".to_string()]);
+                filters.insert("event".to_string(), ["create", "start", "die", "destroy", "rename", "pause", "unpause"]
+                    .iter().map(|e| e.to_string()).collect());
+                let options = EventsOptions {
+                    filters: Some(filters),
+                    ..Default::default()
+                };
+
+                let mut stream = docker_list_events.inner.events(Some(options));
+                while let Some(event) = stream.next().await {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(e) => {
+                            App::log_error(&errors_list_events, "container events", e.to_string());
+                            break;
+                        }
+                    };
+                    let Some(id) = event.actor.and_then(|a| a.id) else { continue };
+                    let updated = match event.action.as_deref() {
+                        Some("destroy") => None,
+                        _ => get_container(&docker_list_events, &id).await.ok().flatten(),
+                    };
+                    let show_all = show_all_events.load(Ordering::Relaxed);
+                    let updated = updated.filter(|c| show_all || c.state == "running");
+                    if let Some(c) = &updated {
+                        App::sync_health(&docker_list_events, &health_map_list_events, std::slice::from_ref(c));
+                    }
+
+                    let mut containers = containers_list_events.write().unwrap();
+                    let pos = containers.iter().position(|c| c.host.is_none() && c.id == id);
+                    match (updated, pos) {
+                        (Some(c), Some(i)) => containers[i] = c,
+                        (Some(c), None) => containers.push(c),
+                        (None, Some(i)) => { containers.remove(i); }
+                        (None, None) => {}
+                    }
+                    updated_list_events.store(Utc::now().timestamp(), Ordering::Relaxed);
+                }
+
+                // Stream ended (e.g. daemon restart): events may have been missed, so re-list
+                tracing::warn!("container event stream ended, re-listing");
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                let show_all = show_all_events.load(Ordering::Relaxed);
+                if let Ok(containers_result) = list_containers(&docker_list_events, show_all).await {
+                    App::replace_host_containers(&containers_list_events, None, containers_result);
+                    updated_list_events.store(Utc::now().timestamp(), Ordering::Relaxed);
+                }
+            }
+        })));
+
+        // --- Lifecycle timeline recorder (also raises restart storm notifications) ---
+        let docker_timeline = self.docker.clone();
+        let timelines = self.container_timelines.clone();
+        let config_timeline = self.config.clone();
+        let low_memory_timeline = self.low_memory;
+        let containers_timeline = self.containers.clone();
+        let toast_timeline = self.toast.clone();
+        let errors_timeline = self.error_log.clone();
+        let notifications_timeline = self.notifications.clone();
+
+        self.background_tasks.push(("lifecycle timeline".to_string(), tokio::spawn(async move {
+            let mut storming: HashSet<String> = HashSet::new();
+            loop {
+                let mut stream = docker_timeline.inner.events(Some(lifecycle_events_options()));
+                while let Some(event) = stream.next().await {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(e) => {
+                            App::log_error(&errors_timeline, "lifecycle events", e.to_string());
+                            break;
+                        }
+                    };
+                    let Some((id, entry)) = lifecycle_event(event) else { continue };
+                    let is_start = entry.action == "start";
+                    let notify = match (entry.action.as_str(), entry.detail.as_str()) {
+                        ("health_status", "unhealthy") => Some(NotifyEvent::Unhealthy),
+                        // `docker stop` and `docker kill` send a kill event before the die
+                        ("die", _) if entry.exit_code.unwrap_or(0) != 0 => {
+                            let stopped = timelines.read().unwrap().get(&id)
+                                .and_then(|t| t.back())
+                                .is_some_and(|last| last.action == "kill");
+                            (!stopped).then_some(NotifyEvent::Crash)
+                        }
+                        _ => None,
+                    };
+                    notifications_timeline.write().unwrap().extend(notify);
+                    let max_events = config_timeline.read().unwrap().buffer_limits(low_memory_timeline).timeline_events;
+                    App::record_lifecycle(&timelines, &id, vec![entry], max_events);
+                    if !is_start {
+                        continue;
+                    }
+
+                    let storm = config_timeline.read().unwrap().restart_storm.clone();
+                    let restarts = timelines.read().unwrap()
+                        .get(&id)
+                        .map_or(0, |t| storm.restarts(t, Utc::now().timestamp()));
+                    if restarts <= storm.max_restarts {
+                        storming.remove(&id);
+                    } else if storming.insert(id.clone()) {
+                        notifications_timeline.write().unwrap().push(NotifyEvent::Alert);
+                        let name = containers_timeline.read().unwrap().iter()
+                            .find(|c| c.id == id)
+                            .map_or_else(|| id.chars().take(12).collect(), |c| c.name.clone());
+                        *toast_timeline.write().unwrap() = Some(Toast::new(
+                            format!("Restart storm: {} restarted {} times in {} min", name, restarts, storm.window_minutes),
+                            true,
+                        ));
+                    }
+                }
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+        })));
 
         // --- Background Task 3: Health Monitoring (Events & Polling) ---
-        let docker_events = app.docker.clone();
-        let health_map_events = container_health.clone();
+        let docker_events = self.docker.clone();
+        let health_map_events = self.container_health.clone();
         
-        tokio::spawn(async move {
-            use bollard::system::EventsOptions;
+        self.background_tasks.push(("health events".to_string(), tokio::spawn(async move {
+            use bollard::query_parameters::EventsOptions;
             let mut filters = HashMap::new();
             filters.insert("type".to_string(), vec!["container".to_string()]);
             filters.insert("event".to_string(), vec!["health_status".to_string()]);
             
             let options = EventsOptions {
-                filters,
+                filters: Some(filters),
                 ..Default::default()
             };
             
@@ -261,13 +974,13 @@ impl App {
                      }
                  }
             }
-        });
+        })));
 
         // Periodic Polling for Unhealthy containers (every 5s)
-        let docker_poll = app.docker.clone();
-        let health_map_poll = container_health.clone();
+        let docker_poll = self.docker.clone();
+        let health_map_poll = self.container_health.clone();
         
-        tokio::spawn(async move {
+        self.background_tasks.push(("health polling".to_string(), tokio::spawn(async move {
              loop {
                  tokio::time::sleep(Duration::from_secs(5)).await;
                  
@@ -289,14 +1002,16 @@ impl App {
                      });
                  }
              }
-        });
+        })));
 
         // --- Background Task 1.5: List Images (every 30s) ---
-        let docker_clone_images = app.docker.clone();
-        let images_clone = app.images.clone();
-        let show_dangling_clone = app.show_dangling.clone();
+        let docker_clone_images = self.docker.clone();
+        let images_clone = self.images.clone();
+        let show_dangling_clone = self.show_dangling.clone();
+        let errors_images = self.error_log.clone();
+        let updated_images = self.images_updated.clone();
 
-        tokio::spawn(async move {
+        self.background_tasks.push(("image list".to_string(), tokio::spawn(async move {
             loop {
                 tokio::time::sleep(Duration::from_secs(30)).await;
                 let show_dangling = show_dangling_clone.load(Ordering::Relaxed);
@@ -304,56 +1019,153 @@ impl App {
                     Ok(images_result) => {
                         let mut images = images_clone.write().unwrap();
                         *images = images_result;
+                        updated_images.store(Utc::now().timestamp(), Ordering::Relaxed);
                     }
                     Err(e) => {
-                        eprintln!("Failed to refresh images: {}", e);
+                        App::log_error(&errors_images, "image list", e.to_string());
                     }
                 }
             }
-        });
-        
-        // --- Background Task 4: Performance Monitoring ---
-        let perf_metrics_clone = perf_metrics.clone();
+        })));
         
-        std::thread::spawn(move || {
-            let mut sys = System::new();
-            let pid = Pid::from(std::process::id() as usize);
-            
-            loop {
-                sys.refresh_process(pid);
-                if let Some(process) = sys.process(pid) {
-                    let cpu = process.cpu_usage();
-                    let mem = process.memory();
-                    
-                    if let Ok(mut metrics) = perf_metrics_clone.write() {
-                        metrics.cpu_usage = cpu as f64;
-                        metrics.memory_usage = mem;
+        // --- Image scanner: scans images with no cached result, one at a time ---
+        if self.config.read().unwrap().scan.enabled {
+            let images_scan = self.images.clone();
+            let scans = self.image_scans.clone();
+            let config_scan = self.config.clone();
+            let errors_scan = self.error_log.clone();
+            *scans.write().unwrap() = crate::scan::load_cache();
+
+            self.background_tasks.push(("image scanner".to_string(), tokio::spawn(async move {
+                // Images the scanner failed on aren't retried until the next start
+                let mut failed = HashSet::new();
+                loop {
+                    let (command, cache_hours) = {
+                        let c = config_scan.read().unwrap();
+                        (c.scan.command.clone(), c.scan.cache_hours)
+                    };
+                    let now = Utc::now().timestamp();
+                    let pending: Vec<(String, String)> = {
+                        let scans = scans.read().unwrap();
+                        images_scan.read().unwrap().iter()
+                            .filter(|i| !failed.contains(&i.id))
+                            .filter(|i| !scans.get(&i.id).is_some_and(|s| s.is_fresh(cache_hours, now)))
+                            .map(|i| (i.id.clone(), crate::scan::scan_reference(i)))
+                            .collect()
+                    };
+                    for (id, reference) in pending {
+                        let started = std::time::Instant::now();
+                        match crate::scan::scan_image(&command, &reference).await {
+                            Ok(summary) => {
+                                tracing::debug!(image = %reference, elapsed_ms = started.elapsed().as_millis() as u64, "image scanned");
+                                let snapshot = {
+                                    let mut scans = scans.write().unwrap();
+                                    scans.insert(id, summary);
+                                    scans.clone()
+                                };
+                                if let Err(e) = crate::scan::save_cache(&snapshot) {
+                                    App::log_error(&errors_scan, "image scan", format!("{:#}", e));
+                                }
+                            }
+                            Err(e) => {
+                                App::log_error(&errors_scan, "image scan", format!("{}: {:#}", reference, e));
+                                failed.insert(id);
+                            }
+                        }
                     }
+                    tokio::time::sleep(Duration::from_secs(SCAN_CHECK_SECS)).await;
                 }
-                std::thread::sleep(Duration::from_secs(2));
-            }
-        });
+            })));
+        }
+
+        // --- Docker Hub rate limit: remaining pulls for the status bar ---
+        let limit_config = self.config.read().unwrap().hub_rate_limit;
+        if limit_config.enabled {
+            let hub_limit = self.hub_rate_limit.clone();
+            let errors_limit = self.error_log.clone();
+            let proxy = self.proxy_config();
+
+            self.background_tasks.push(("hub rate limit".to_string(), tokio::spawn(async move {
+                // Only the first failure in a row is logged, so an offline
+                // machine doesn't fill the error console
+                let mut failing = false;
+                loop {
+                    match crate::ratelimit::check_hub(&proxy).await {
+                        Ok(limit) => {
+                            *hub_limit.write().unwrap() = Some(limit);
+                            failing = false;
+                        }
+                        Err(e) if !failing => {
+                            App::log_error(&errors_limit, "hub rate limit", format!("{:#}", e));
+                            failing = true;
+                        }
+                        Err(e) => tracing::debug!(error = %e, "hub rate limit check failed"),
+                    }
+                    tokio::time::sleep(Duration::from_secs(limit_config.check_minutes.max(1) * 60)).await;
+                }
+            })));
+        }
+
+        // --- Extra hosts: poll each configured daemon's container list ---
+        let remotes: Vec<(String, DockerClient)> = self.remote_clients.iter().map(|(n, c)| (n.clone(), c.clone())).collect();
+        for (name, client) in remotes {
+            let containers_remote = self.containers.clone();
+            let show_all_remote = self.show_all.clone();
+            let health_map_remote = self.container_health.clone();
+            let errors_remote = self.error_log.clone();
+
+            self.background_tasks.push((format!("host {}", name), tokio::spawn(async move {
+                let mut reachable = true;
+                loop {
+                    match list_containers(&client, show_all_remote.load(Ordering::Relaxed)).await {
+                        Ok(list) => {
+                            if !reachable {
+                                tracing::info!(host = %name, "host reachable again");
+                            }
+                            App::sync_health(&client, &health_map_remote, &list);
+                            App::replace_host_containers(&containers_remote, Some(&name), list);
+                            reachable = true;
+                        }
+                        Err(e) => {
+                            // Once per outage rather than on every poll
+                            if reachable {
+                                App::log_error(&errors_remote, &format!("host {}", name), e.to_string());
+                            }
+                            reachable = false;
+                        }
+                    }
+                    tokio::time::sleep(Duration::from_secs(REMOTE_POLL_SECS)).await;
+                }
+            })));
+        }
 
         // --- Background Task 2: Fetch Stats (Dynamic Polling) ---
-        let docker_clone = app.docker.clone();
-        let containers_clone = containers.clone();
-        let stats_clone = container_stats.clone();
-        let viewport_clone = viewport_state.clone();
-        let config_clone = app.config.clone();
-        let perf_metrics_poll = app.perf_metrics.clone();
+        let docker_clone = self.docker.clone();
+        let containers_clone = self.containers.clone();
+        let stats_clone = self.container_stats.clone();
+        let viewport_clone = self.viewport_state.clone();
+        let config_clone = self.config.clone();
+        let perf_metrics_poll = self.perf_metrics.clone();
+        let errors_poll = self.error_log.clone();
+        let stats_refresh = self.stats_refresh.clone();
+        let remote_clients = self.remote_clients.clone();
+        let poll_all = self.poll_all_stats.clone();
+        let low_memory = self.low_memory;
         
-        tokio::spawn(async move {
+        self.background_tasks.push(("stats".to_string(), tokio::spawn(async move {
             let semaphore = Arc::new(Semaphore::new(10));
 
             loop {
                 let start_time = tokio::time::Instant::now();
                 
-                let (refresh_rate, poll_strategy, viewport_buffer) = {
+                let (refresh_rate, poll_strategy, viewport_buffer, limits) = {
                     let c = config_clone.read().unwrap();
-                    (c.refresh_rate.clone(), c.poll_strategy.clone(), c.viewport_buffer)
+                    (c.refresh_rate.clone(), c.poll_strategy.clone(), c.viewport_buffer, c.buffer_limits(low_memory))
                 };
 
                 let interval_ms = match refresh_rate {
+                    // A manual refresh polls everything at once
+                    RefreshRate::Manual if stats_refresh.swap(false, Ordering::Relaxed) => 0,
                     RefreshRate::Manual => {
                          tokio::time::sleep(Duration::from_millis(500)).await;
                          continue;
@@ -361,7 +1173,7 @@ impl App {
                     RefreshRate::Interval(d) => d.as_millis() as u64,
                 };
 
-                let targets: Vec<String> = {
+                let targets: Vec<(Option<String>, String)> = {
                     let containers = containers_clone.read().unwrap();
                     let total = containers.len();
                     
@@ -369,10 +1181,16 @@ impl App {
                         Vec::new()
                     } else {
                         match poll_strategy {
+                            _ if poll_all.load(Ordering::Relaxed) => {
+                                containers.iter()
+                                    .filter(|c| c.state == "running")
+                                    .map(|c| (c.host.clone(), c.id.clone()))
+                                    .collect()
+                            },
                             PollStrategy::AllContainers => {
                                 containers.iter()
                                     .filter(|c| c.state == "running")
-                                    .map(|c| c.id.clone())
+                                    .map(|c| (c.host.clone(), c.id.clone()))
                                     .collect()
                             },
                             PollStrategy::VisibleOnly => {
@@ -387,7 +1205,7 @@ impl App {
                                     containers[start..actual_end]
                                         .iter()
                                         .filter(|c| c.state == "running")
-                                        .map(|c| c.id.clone())
+                                        .map(|c| (c.host.clone(), c.id.clone()))
                                         .collect()
                                 }
                             }
@@ -409,10 +1227,11 @@ impl App {
 
                 let mut tasks = Vec::new();
 
-                for (i, id) in targets.into_iter().enumerate() {
-                    let docker = docker_clone.clone();
+                for (i, (host, id)) in targets.into_iter().enumerate() {
+                    let docker = host.as_deref().and_then(|h| remote_clients.get(h)).unwrap_or(&docker_clone).clone();
                     let stats_map = stats_clone.clone();
                     let sem = semaphore.clone();
+                    let errors_stats = errors_poll.clone();
                     
                     let delay = std::cmp::min(delay_per_req * i as u64, interval_ms);
 
@@ -434,25 +1253,25 @@ impl App {
                                         stats.cached_memory = cached_mem;
                                         stats.memory_limit = limit;
                                         stats.last_updated = now;
+                                        stats.sample_times.push(now);
                                         stats.cpu_history.push((cpu * 100.0) as u64);
-                                        stats.user_cpu_history.push((user_cpu * 100.0) as u64);
-                                        stats.system_cpu_history.push((system_cpu * 100.0) as u64);
                                         stats.memory_history.push(mem);
-                                        stats.cached_memory_history.push(cached_mem);
-                                        if stats.cpu_history.len() > 100 {
-                                            stats.cpu_history.remove(0);
-                                        }
-                                        if stats.user_cpu_history.len() > 100 {
-                                            stats.user_cpu_history.remove(0);
+                                        if limits.detail_series {
+                                            stats.user_cpu_history.push((user_cpu * 100.0) as u64);
+                                            stats.system_cpu_history.push((system_cpu * 100.0) as u64);
+                                            stats.cached_memory_history.push(cached_mem);
+                                        } else {
+                                            stats.user_cpu_history = Vec::new();
+                                            stats.system_cpu_history = Vec::new();
+                                            stats.cached_memory_history = Vec::new();
                                         }
-                                        if stats.system_cpu_history.len() > 100 {
-                                            stats.system_cpu_history.remove(0);
+                                        for series in [&mut stats.cpu_history, &mut stats.user_cpu_history, &mut stats.system_cpu_history, &mut stats.memory_history, &mut stats.cached_memory_history] {
+                                            if series.len() > limits.stats_samples {
+                                                series.drain(..series.len() - limits.stats_samples);
+                                            }
                                         }
-                                        if stats.memory_history.len() > 100 {
-                                            stats.memory_history.remove(0);
-                                        }
-                                        if stats.cached_memory_history.len() > 100 {
-                                            stats.cached_memory_history.remove(0);
+                                        if stats.sample_times.len() > limits.stats_samples {
+                                            stats.sample_times.drain(..stats.sample_times.len() - limits.stats_samples);
                                         }
                                     })
                                     .or_insert_with(|| ContainerStats {
@@ -463,22 +1282,24 @@ impl App {
                                         cached_memory: cached_mem,
                                         memory_limit: limit,
                                         cpu_history: vec![(cpu * 100.0) as u64],
-                                        user_cpu_history: vec![(user_cpu * 100.0) as u64],
-                                        system_cpu_history: vec![(system_cpu * 100.0) as u64],
+                                        user_cpu_history: if limits.detail_series { vec![(user_cpu * 100.0) as u64] } else { Vec::new() },
+                                        system_cpu_history: if limits.detail_series { vec![(system_cpu * 100.0) as u64] } else { Vec::new() },
                                         memory_history: vec![mem],
-                                        cached_memory_history: vec![cached_mem],
+                                        cached_memory_history: if limits.detail_series { vec![cached_mem] } else { Vec::new() },
+                                        sample_times: vec![now],
                                         last_updated: now,
                                     });
                             }
                             Ok(None) => {}
                             Err(e) => {
-                                eprintln!("Failed to fetch stats for {}: {}", id, e);
+                                App::log_error(&errors_stats, "stats", format!("{}: {}", id, e));
                             }
                         }
                     }));
                 }
                 
                 let elapsed = start_time.elapsed();
+                tracing::debug!(targets = target_count, elapsed_ms = elapsed.as_millis() as u64, "stats poll round");
                 
                 if let Ok(mut metrics) = perf_metrics_poll.write() {
                     metrics.poll_time_ms = elapsed.as_millis() as u64;
@@ -488,9 +1309,163 @@ impl App {
                     tokio::time::sleep(Duration::from_millis(interval_ms) - elapsed).await;
                 }
             }
-        });
-        
-        Ok(app)
+        })));
+
+        // --- Connection watchdog: detects a lost daemon and retries with backoff ---
+        let docker_watch = self.docker.clone();
+        let connection = self.connection.clone();
+        let errors_watch = self.error_log.clone();
+
+        self.background_tasks.push(("connection watchdog".to_string(), tokio::spawn(async move {
+            let mut backoff = 1;
+            loop {
+                let reachable = matches!(
+                    tokio::time::timeout(Duration::from_secs(5), docker_watch.ping()).await,
+                    Ok(Ok(()))
+                );
+                let delay = {
+                    let mut state = connection.write().unwrap();
+                    let now = Utc::now().timestamp();
+                    if reachable {
+                        if let Some(since) = state.down_since.take() {
+                            tracing::info!(down_secs = now - since, attempts = state.attempts, "daemon reachable again");
+                            state.recovered = true;
+                        }
+                        state.attempts = 0;
+                        backoff = 1;
+                        CONNECTION_CHECK_SECS
+                    } else {
+                        if state.down_since.is_none() {
+                            App::log_error(&errors_watch, "connection", format!("{} is not responding", docker_watch.endpoint));
+                            state.down_since = Some(now);
+                        }
+                        state.attempts += 1;
+                        let delay = backoff;
+                        backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF_SECS);
+                        state.next_retry = now + delay as i64;
+                        delay
+                    }
+                };
+                tokio::time::sleep(Duration::from_secs(delay)).await;
+            }
+        })));
+
+        tracing::info!(
+            context = %self.docker_context,
+            tasks = self.background_tasks.len(),
+            remote_hosts = self.remote_clients.len(),
+            "background tasks started"
+        );
+    }
+
+
+    /// Marks the current view as refreshing; the event loop picks it up after the next draw.
+    pub fn request_refresh(&mut self) {
+        self.view_refresh.write().unwrap().entry(self.current_view).or_default().started = Some(std::time::Instant::now());
+        self.pending_refresh = true;
+    }
+
+    fn finish_refresh(view_refresh: &Arc<RwLock<HashMap<View, ViewRefresh>>>, view: View) {
+        let mut view_refresh = view_refresh.write().unwrap();
+        let entry = view_refresh.entry(view).or_default();
+        entry.started = None;
+        entry.finished_at = Some(Utc::now().timestamp());
+    }
+
+    /// Reloads the current view and the panes that depend on it, regardless of
+    /// the refresh rate. The System analysis finishes in the background.
+    pub async fn refresh_current_view(&mut self) {
+        self.pending_refresh = false;
+        let view = self.current_view;
+        let started = std::time::Instant::now();
+        match view {
+            View::Containers => {
+                if let Err(e) = self.refresh_containers().await {
+                    App::log_error(&self.error_log, "container list", e.to_string());
+                }
+                let containers = self.containers.read().unwrap().clone();
+                App::sync_health(&self.docker, &self.container_health, &containers);
+                self.stats_refresh.store(true, Ordering::Relaxed);
+                // Forces the next debounced fetch to reload details and logs
+                self.last_fetched_id = None;
+            }
+            View::Top => {
+                if let Err(e) = self.refresh_containers().await {
+                    App::log_error(&self.error_log, "container list", e.to_string());
+                }
+                self.stats_refresh.store(true, Ordering::Relaxed);
+            }
+            View::Images => {
+                if let Err(e) = self.refresh_images().await {
+                    App::log_error(&self.error_log, "image list", e.to_string());
+                }
+                self.trigger_image_details(false);
+            }
+            View::Services => self.refresh_services().await,
+            View::Stacks => self.refresh_stacks().await,
+            View::Nodes => self.refresh_nodes().await,
+            View::System => {
+                let analysis = App::spawn_system_analysis(self.docker.clone(), self.system_report.clone(), self.system_error.clone());
+                let view_refresh = self.view_refresh.clone();
+                tokio::spawn(async move {
+                    let _ = analysis.await;
+                    App::finish_refresh(&view_refresh, View::System);
+                });
+                return;
+            }
+        }
+        tracing::debug!(?view, elapsed_ms = started.elapsed().as_millis() as u64, "manual refresh");
+        App::finish_refresh(&self.view_refresh, view);
+    }
+
+    /// Connects the extra hosts from the config; one with an unusable endpoint is
+    /// reported in the error console and left out.
+    async fn connect_remote_hosts(&mut self, hosts: Vec<HostConfig>) {
+        let timeouts = self.config.read().unwrap().timeouts;
+        for host in hosts {
+            match DockerClient::connect(&host.host, host.tls.as_ref()) {
+                Ok(client) => {
+                    self.remote_clients.insert(host.name, client.with_timeouts(timeouts));
+                }
+                Err(e) => App::log_error(&self.error_log, &format!("host {}", host.name), e.to_string()),
+            }
+        }
+        // Side by side, so an unreachable host only costs one probe timeout
+        futures::future::join_all(self.remote_clients.values_mut().map(|client| client.detect_engine())).await;
+    }
+
+    /// Swaps in a fresh container list for one host (`None` = primary), keeping
+    /// the entries of every other host.
+    fn replace_host_containers(list: &Arc<RwLock<Vec<ContainerInfo>>>, host: Option<&str>, mut containers: Vec<ContainerInfo>) {
+        for c in &mut containers {
+            c.host = host.map(str::to_string);
+        }
+        let mut list = list.write().unwrap();
+        list.retain(|c| c.host.as_deref() != host);
+        list.extend(containers);
+    }
+
+    /// Client for the daemon that runs `container_id`.
+    pub fn docker_for(&self, container_id: &str) -> DockerClient {
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        self.docker_for_host(host.as_deref())
+    }
+
+    pub fn docker_for_host(&self, host: Option<&str>) -> DockerClient {
+        host.and_then(|h| self.remote_clients.get(h)).unwrap_or(&self.docker).clone()
+    }
+
+    /// HOST column value: the extra host's name, or the active context for the primary.
+    pub fn host_label<'a>(&'a self, container: &'a ContainerInfo) -> &'a str {
+        container.host.as_deref().unwrap_or(&self.docker_context)
+    }
+
+    /// Name of a listed container, or its short ID when it isn't in the list.
+    pub fn container_label(&self, container_id: &str) -> String {
+        self.containers.read().unwrap().iter()
+            .find(|c| c.id == container_id)
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| container_id.chars().take(12).collect())
     }
 
     pub async fn refresh_containers(&mut self) -> Result<()> {
@@ -509,19 +1484,98 @@ impl App {
             }
         }
 
-        let mut containers = self.containers.write().unwrap();
-        *containers = containers_result;
-        drop(containers);
+        App::replace_host_containers(&self.containers, None, containers_result);
+        self.containers_updated.store(Utc::now().timestamp(), Ordering::Relaxed);
         
         self.update_filtered_containers();
         Ok(())
     }
 
+    /// Merges `entries` into a container's timeline, dropping duplicates and the
+    /// oldest entries beyond `max_events`.
+    fn record_lifecycle(timelines: &Arc<RwLock<HashMap<String, VecDeque<LifecycleEvent>>>>, id: &str, entries: Vec<LifecycleEvent>, max_events: usize) {
+        let mut timelines = timelines.write().unwrap();
+        let timeline = timelines.entry(id.to_string()).or_default();
+        for entry in entries {
+            if !timeline.contains(&entry) {
+                timeline.push_back(entry);
+            }
+        }
+        timeline.make_contiguous().sort_by_key(|e| e.time_nano);
+        while timeline.len() > max_events {
+            timeline.pop_front();
+        }
+    }
+
+    /// Opens the lifecycle timeline for the selected container and backfills it
+    /// from the daemon's recent event history.
+    pub fn open_timeline(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let docker = self.docker_for(&container.id);
+        let timelines = self.container_timelines.clone();
+        let toast = self.toast.clone();
+        let id = container.id.clone();
+        let max_events = self.config.read().unwrap().buffer_limits(self.low_memory).timeline_events;
+        tokio::spawn(async move {
+            let since = Utc::now().timestamp() - TIMELINE_BACKFILL_HOURS * 3600;
+            match container_history(&docker, &id, since).await {
+                Ok(entries) => App::record_lifecycle(&timelines, &id, entries, max_events),
+                Err(e) => *toast.write().unwrap() = Some(Toast::new(format!("Failed to load event history: {}", e), true)),
+            }
+        });
+        self.push_modal(Modal::Timeline { container_id: container.id, container_name: container.name });
+    }
+
+    /// Refreshes health info for running containers whose status text suggests
+    /// a different health state than the one we have cached.
+    fn sync_health(docker: &DockerClient, health_map: &Arc<RwLock<HashMap<String, ContainerHealth>>>, containers: &[ContainerInfo]) {
+        let map = health_map.read().unwrap();
+        for c in containers {
+            if c.state != "running" { continue; }
+
+            let new_status = parse_health_status_from_string(&c.status);
+            let needs_update = match map.get(&c.id) {
+                Some(current) => current.status != new_status,
+                None => true,
+            };
+
+            if needs_update {
+                let docker = docker.clone();
+                let health_map_inner = health_map.clone();
+                let id = c.id.clone();
+                tokio::spawn(async move {
+                    if let Ok(health) = fetch_health_info(&docker, &id).await {
+                        health_map_inner.write().unwrap().insert(id, health);
+                    }
+                });
+            }
+        }
+    }
+
     pub fn update_filtered_containers(&mut self) {
+        // Live sorts reorder rows on every refresh; the selection follows its container
+        let selected_id = self.selected_container().map(|c| c.id);
         let containers = self.containers.read().unwrap();
         let health = self.container_health.read().unwrap();
         
+        let query = self.container_filter.value();
+        let case = self.config.read().unwrap().search_case;
+        let mut hidden_count = 0;
         let mut filtered: Vec<ContainerInfo> = containers.iter().filter(|c| {
+             if !self.show_hidden && self.is_hidden(c) {
+                 hidden_count += 1;
+                 return false;
+             }
+             // Pinned containers stay listed whatever the filters
+             if self.is_pinned(c) {
+                 return true;
+             }
+             if !query.is_empty() && search_score(c, query, case).is_none() {
+                 return false;
+             }
+             if self.state_filter.is_some_and(|f| !f.matches(c)) {
+                 return false;
+             }
              match self.health_filter {
                  HealthFilter::All => true,
                  HealthFilter::Unhealthy => {
@@ -542,8 +1596,8 @@ impl App {
         }).cloned().collect();
         
         match self.container_sort {
-            SortOrder::CreatedDesc => filtered.sort_by(|a, b| b.created.cmp(&a.created)),
-            SortOrder::CreatedAsc => filtered.sort_by(|a, b| a.created.cmp(&b.created)),
+            SortOrder::CreatedDesc => filtered.sort_by_key(|c| std::cmp::Reverse(c.created)),
+            SortOrder::CreatedAsc => filtered.sort_by_key(|c| c.created),
             SortOrder::HealthDesc => {
                 filtered.sort_by(|a, b| {
                     let ha = health.get(&a.id).map(|h| &h.status).unwrap_or(&HealthStatus::NoHealthCheck);
@@ -558,18 +1612,75 @@ impl App {
                     ha.cmp(hb)
                 });
             }
+            SortOrder::CpuDesc | SortOrder::MemDesc => {
+                let stats = self.container_stats.read().unwrap();
+                // Stopped containers keep stale stats; they sort with the ones without any
+                let live = |c: &ContainerInfo| stats.get(&c.id).filter(|_| c.state == "running");
+                if self.container_sort == SortOrder::CpuDesc {
+                    filtered.sort_by(|a, b| {
+                        let cpu = |c| live(c).map(|s| s.cpu_percent).unwrap_or(-1.0);
+                        cpu(b).total_cmp(&cpu(a))
+                    });
+                } else {
+                    filtered.sort_by_key(|c| std::cmp::Reverse(live(c).map(|s| s.memory_usage)));
+                }
+            }
+            SortOrder::NameAsc => filtered.sort_by_cached_key(|c| c.name.to_lowercase()),
+            // Running first, longest up first; uptime counts from creation as in the UP column
+            SortOrder::UptimeDesc => filtered.sort_by_key(|c| (c.state != "running", c.created)),
             _ => {
-                 filtered.sort_by(|a, b| b.created.cmp(&a.created));
+                 filtered.sort_by_key(|c| std::cmp::Reverse(c.created));
+            }
+        }
+
+        // Stable, so pinned containers keep the chosen order among themselves
+        filtered.sort_by_key(|c| !self.is_pinned(c));
+
+        let mut ranked: Vec<(i64, &ContainerInfo)> = filtered.iter()
+            .filter_map(|c| search_score(c, query, case).map(|score| (score, c)))
+            .collect();
+        ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.search_matches = ranked.into_iter().map(|(_, c)| c.id.clone()).collect();
+
+        match &self.group_by {
+            Some(group_by) => {
+                // Stable, so each group keeps the sort order; the ungrouped go last
+                filtered.sort_by(|a, b| match (group_by.key(a), group_by.key(b)) {
+                    (Some(a), Some(b)) => a.cmp(b),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                });
+                self.list_rows.clear();
+                let mut start = 0;
+                while start < filtered.len() {
+                    let key = group_by.key(&filtered[start]);
+                    let len = filtered[start..].iter().take_while(|c| group_by.key(c) == key).count();
+                    let key = key.unwrap_or(UNGROUPED).to_string();
+                    let collapsed = self.collapsed_groups.contains(&key);
+                    let running = filtered[start..start + len].iter().filter(|c| c.state == "running").count();
+                    self.list_rows.push(ListRow::Group { key, count: len, running, collapsed });
+                    if !collapsed {
+                        self.list_rows.extend((start..start + len).map(ListRow::Container));
+                    }
+                    start += len;
+                }
             }
+            None => self.list_rows = (0..filtered.len()).map(ListRow::Container).collect(),
         }
 
         self.filtered_containers = filtered;
         self.total_containers = self.filtered_containers.len();
+        self.hidden_count = hidden_count;
 
-        if self.total_containers > 0 {
+        let followed = selected_id.and_then(|id| {
+            let index = self.filtered_containers.iter().position(|c| c.id == id)?;
+            self.list_rows.iter().position(|r| *r == ListRow::Container(index))
+        });
+        if let Some(row) = followed {
+            self.table_state.select(Some(row));
+        } else if !self.list_rows.is_empty() {
              if let Some(selected) = self.table_state.selected() {
-                 if selected >= self.total_containers {
-                     self.table_state.select(Some(self.total_containers - 1));
+                 if selected >= self.list_rows.len() {
+                     self.table_state.select(Some(self.list_rows.len() - 1));
                  }
              } else {
                  self.table_state.select(Some(0));
@@ -583,12 +1694,21 @@ impl App {
         self.container_sort = match self.container_sort {
             SortOrder::CreatedDesc => SortOrder::CreatedAsc,
             SortOrder::CreatedAsc => SortOrder::HealthAsc,
-            SortOrder::HealthAsc => SortOrder::CreatedDesc,
+            SortOrder::HealthAsc => SortOrder::CpuDesc,
+            SortOrder::CpuDesc => SortOrder::MemDesc,
+            SortOrder::MemDesc => SortOrder::NameAsc,
+            SortOrder::NameAsc => SortOrder::UptimeDesc,
+            SortOrder::UptimeDesc => SortOrder::CreatedDesc,
             _ => SortOrder::CreatedDesc,
         };
         self.update_filtered_containers();
     }
 
+    pub fn clear_container_filter(&mut self) {
+        self.container_filter.clear();
+        self.update_filtered_containers();
+    }
+
     pub fn toggle_health_filter(&mut self) {
         self.health_filter = match self.health_filter {
             HealthFilter::All => HealthFilter::Unhealthy,
@@ -598,13 +1718,109 @@ impl App {
         self.update_filtered_containers();
     }
 
+    /// Applies the filter behind a status bar badge, or clears it when it is
+    /// already applied. Stopped containers are only listed with `f` (all) on,
+    /// so picking them turns it on.
+    pub async fn toggle_state_filter(&mut self, filter: StateFilter) {
+        if filter == StateFilter::Unhealthy {
+            let active = self.health_filter == HealthFilter::Unhealthy;
+            self.health_filter = if active { HealthFilter::All } else { HealthFilter::Unhealthy };
+            self.state_filter = None;
+        } else {
+            self.state_filter = (self.state_filter != Some(filter)).then_some(filter);
+            self.health_filter = HealthFilter::All;
+        }
+        if self.state_filter == Some(StateFilter::Stopped) && !self.show_all.load(Ordering::Relaxed) {
+            self.show_all.store(true, Ordering::Relaxed);
+            if let Err(e) = self.refresh_containers().await {
+                self.show_toast(format!("Failed to list containers: {}", e), true);
+            }
+        }
+        self.update_filtered_containers();
+    }
+
+    /// Selects the search match `step` places from the selected one in match
+    /// order, wrapping around; from no match, the best one.
+    pub fn select_search_match(&mut self, step: isize) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let len = self.search_matches.len() as isize;
+        let current = self.selected_container()
+            .and_then(|c| self.search_matches.iter().position(|id| *id == c.id));
+        let i = match current {
+            Some(i) => (i as isize + step).rem_euclid(len) as usize,
+            None => 0,
+        };
+        self.select_container_row(&self.search_matches[i].clone());
+    }
+
+    /// Selects the best search match, as the search is typed.
+    pub fn select_best_search_match(&mut self) {
+        match self.search_matches.first().cloned() {
+            Some(id) => { self.select_container_row(&id); }
+            None => self.table_state.select((!self.list_rows.is_empty()).then_some(0)),
+        }
+    }
+
+    /// Selects the row of container `id`, expanding its group if it is
+    /// collapsed. Returns false if the filters hide it.
+    fn select_container_row(&mut self, id: &str) -> bool {
+        let Some(index) = self.filtered_containers.iter().position(|c| c.id == id) else { return false };
+        if let Some(key) = self.group_by.as_ref().map(|g| g.key(&self.filtered_containers[index]).unwrap_or(UNGROUPED).to_string()) {
+            if self.collapsed_groups.remove(&key) {
+                self.update_filtered_containers();
+            }
+        }
+        let row = self.list_rows.iter().position(|r| *r == ListRow::Container(index));
+        self.table_state.select(row);
+        row.is_some()
+    }
+
+    /// The container shown in table row `row`; `None` for a group header.
+    pub fn container_at_row(&self, row: usize) -> Option<&ContainerInfo> {
+        match self.list_rows.get(row)? {
+            ListRow::Container(i) => self.filtered_containers.get(*i),
+            ListRow::Group { .. } => None,
+        }
+    }
+
+    /// Groups the list by `group_by`, or flattens it again with `None`.
+    pub fn set_group_by(&mut self, group_by: Option<GroupBy>) {
+        let selected = self.selected_container().map(|c| c.id);
+        self.group_by = group_by;
+        self.collapsed_groups.clear();
+        self.update_filtered_containers();
+        match selected {
+            Some(id) => { self.select_container_row(&id); }
+            None => self.table_state.select((!self.list_rows.is_empty()).then_some(0)),
+        }
+    }
+
+    /// Collapses or expands the selected group; on a container row, collapses
+    /// the group it is in and selects the group's header.
+    pub fn toggle_selected_group(&mut self) {
+        let Some(selected) = self.table_state.selected() else { return };
+        let header = self.list_rows.iter().take(selected + 1)
+            .rposition(|r| matches!(r, ListRow::Group { .. }));
+        let Some(header) = header else { return };
+        let ListRow::Group { key, collapsed, .. } = &self.list_rows[header] else { return };
+        if *collapsed {
+            self.collapsed_groups.remove(key);
+        } else {
+            self.collapsed_groups.insert(key.clone());
+        }
+        self.update_filtered_containers();
+        self.table_state.select(Some(header));
+    }
+
     pub fn next(&mut self) {
-        if self.total_containers == 0 {
+        if self.list_rows.is_empty() {
             return;
         }
         let i = match self.table_state.selected() {
             Some(i) => {
-                if i >= self.total_containers - 1 {
+                if i >= self.list_rows.len() - 1 {
                     0
                 } else {
                     i + 1
@@ -616,13 +1832,13 @@ impl App {
     }
 
     pub fn previous(&mut self) {
-        if self.total_containers == 0 {
+        if self.list_rows.is_empty() {
             return;
         }
         let i = match self.table_state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.total_containers - 1
+                    self.list_rows.len() - 1
                 } else {
                     i - 1
                 }
@@ -635,261 +1851,3203 @@ impl App {
     pub fn selected_container(&self) -> Option<ContainerInfo> {
         self.table_state
             .selected()
-            .and_then(|i| self.filtered_containers.get(i).cloned())
+            .and_then(|i| self.container_at_row(i).cloned())
     }
 
-    pub fn trigger_fetch(&mut self, container_id: String) {
-        if self.last_fetched_id.as_ref() == Some(&container_id) {
-            return;
+    /// Panes of the containers view in Tab order; graphs only exist in the detailed stats view.
+    pub fn focus_order(&self) -> Vec<Focus> {
+        let mut order = vec![Focus::ContainerList, Focus::Logs, Focus::Details];
+        if self.config.read().unwrap().stats_view == StatsView::Detailed {
+            order.push(Focus::Graphs);
         }
-        
-        self.last_fetched_id = Some(container_id.clone());
-        
-        {
-            let mut details = self.selected_container_details.write().unwrap();
-            *details = None;
-            let mut logs = self.selected_container_logs.write().unwrap();
-            logs.clear();
+        order
+    }
+
+    pub fn cycle_focus(&mut self, forward: bool) {
+        let order = self.focus_order();
+        self.focus.set_order(order);
+        if forward {
+            self.focus.next();
+        } else {
+            self.focus.prev();
         }
+    }
 
-        let docker = self.docker.clone();
-        let details_lock = self.selected_container_details.clone();
-        let id_clone = container_id.clone();
+    /// Shows the quick-info popup for `row`, fetching its last log lines in the background.
+    pub fn show_preview(&mut self, row: usize) {
+        let Some(container) = self.container_at_row(row).cloned() else {
+            self.preview = None;
+            return;
+        };
+        if self.preview.as_ref().is_some_and(|p| p.container_id == container.id) {
+            return;
+        }
 
+        let logs = Arc::new(RwLock::new(Vec::new()));
+        let docker = self.docker_for(&container.id);
+        let id = container.id.clone();
+        let logs_clone = logs.clone();
         tokio::spawn(async move {
-            let details_res = inspect_container(&docker, &id_clone).await;
-            let details_str = match details_res {
-                Ok(info) => format_details(info),
-                Err(e) => format!("Error fetching details: {}", e),
-            };
-            *details_lock.write().unwrap() = Some(details_str);
+            if let Ok(lines) = fetch_recent_logs(&docker, &id, 3).await {
+                *logs_clone.write().unwrap() = lines;
+            }
         });
 
-        self.start_log_stream(container_id);
+        self.preview = Some(Preview { container_id: container.id.clone(), row, logs });
     }
 
-    fn start_log_stream(&mut self, container_id: String) {
-        if let Some(handle) = self.log_stream_task.take() {
-            handle.abort();
+    /// Maps a terminal position to a row of the container table, if it is over one.
+    pub fn container_row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.container_list_area;
+        // Skip the top border and the header row
+        let first_row = area.y + 2;
+        let last_row = area.y + area.height.saturating_sub(1);
+        if column <= area.x || column >= area.x + area.width.saturating_sub(1) || row < first_row || row >= last_row {
+            return None;
         }
+        let index = self.table_state.offset() + (row - first_row) as usize;
+        (index < self.list_rows.len()).then_some(index)
+    }
 
-        let docker = self.docker.clone();
-        let logs_lock = self.selected_container_logs.clone();
-        
-        let task = tokio::spawn(async move {
-            let mut stream = stream_logs(&docker, &container_id, "100");
-            
-            while let Some(log_result) = stream.next().await {
-                match log_result {
-                    Ok(log) => {
-                        let mut logs = logs_lock.write().unwrap();
-                        logs.push(log.to_string());
-                        if logs.len() > 1000 {
-                            logs.remove(0);
-                        }
-                    }
-                    Err(_) => break,
-                }
+    /// Opens the signals panel: the configured stop signal and grace period,
+    /// plus a picker to send any signal to the selected container.
+    pub async fn open_signal_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let docker = self.docker_for(&container.id);
+        let info = match inspect_container(&docker, &container.id).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.show_toast(format!("Failed to inspect {}: {}", container.name, e), true);
+                return;
             }
-        });
-        
-        self.log_stream_task = Some(task);
-    }
+        };
+        let config = info.config.unwrap_or_default();
+        let image_signal = match info.image.as_deref() {
+            Some(image) => inspect_image(&docker, image).await.ok()
+                .and_then(|i| i.config?.stop_signal),
+            None => None,
+        };
+        let stop_signal = match (config.stop_signal, image_signal) {
+            (Some(signal), Some(image)) if signal == image => format!("{} (from image STOPSIGNAL)", signal),
+            (Some(signal), _) => format!("{} (container override)", signal),
+            (None, _) => "SIGTERM (default)".to_string(),
+        };
+        let grace = match config.stop_timeout {
+            Some(secs) => format!("{}s", secs),
+            None => "10s (default)".to_string(),
+        };
 
-    pub async fn restart_container(&mut self) -> Result<()> {
-        if let Some(container) = self.selected_container() {
-            restart_container(&self.docker, &container.id).await?;
+        let signals = ["SIGTERM", "SIGHUP", "SIGINT", "SIGQUIT", "SIGUSR1", "SIGUSR2", "SIGKILL", "SIGSTOP", "SIGCONT"];
+        let mut form = Form::new(format!("Signals: {}", container.name))
+            .label(format!("Stop signal: {}", stop_signal))
+            .label(format!("Stop grace period: {} before SIGKILL", grace));
+        if container.state != "running" {
+            form = form.label(format!("{} is {}; the daemon only delivers signals to running containers", container.name, container.state));
         }
-        Ok(())
+        let form = form
+            .select("signal", "Send signal", signals.iter().map(|s| s.to_string()).collect(), 0)
+            .text("custom", "Or custom signal (name or number)", TextInput::with_validator(validate_signal));
+        self.push_modal(Modal::SendSignal { container_id: container.id, container_name: container.name, form });
     }
 
-    pub async fn stop_container(&mut self) -> Result<()> {
-        if let Some(container) = self.selected_container() {
-            stop_container(&self.docker, &container.id).await?;
+    pub async fn send_signal(&self, container_id: &str, container_name: &str, signal: &str) {
+        match kill_container(&self.docker_for(container_id), container_id, signal).await {
+            Ok(()) => self.show_toast(format!("Sent {} to {}", signal, container_name), false),
+            Err(e) => self.show_toast(format!("Failed to send {} to {}: {}", signal, container_name, e), true),
         }
-        Ok(())
     }
 
-    pub async fn start_container(&mut self) -> Result<()> {
-        if let Some(container) = self.selected_container() {
-            start_container(&self.docker, &container.id).await?;
+    /// Opens the stdin prompt for the selected container, which must have been
+    /// started with stdin open (`-i` / `stdin_open: true`).
+    pub async fn open_stdin_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        if container.state != "running" {
+            self.show_toast(format!("{} is not running", container.name), true);
+            return;
         }
-        Ok(())
-    }
-
-    pub async fn remove_container(&mut self) -> Result<()> {
-        if let Some(container) = self.selected_container() {
-            remove_container(&self.docker, &container.id).await?;
-            self.refresh_containers().await?;
-            if self.total_containers > 0 && self.table_state.selected().unwrap_or(0) >= self.total_containers {
-                 self.table_state.select(Some(self.total_containers - 1));
+        let info = match inspect_container(&self.docker_for(&container.id), &container.id).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.show_toast(format!("Failed to inspect {}: {}", container.name, e), true);
+                return;
             }
+        };
+        let config = info.config.unwrap_or_default();
+        if !config.open_stdin.unwrap_or(false) {
+            self.show_toast(format!("{} has no open stdin (start it with -i)", container.name), true);
+            return;
         }
-        Ok(())
-    }
-
-    pub async fn pause_container(&mut self) -> Result<()> {
-        if let Some(container) = self.selected_container() {
-            if container.state == "running" {
-                pause_container(&self.docker, &container.id).await?;
-                self.refresh_containers().await?;
-            }
+        let mut form = Form::new(format!("Send to stdin: {}", container.name))
+            .text("line", "Line to send (a newline is added)", TextInput::new())
+            .text("file", "Or send the contents of a file", TextInput::new());
+        if config.stdin_once.unwrap_or(false) {
+            form = form.label("Stdin closes after the first send (attached once)");
         }
-        Ok(())
+        self.push_modal(Modal::SendStdin { container_id: container.id, container_name: container.name, form });
     }
 
-    pub async fn unpause_container(&mut self) -> Result<()> {
-        if let Some(container) = self.selected_container() {
-            if container.state == "paused" {
-                unpause_container(&self.docker, &container.id).await?;
-                self.refresh_containers().await?;
+    /// Sends `file`'s contents when a path is given, otherwise `line` and a newline.
+    pub async fn send_to_stdin(&self, container_id: &str, container_name: &str, line: &str, file: &str) {
+        let input = if file.is_empty() {
+            format!("{}\n", line).into_bytes()
+        } else {
+            match tokio::fs::read(file).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    self.show_toast(format!("Failed to read {}: {}", file, e), true);
+                    return;
+                }
             }
+        };
+        match send_stdin(&self.docker_for(container_id), container_id, &input).await {
+            Ok(()) => self.show_toast(format!("Sent {} bytes to {}", input.len(), container_name), false),
+            Err(e) => self.show_toast(format!("Failed to write to {}: {}", container_name, e), true),
         }
-        Ok(())
     }
 
-    pub async fn refresh_images(&mut self) -> Result<()> {
-        let show_dangling = self.show_dangling.load(Ordering::Relaxed);
-        let images_result = list_images(&self.docker, show_dangling).await?;
-        
-        self.total_images = images_result.len();
-        self.total_image_size = images_result.iter().map(|i| i.size as u64).sum();
-
-        let mut images = self.images.write().unwrap();
-        *images = images_result;
-        
-        match self.image_sort {
-            SortOrder::CreatedDesc => images.sort_by(|a, b| b.created.cmp(&a.created)),
-            SortOrder::CreatedAsc => images.sort_by(|a, b| a.created.cmp(&b.created)),
-            SortOrder::SizeDesc => images.sort_by(|a, b| b.size.cmp(&a.size)),
-            SortOrder::SizeAsc => images.sort_by(|a, b| a.size.cmp(&b.size)),
-            SortOrder::HealthDesc | SortOrder::HealthAsc => {
-                images.sort_by(|a, b| b.created.cmp(&a.created));
+    pub fn open_context_picker(&mut self) {
+        match list_contexts() {
+            Ok(contexts) => {
+                let selected = contexts.iter().position(|c| c.name == self.docker_context).unwrap_or(0);
+                self.push_modal(Modal::ContextPicker { contexts, selected });
             }
+            Err(e) => self.show_toast(format!("Failed to read Docker contexts: {}", e), true),
         }
-        Ok(())
     }
 
-    pub fn cycle_sort(&mut self) {
-        self.image_sort = match self.image_sort {
-            SortOrder::CreatedDesc => SortOrder::CreatedAsc,
-            SortOrder::CreatedAsc => SortOrder::SizeDesc,
-            SortOrder::SizeDesc => SortOrder::SizeAsc,
-            SortOrder::SizeAsc => SortOrder::CreatedDesc,
-            _ => SortOrder::CreatedDesc,
-        };
+    /// Pinned by name with `*`, or by a pattern of the active workspace.
+    pub fn is_pinned(&self, container: &ContainerInfo) -> bool {
+        self.config.read().unwrap().pinned.contains(&container.name)
+            || self.pinned.iter().any(|p| glob_match(p, &container.name) || glob_match(p, &container.image))
     }
 
-    pub fn next_image(&mut self) {
-        if self.total_images == 0 { return; }
-        let i = match self.table_state_images.selected() {
-            Some(i) => if i >= self.total_images - 1 { 0 } else { i + 1 },
-            None => 0,
-        };
-        self.table_state_images.select(Some(i));
+    pub fn is_hidden(&self, container: &ContainerInfo) -> bool {
+        self.config.read().unwrap().hidden.iter().any(|p| glob_match(p, &container.name))
     }
 
-    pub fn previous_image(&mut self) {
-        if self.total_images == 0 { return; }
-        let i = match self.table_state_images.selected() {
-            Some(i) => if i == 0 { self.total_images - 1 } else { i - 1 },
-            None => 0,
+    /// Adds the selected container's name to the hide list, or takes it off
+    /// while hidden containers are revealed, and saves the config.
+    pub fn toggle_hidden(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let message = {
+            let mut config = self.config.write().unwrap();
+            if let Some(i) = config.hidden.iter().position(|name| *name == container.name) {
+                config.hidden.remove(i);
+                format!("{} is no longer hidden", container.name)
+            } else if let Some(pattern) = config.hidden.iter().find(|p| glob_match(p, &container.name)) {
+                format!("{} is hidden by '{}'; edit hidden in config.toml to change it", container.name, pattern)
+            } else {
+                config.hidden.push(container.name.clone());
+                if self.show_hidden {
+                    format!("Hid {}", container.name)
+                } else {
+                    format!("Hid {}; . reveals hidden containers", container.name)
+                }
+            }
         };
-        self.table_state_images.select(Some(i));
+        self.save_config();
+        self.update_filtered_containers();
+        self.show_toast(message, false);
     }
 
-    pub fn selected_image(&self) -> Option<ImageInfo> {
-        self.images.read().unwrap().get(self.table_state_images.selected()?).cloned()
+    pub fn toggle_show_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        let selected = self.selected_container().map(|c| c.id);
+        self.update_filtered_containers();
+        if let Some(id) = selected {
+            self.select_container_row(&id);
+        }
     }
 
-    pub fn trigger_image_details(&mut self) {
-        if let Some(image) = self.selected_image() {
-            let docker = self.docker.clone();
-            let details_lock = self.selected_image_details.clone();
-            tokio::spawn(async move {
-                match inspect_image(&docker, &image.id).await {
-                    Ok(info) => *details_lock.write().unwrap() = Some(format_image_details(info)),
-                    Err(e) => *details_lock.write().unwrap() = Some(format!("Error: {}", e)),
+    /// Pins the selected container by name, or unpins it, and saves the config.
+    pub fn toggle_pin(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let pinned = {
+            let mut config = self.config.write().unwrap();
+            match config.pinned.iter().position(|name| *name == container.name) {
+                Some(i) => {
+                    config.pinned.remove(i);
+                    false
                 }
-            });
-        }
+                None => {
+                    config.pinned.push(container.name.clone());
+                    true
+                }
+            }
+        };
+        self.save_config();
+        self.update_filtered_containers();
+        self.select_container_row(&container.id);
+        let message = if pinned {
+            format!("Pinned {}", container.name)
+        } else if self.is_pinned(&container) {
+            format!("{} stays pinned by the workspace", container.name)
+        } else {
+            format!("Unpinned {}", container.name)
+        };
+        self.show_toast(message, false);
     }
 
-    pub async fn remove_current_image(&mut self, force: bool) -> Result<()> {
-        if let Some(image) = self.selected_image() {
-            remove_image(&self.docker, &image.id, force).await?;
-            self.refresh_images().await?;
+    pub fn open_workspace_picker(&mut self) {
+        let workspaces = self.config.read().unwrap().workspaces.clone();
+        if workspaces.is_empty() {
+            self.show_toast("No workspaces in config.toml".to_string(), true);
+            return;
         }
-        Ok(())
+        let selected = workspaces.iter().position(|w| Some(&w.name) == self.workspace.as_ref()).unwrap_or(0);
+        self.push_modal(Modal::WorkspacePicker { workspaces, selected });
     }
 
-    pub async fn prune_images(&mut self) -> Result<()> {
-        prune_images(&self.docker).await?;
-        self.refresh_images().await?;
-        Ok(())
+    /// Looks up a workspace from the config by name, for `--workspace`.
+    pub fn find_workspace(&self, name: &str) -> Option<Workspace> {
+        self.config.read().unwrap().workspaces.iter().find(|w| w.name == name).cloned()
     }
 
-    pub fn start_pull_image(&mut self, image_name: String) {
-        let docker = self.docker.clone();
-        let progress_lock = self.pull_progress.clone();
-        let is_pulling = self.is_pulling.clone();
-        let images_ref = self.images.clone();
-        
-        is_pulling.store(true, Ordering::Relaxed);
-        progress_lock.write().unwrap().clear();
+    /// Proxy settings for dockyard's own registry calls: the active workspace's,
+    /// over the top-level ones.
+    pub fn proxy_config(&self) -> ProxyConfig {
+        let config = self.config.read().unwrap();
+        let workspace = self.workspace.as_ref().and_then(|name| config.workspaces.iter().find(|w| w.name == *name));
+        match workspace.and_then(|w| w.proxy.as_ref()) {
+            Some(proxy) => proxy.or(&config.proxy),
+            None => config.proxy.clone(),
+        }
+    }
 
-        tokio::spawn(async move {
-            let mut stream = pull_image(&docker, image_name);
-            while let Some(res) = stream.next().await {
-                let mut progress = progress_lock.write().unwrap();
-                match res {
-                    Ok(info) => {
-                        let msg = format!("{:?}", info); // Simplest conversion
-                        progress.push(msg);
-                    }
-                    Err(e) => progress.push(format!("Error: {}", e)),
+    /// Switches context (if the workspace names one) and then applies its
+    /// filters, pins and layout. Nothing changes if the context can't be reached.
+    pub async fn apply_workspace(&mut self, workspace: Workspace) {
+        if let Some(name) = workspace.context.as_ref().filter(|name| **name != self.docker_context) {
+            let context = match list_contexts() {
+                Ok(contexts) => contexts.into_iter().find(|c| c.name == *name),
+                Err(e) => {
+                    self.show_toast(format!("Failed to read Docker contexts: {}", e), true);
+                    return;
                 }
-                if progress.len() > 100 { progress.remove(0); }
-            }
-            is_pulling.store(false, Ordering::Relaxed);
-            
-            if let Ok(imgs) = list_images(&docker, false).await {
-                *images_ref.write().unwrap() = imgs;
+            };
+            let Some(context) = context else {
+                self.show_toast(format!("Workspace {}: no Docker context named {}", workspace.name, name), true);
+                return;
+            };
+            self.switch_context(context).await;
+            if self.docker_context != *name {
+                return;
             }
-        });
-    }
+        }
 
-    pub fn toggle_filter(&mut self) {
-        let current = self.show_all.load(Ordering::Relaxed);
-        self.show_all.store(!current, Ordering::Relaxed);
+        let base_read_only = *self.base_read_only.get_or_insert(self.read_only);
+        self.read_only = base_read_only || workspace.read_only;
+        self.container_filter.set_value(workspace.filter.clone().unwrap_or_default());
+        if let Some(show_all) = workspace.show_all {
+            self.show_all.store(show_all, Ordering::Relaxed);
+        }
+        if let Some(health) = workspace.health {
+            self.health_filter = health;
+        }
+        if let Some(sort) = workspace.sort {
+            self.container_sort = sort;
+        }
+        self.pinned = workspace.pinned;
+        if let Some(stats_view) = workspace.stats_view {
+            self.config.write().unwrap().stats_view = stats_view;
+        }
+        if let Err(e) = self.refresh_containers().await {
+            App::log_error(&self.error_log, "container list", e.to_string());
+        }
+        self.table_state.select((self.total_containers > 0).then_some(0));
+        self.last_fetched_id = None;
+        if let Some(view) = workspace.view {
+            self.enter_view(view).await;
+        }
+        self.show_toast(format!("Workspace {}{}", workspace.name, if self.read_only { " (read-only)" } else { "" }), false);
+        self.workspace = Some(workspace.name);
     }
 
-    pub fn apply_turbo_preset(&mut self) {
-        let mut config = self.config.write().unwrap();
-        if config.turbo_mode {
-            config.refresh_rate = RefreshRate::Interval(Duration::from_secs(2));
-            config.stats_view = StatsView::Minimal;
-            config.poll_strategy = PollStrategy::VisibleOnly;
-        } else {
-            config.refresh_rate = RefreshRate::Interval(Duration::from_secs(1));
+    /// Reconnects to another context's daemon. The old connection stays in use
+    /// unless the new endpoint answers a ping; on success every per-daemon cache
+    /// and queued action is dropped and the background tasks are restarted
+    /// against the new client.
+    pub async fn switch_context(&mut self, context: DockerContext) {
+        if context.name == self.docker_context {
+            return;
+        }
+        let mut docker = match DockerClient::for_context(&context) {
+            Ok(docker) => docker.with_timeouts(self.config.read().unwrap().timeouts),
+            Err(e) => {
+                self.show_toast(format!("Cannot use context {}: {}", context.name, e), true);
+                return;
+            }
+        };
+        // Before the ping, which an old daemon refuses at our default API version
+        docker.detect_engine().await;
+        if let Err(e) = docker.ping().await {
+            App::log_error(&self.error_log, "context", format!("{} ({}): {}", context.name, context.host, e));
+            self.show_toast(format!("Context {} is unreachable: {}", context.name, e), true);
+            return;
+        }
+
+        tracing::info!(from = %self.docker_context, to = %context.name, host = %context.host, "switching docker context");
+        // Queued removals were meant for the old daemon; run against the new
+        // one they could hit a same-named stack or image there
+        let cancelled = self.pending_actions.len();
+        self.pending_actions.clear();
+        self.stop_background_tasks();
+        *self.connection.write().unwrap() = ConnectionState::default();
+        self.docker = docker;
+        self.docker_context = context.name.clone();
+
+        self.containers.write().unwrap().retain(|c| c.host.is_some());
+        self.container_stats.write().unwrap().clear();
+        self.container_health.write().unwrap().clear();
+        self.container_timelines.write().unwrap().clear();
+        self.images.write().unwrap().clear();
+        self.image_usage.write().unwrap().clear();
+        self.containers_updated.store(0, Ordering::Relaxed);
+        self.images_updated.store(0, Ordering::Relaxed);
+        *self.selected_container_details.write().unwrap() = None;
+        self.selected_container_logs.write().unwrap().clear();
+        *self.selected_image_details.write().unwrap() = None;
+        *self.system_report.write().unwrap() = None;
+        *self.system_error.write().unwrap() = None;
+        self.last_fetched_id = None;
+        self.services.clear();
+        self.stacks.clear();
+        self.nodes.clear();
+        self.table_state.select(None);
+        self.table_state_images.select(None);
+        self.registry_info = registry_info(&self.docker).await.unwrap_or_default();
+        self.pull_target = 0;
+
+        if let Err(e) = self.refresh_containers().await {
+            App::log_error(&self.error_log, "container list", e.to_string());
+        }
+        if let Err(e) = self.refresh_images().await {
+            App::log_error(&self.error_log, "image list", e.to_string());
+        }
+        if self.total_containers > 0 {
+            self.table_state.select(Some(0));
+        }
+        self.spawn_background_tasks();
+        match self.current_view {
+            View::Services => self.refresh_services().await,
+            View::Stacks => self.refresh_stacks().await,
+            View::Nodes => self.refresh_nodes().await,
+            View::System => self.refresh_system(),
+            View::Containers | View::Top | View::Images => {}
+        }
+        let mut message = format!("Switched to context {} ({})", context.name, context.host);
+        if cancelled > 0 {
+            message.push_str(&format!("; cancelled {} queued action{}", cancelled, if cancelled == 1 { "" } else { "s" }));
+        }
+        self.show_toast(message, false);
+    }
+
+    fn stop_background_tasks(&mut self) {
+        for (_, task) in self.background_tasks.drain(..) {
+            task.abort();
+        }
+        if let Some(handle) = self.log_stream_task.take() {
+            handle.abort();
+        }
+    }
+
+    /// Once the watchdog sees the daemon again, restarts every stream and
+    /// poller (their connections died with the daemon) and reloads the lists.
+    /// Returns true if it did, so the caller re-fetches the selection.
+    pub async fn resume_after_reconnect(&mut self) -> bool {
+        if !std::mem::take(&mut self.connection.write().unwrap().recovered) {
+            return false;
+        }
+        self.stop_background_tasks();
+        if let Err(e) = self.refresh_containers().await {
+            App::log_error(&self.error_log, "container list", e.to_string());
+        }
+        if let Err(e) = self.refresh_images().await {
+            App::log_error(&self.error_log, "image list", e.to_string());
+        }
+        // Forces the details and log stream of the selection to be fetched again
+        self.last_fetched_id = None;
+        self.spawn_background_tasks();
+        self.show_toast(format!("Reconnected to {}", self.docker_context), false);
+        true
+    }
+
+    /// Restarts of a container within the configured storm window, and whether
+    /// that exceeds the limit.
+    pub fn restart_storm(&self, container_id: &str) -> (usize, bool) {
+        let storm = self.config.read().unwrap().restart_storm.clone();
+        let restarts = self.container_timelines.read().unwrap()
+            .get(container_id)
+            .map_or(0, |t| storm.restarts(t, Utc::now().timestamp()));
+        (restarts, restarts > storm.max_restarts)
+    }
+
+    /// First configured exec profile matching the container's name or image.
+    pub fn exec_profile_for(&self, container_id: &str) -> Option<ExecProfile> {
+        let containers = self.containers.read().unwrap();
+        let container = containers.iter().find(|c| c.id == container_id)?;
+        self.config.read().unwrap().exec_profiles.iter()
+            .find(|p| p.matches(&container.name, &container.image))
+            .cloned()
+    }
+
+    /// Offers the selected running container's exec commands to choose from.
+    pub fn open_exec_chooser(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        if container.state.to_lowercase() != "running" {
+            return;
+        }
+        let commands = {
+            let config = self.config.read().unwrap();
+            let profiles: Vec<&ExecProfile> = config.exec_profiles.iter()
+                .filter(|p| p.matches(&container.name, &container.image))
+                .collect();
+            exec_choices(&profiles)
+        };
+        self.push_modal(Modal::ExecChooser { container_id: container.id, container_name: container.name, commands, selected: 0 });
+    }
+
+    pub fn open_exec_custom_dialog(&mut self, container_id: String, container_name: String) {
+        let form = Form::new(format!("Exec in {}", container_name))
+            .text("command", "Command", TextInput::with_validator(validate_command));
+        self.push_modal(Modal::ExecCustom { container_id, container_name, form });
+    }
+
+    pub fn push_modal(&mut self, modal: Modal) {
+        if matches!(modal, Modal::TextViewer { .. } | Modal::Timeline { .. } | Modal::Diagnostics(_)) {
+            self.viewer_scroll = 0;
+        }
+        self.modals.push(modal);
+    }
+
+    pub fn pop_modal(&mut self) -> Option<Modal> {
+        let modal = self.modals.pop();
+        if matches!(modal, Some(Modal::Help)) {
+            self.current_help_tab = crate::types::HelpTab::Keybindings;
+            self.help_scroll = 0;
+        }
+        modal
+    }
+
+    pub fn top_modal(&self) -> Option<&Modal> {
+        self.modals.last()
+    }
+
+    pub fn top_modal_mut(&mut self) -> Option<&mut Modal> {
+        self.modals.last_mut()
+    }
+
+    /// Records a background failure for the error console. Never print from
+    /// background tasks; it corrupts the alternate screen.
+    pub fn log_error(errors: &Arc<RwLock<VecDeque<ErrorEntry>>>, source: &str, message: String) {
+        tracing::error!(source, %message, "background task failed");
+        let mut errors = errors.write().unwrap();
+        errors.push_back(ErrorEntry { time: Utc::now().timestamp(), source: source.to_string(), message });
+        while errors.len() > ERROR_LOG_LEN {
+            errors.pop_front();
+        }
+    }
+
+    /// Switches to `view` and loads what it shows.
+    pub async fn enter_view(&mut self, view: View) {
+        self.current_view = view;
+        self.poll_all_stats.store(view == View::Top, Ordering::Relaxed);
+        match view {
+            // Trigger details fetch for initial selection if switching to images
+            View::Images => self.trigger_image_details(false),
+            View::Services => self.refresh_services().await,
+            View::Stacks => self.refresh_stacks().await,
+            View::Nodes => self.refresh_nodes().await,
+            View::System => self.refresh_system(),
+            View::Containers | View::Top => {}
+        }
+    }
+
+    /// Running containers with stats for the Top view, heaviest first.
+    pub fn top_rows(&self) -> Vec<(ContainerInfo, ContainerStats)> {
+        let stats = self.container_stats.read().unwrap();
+        let mut rows: Vec<(ContainerInfo, ContainerStats)> = self.containers.read().unwrap().iter()
+            .filter(|c| c.state == "running")
+            .filter_map(|c| stats.get(&c.id).map(|s| (c.clone(), s.clone())))
+            .collect();
+        match self.top_sort {
+            TopSort::Cpu => rows.sort_by(|a, b| b.1.cpu_percent.total_cmp(&a.1.cpu_percent)),
+            TopSort::Memory => rows.sort_by_key(|(_, s)| std::cmp::Reverse(s.memory_usage)),
+        }
+        rows
+    }
+
+    /// Moves the Top view selection by `delta` rows in the current ranking.
+    pub fn move_top_selection(&mut self, delta: isize) {
+        let rows = self.top_rows();
+        if rows.is_empty() {
+            return;
+        }
+        let current = self.top_selected.as_ref().and_then(|id| rows.iter().position(|(c, _)| &c.id == id));
+        let i = match current {
+            Some(i) => (i as isize + delta).clamp(0, rows.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.top_selected = Some(rows[i].0.id.clone());
+    }
+
+    pub fn toggle_top_sort(&mut self) {
+        self.top_sort = match self.top_sort {
+            TopSort::Cpu => TopSort::Memory,
+            TopSort::Memory => TopSort::Cpu,
+        };
+    }
+
+    /// The container selected in the Top view, if it is still running.
+    pub fn top_selected_container(&self) -> Option<ContainerInfo> {
+        let id = self.top_selected.as_ref()?;
+        self.containers.read().unwrap().iter().find(|c| &c.id == id && c.state == "running").cloned()
+    }
+
+    /// Shows the Top view's selection in the Containers view.
+    pub async fn open_top_selection(&mut self) {
+        let Some(container) = self.top_selected_container() else { return };
+        if self.select_container_row(&container.id) {
+            self.enter_view(View::Containers).await;
+        } else {
+            self.show_toast(format!("{} is hidden by the container filters", container.name), true);
+        }
+    }
+
+    /// In `--read-only` mode, explains why `action` is refused and returns true.
+    pub fn deny_read_only(&self, action: &str) -> bool {
+        if self.read_only {
+            self.show_toast(format!("Read-only mode: {} is disabled", action), true);
+        }
+        self.read_only
+    }
+
+    pub fn toggle_error_console(&mut self) {
+        self.show_error_console = !self.show_error_console;
+        self.errors_seen = self.error_log.read().unwrap().len();
+    }
+
+    /// Probes the daemons and snapshots task and buffer state into a plain-text
+    /// report for bug reports. Re-running replaces the open report.
+    pub async fn open_diagnostics(&mut self) {
+        let report = self.diagnostics_report().await;
+        if matches!(self.top_modal(), Some(Modal::Diagnostics(_))) {
+            self.pop_modal();
+        }
+        self.push_modal(Modal::Diagnostics(report));
+    }
+
+    async fn diagnostics_report(&self) -> String {
+        let mut s = String::new();
+        s.push_str(&format!("dockyard {} ({}/{})\n", env!("CARGO_PKG_VERSION"), std::env::consts::OS, std::env::consts::ARCH));
+        s.push_str(&format!("Generated: {}\n", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+
+        let check = check_daemon(&self.docker).await;
+        s.push_str("\nConnection:\n");
+        s.push_str(&format!("  Context: {}\n", self.docker_context));
+        s.push_str(&format!("  Endpoint: {}\n", self.docker.endpoint));
+        s.push_str(&format!("  Transport: {}\n", self.docker.transport()));
+        if let Some(access) = &check.socket_access {
+            s.push_str(&format!("  Socket access: {}\n", access));
+        }
+        match &check.error {
+            Some(e) => s.push_str(&format!("  Daemon: unreachable ({})\n", e)),
+            None => s.push_str(&format!(
+                "  Daemon: {} {} on {} ({} ms)\n",
+                check.engine_name.as_deref().unwrap_or("Docker"),
+                check.engine_version.as_deref().unwrap_or("?"),
+                check.platform.as_deref().unwrap_or("?"),
+                check.latency_ms.unwrap_or(0),
+            )),
+        }
+
+        s.push_str("\nAPI Version:\n");
+        s.push_str(&format!("  Client: {}\n", check.client_api));
+        if let Some(api) = &check.server_api {
+            s.push_str(&format!("  Daemon: {} (minimum {})\n", api, check.min_api.as_deref().unwrap_or("?")));
+        }
+        s.push_str(&format!("  Status: {}\n", check.api_status()));
+
+        if !self.remote_clients.is_empty() {
+            let mut hosts: Vec<(&String, &DockerClient)> = self.remote_clients.iter().collect();
+            hosts.sort_by(|a, b| a.0.cmp(b.0));
+            let statuses = futures::future::join_all(hosts.iter().map(|(_, client)| ping_host(client))).await;
+            s.push_str("\nExtra Hosts:\n");
+            for ((name, client), status) in hosts.iter().zip(statuses) {
+                s.push_str(&format!("  {}: {} via {} - {}\n", name, client.endpoint, client.transport(), status));
+            }
+        }
+
+        s.push_str("\nBackground Tasks:\n");
+        for (name, task) in &self.background_tasks {
+            s.push_str(&format!("  {}: {}\n", name, if task.is_finished() { "STOPPED" } else { "running" }));
+        }
+        let log_stream = match &self.log_stream_task {
+            Some(task) if !task.is_finished() => "running",
+            Some(_) => "finished",
+            None => "idle",
+        };
+        s.push_str(&format!("  log stream: {}\n", log_stream));
+
+        // Rough sizes of what dockyard keeps in memory; strings count their text only
+        let text_bytes = |lines: &[String]| lines.iter().map(|l| l.len() as u64).sum::<u64>();
+        s.push_str("\nBuffers:\n");
+        s.push_str(&format!("  Containers: {}\n", self.containers.read().unwrap().len()));
+        s.push_str(&format!("  Images: {}\n", self.images.read().unwrap().len()));
+        {
+            let stats = self.container_stats.read().unwrap();
+            let samples: usize = stats.values().map(|st| st.sample_times.len()).sum();
+            // Six 8-byte series per sample
+            s.push_str(&format!("  Stats history: {} containers, {} samples, {}\n", stats.len(), samples, format_bytes(samples as u64 * 6 * 8)));
+        }
+        {
+            let timelines = self.container_timelines.read().unwrap();
+            let events: usize = timelines.values().map(|t| t.len()).sum();
+            let bytes: u64 = timelines.values().flatten()
+                .map(|e| (std::mem::size_of::<LifecycleEvent>() + e.action.len() + e.detail.len()) as u64)
+                .sum();
+            s.push_str(&format!("  Timelines: {} containers, {} events, {}\n", timelines.len(), events, format_bytes(bytes)));
+        }
+        {
+            let logs = self.selected_container_logs.read().unwrap();
+            s.push_str(&format!("  Container logs: {} lines, {}\n", logs.len(), format_bytes(text_bytes(&logs))));
+        }
+        {
+            let output = self.output_log.read().unwrap();
+            s.push_str(&format!("  Output log: {} lines, {}\n", output.len(), format_bytes(text_bytes(&output))));
+        }
+        s.push_str(&format!("  Error log: {} entries\n", self.error_log.read().unwrap().len()));
+        s.push_str(&format!("  Process memory: {}\n", format_bytes(self.perf_metrics.read().unwrap().memory_usage)));
+
+        let errors = self.error_log.read().unwrap();
+        if !errors.is_empty() {
+            s.push_str("\nRecent Errors:\n");
+            for e in errors.iter().rev().take(5) {
+                let time = chrono::DateTime::from_timestamp(e.time, 0).map(|t| t.format("%H:%M:%S").to_string()).unwrap_or_default();
+                s.push_str(&format!("  {} [{}] {}\n", time, e.source, e.message));
+            }
+        }
+        s
+    }
+
+    /// Puts the privacy cover up once `privacy.idle_minutes` have passed without input.
+    pub fn check_idle(&mut self) {
+        let idle_minutes = self.config.read().unwrap().privacy.idle_minutes;
+        if idle_minutes > 0 && self.last_input.elapsed() >= std::time::Duration::from_secs(idle_minutes * 60) {
+            self.privacy_cover = true;
+        }
+    }
+
+    /// Records input; returns true when it only took the privacy cover down.
+    pub fn note_input(&mut self) -> bool {
+        self.last_input = std::time::Instant::now();
+        std::mem::take(&mut self.privacy_cover)
+    }
+
+    /// Starts a flash for queued notifications that ask for one, and returns
+    /// whether any asked for the bell.
+    pub fn take_notifications(&mut self) -> bool {
+        let events = std::mem::take(&mut *self.notifications.write().unwrap());
+        if events.is_empty() {
+            return false;
+        }
+        let config = self.config.read().unwrap().notifications;
+        let styles: Vec<NotifyStyle> = events.into_iter().map(|e| config.style(e)).collect();
+        if styles.contains(&NotifyStyle::Flash) {
+            self.flash_until = Some(std::time::Instant::now() + FLASH_DURATION);
+        }
+        styles.contains(&NotifyStyle::Bell)
+    }
+
+    pub fn is_flashing(&self) -> bool {
+        self.flash_until.is_some_and(|until| std::time::Instant::now() < until)
+    }
+
+    /// Errors logged since the console was last opened.
+    pub fn unread_errors(&self) -> usize {
+        self.error_log.read().unwrap().len().saturating_sub(self.errors_seen)
+    }
+
+    /// Appends a line to the Output pane, keeping the last 100 lines.
+    pub fn log_output(output: &Arc<RwLock<Vec<String>>>, line: String) {
+        let mut lines = output.write().unwrap();
+        lines.push(line);
+        if lines.len() > 100 {
+            lines.remove(0);
+        }
+    }
+
+    pub fn open_scale_dialog(&mut self) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        let (Some(project), Some(service)) = (container.compose_project(), container.compose_service()) else {
+            self.show_toast("Selected container is not a compose service".to_string(), true);
+            return;
+        };
+        let current = self.containers.read().unwrap()
+            .iter()
+            .filter(|c| c.host == container.host && c.compose_project() == Some(project) && c.compose_service() == Some(service))
+            .count();
+
+        let mut replicas = TextInput::with_validator(validate_replicas);
+        replicas.set_value(current.to_string());
+        let form = Form::new(format!("Scale {}", service))
+            .label(format!("Project '{}', service '{}' has {} replica(s)", project, service, current))
+            .text("replicas", "Replicas", replicas);
+
+        self.push_modal(Modal::ScaleService { project: project.to_string(), service: service.to_string(), form });
+    }
+
+    pub fn start_scale_service(&mut self, project: String, service: String, target: u32) {
+        // Scale on the host the selected replica runs on
+        let host = self.selected_container().and_then(|c| c.host);
+        let docker = self.docker_for_host(host.as_deref());
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+
+        output.write().unwrap().clear();
+        self.show_toast(format!("Scaling {} to {} (see Output pane in Images view)", service, target), false);
+
+        tokio::spawn(async move {
+            let log_lock = output.clone();
+            let result = scale_service(&docker, &project, &service, target, move |line| App::log_output(&log_lock, line)).await;
+            let toast_msg = match result {
+                Ok(()) => Toast::new(format!("Scaled {} to {} replica(s)", service, target), false),
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("Scaling {} failed: {}", service, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            if let Ok(list) = list_containers(&docker, show_all.load(Ordering::Relaxed)).await {
+                App::replace_host_containers(&containers, host.as_deref(), list);
+            }
+        });
+    }
+
+    /// Opens the label editor: the current labels, fields for the changes and
+    /// what recreating the container (labels are immutable) will cost.
+    pub async fn open_label_editor(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let info = match inspect_container(&self.docker_for(&container.id), &container.id).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.show_toast(format!("Failed to inspect {}: {}", container.name, e), true);
+                return;
+            }
+        };
+        let mut labels: Vec<String> = info.config.as_ref()
+            .and_then(|c| c.labels.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        labels.sort();
+
+        let mut form = Form::new(format!("Labels: {}", container.name));
+        if labels.is_empty() {
+            form = form.label("No labels");
+        }
+        for label in labels.iter().take(MAX_LISTED_LABELS) {
+            form = form.label(format!("  {}", label));
+        }
+        if labels.len() > MAX_LISTED_LABELS {
+            form = form.label(format!("  ... and {} more", labels.len() - MAX_LISTED_LABELS));
+        }
+        form = form
+            .text("set", "Add or change (key=value; key2=value2)", TextInput::with_validator(validate_label_assignments))
+            .text("remove", "Remove keys (separated by spaces)", TextInput::new())
+            .label("Labels are fixed at creation, so the container is recreated:");
+        for warning in recreate_warnings(&info) {
+            form = form.label(format!("  ⚠ {}", warning));
+        }
+        self.push_modal(Modal::EditLabels { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Recreates the container with `set` applied and `remove` dropped from its
+    /// labels, logging each step to the Output pane.
+    pub fn start_label_edit(&mut self, container_id: String, container_name: String, set: Vec<(String, String)>, remove: Vec<String>) {
+        if set.is_empty() && remove.is_empty() {
+            self.show_toast("No label changes".to_string(), false);
+            return;
+        }
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        let docker = self.docker_for_host(host.as_deref());
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+
+        output.write().unwrap().clear();
+        self.show_toast(format!("Recreating {} with new labels (see Output pane in Images view)", container_name), false);
+
+        tokio::spawn(async move {
+            let log_lock = output.clone();
+            let result = async {
+                let info = inspect_container(&docker, &container_id).await?;
+                let mut config = config_from_inspect(&info);
+                let labels = config.labels.get_or_insert_with(HashMap::new);
+                for key in &remove {
+                    labels.remove(key);
+                }
+                labels.extend(set);
+                recreate_container(&docker, &info, config, move |line| App::log_output(&log_lock, line)).await
+            }.await;
+            let toast_msg = match result {
+                Ok(_) => Toast::new(format!("Recreated {} with the new labels", container_name), false),
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("Relabeling {} failed: {}", container_name, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            if let Ok(list) = list_containers(&docker, show_all.load(Ordering::Relaxed)).await {
+                App::replace_host_containers(&containers, host.as_deref(), list);
+            }
+        });
+    }
+
+    /// Opens the edit dialog with the container's image, env var names and
+    /// published ports.
+    pub async fn open_edit_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let info = match inspect_container(&self.docker_for(&container.id), &container.id).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.show_toast(format!("Failed to inspect {}: {}", container.name, e), true);
+                return;
+            }
+        };
+        let mut image = TextInput::with_validator(validate_image_ref);
+        image.set_value(info.config.as_ref().and_then(|c| c.image.clone()).unwrap_or_default());
+        let mut ports = TextInput::with_validator(validate_port_specs);
+        ports.set_value(port_specs(&info.host_config.clone().unwrap_or_default()).join(" "));
+        // Values stay hidden; env vars often hold secrets
+        let mut names: Vec<String> = info.config.as_ref()
+            .and_then(|c| c.env.clone())
+            .unwrap_or_default()
+            .iter()
+            .map(|var| var.split_once('=').map_or(var.as_str(), |(name, _)| name).to_string())
+            .collect();
+        names.sort();
+
+        let mut form = Form::new(format!("Edit: {}", container.name))
+            .text("image", "Image", image)
+            .label(if names.is_empty() { "Env: none".to_string() } else { format!("Env: {}", names.join(", ")) })
+            .text("env_set", "Set env (KEY=value; KEY2=value2)", TextInput::with_validator(validate_label_assignments))
+            .text("env_remove", "Remove env vars (names separated by spaces)", TextInput::new())
+            .text("ports", "Published ports (host:container, blank for none)", ports)
+            .label("The container is recreated with the same name, volumes and networks:");
+        for warning in recreate_warnings(&info) {
+            form = form.label(format!("  ⚠ {}", warning));
+        }
+        self.push_modal(Modal::EditContainer { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Recreates the container with the edited image, env and ports, pulling
+    /// the image first when the host doesn't have it, and logging each step to
+    /// the Output pane.
+    pub fn start_container_edit(&mut self, container_id: String, container_name: String, edit: ContainerEdit) {
+        let ports = match parse_port_specs(&edit.ports) {
+            Ok(ports) => ports,
+            Err(e) => {
+                self.show_toast(e, true);
+                return;
+            }
+        };
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        let docker = self.docker_for_host(host.as_deref());
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+
+        output.write().unwrap().clear();
+        self.show_toast(format!("Recreating {} with the changes (see Output pane in Images view)", container_name), false);
+
+        tokio::spawn(async move {
+            let log_lock = output.clone();
+            let result = async {
+                let info = inspect_container(&docker, &container_id).await?;
+                let mut config = config_from_inspect(&info);
+                config.image = Some(edit.image.clone());
+                let env = config.env.get_or_insert_with(Vec::new);
+                env.retain(|var| {
+                    let name = var.split_once('=').map_or(var.as_str(), |(name, _)| name);
+                    !edit.env_remove.iter().any(|n| n == name) && !edit.env_set.iter().any(|(n, _)| n == name)
+                });
+                env.extend(edit.env_set.iter().map(|(name, value)| format!("{}={}", name, value)));
+                // Bindings are only rebuilt when the specs changed, as parsing normalizes them
+                let current = port_specs(&info.host_config.clone().unwrap_or_default()).join(" ");
+                if edit.ports.split_whitespace().collect::<Vec<_>>().join(" ") != current {
+                    let exposed = config.exposed_ports.get_or_insert_with(HashMap::new);
+                    for port in ports.keys() {
+                        exposed.entry(port.clone()).or_default();
+                    }
+                    if let Some(host) = config.host_config.as_mut() {
+                        host.port_bindings = Some(ports);
+                    }
+                }
+                if config == config_from_inspect(&info) {
+                    return Ok(false);
+                }
+
+                // Pull before anything is stopped, so a bad tag leaves the container alone
+                if inspect_image(&docker, &edit.image).await.is_err() {
+                    App::log_output(&log_lock, format!("Pulling {}", edit.image));
+                    let mut pull = pull_image(&docker, edit.image.clone());
+                    while let Some(progress) = pull.next().await {
+                        progress?;
+                    }
+                }
+                let log = log_lock.clone();
+                recreate_container(&docker, &info, config, move |line| App::log_output(&log, line)).await?;
+                Ok::<_, AppError>(true)
+            }.await;
+            let toast_msg = match result {
+                Ok(true) => Toast::new(format!("Recreated {} with the changes", container_name), false),
+                Ok(false) => Toast::new(format!("No changes to {}", container_name), false),
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("Editing {} failed: {}", container_name, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            if let Ok(list) = list_containers(&docker, show_all.load(Ordering::Relaxed)).await {
+                App::replace_host_containers(&containers, host.as_deref(), list);
+            }
+        });
+    }
+
+    /// Opens the duplicate dialog with a suggested name and the original's ports,
+    /// which usually have to change for the copy to start.
+    pub async fn open_duplicate_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let info = match inspect_container(&self.docker_for(&container.id), &container.id).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.show_toast(format!("Failed to inspect {}: {}", container.name, e), true);
+                return;
+            }
+        };
+        let mut name = TextInput::with_validator(validate_container_name);
+        name.set_value(format!("{}-copy", container.name));
+        let mut ports = TextInput::with_validator(validate_port_specs);
+        let specs = port_specs(&info.host_config.unwrap_or_default()).join(" ");
+        ports.set_value(specs.clone());
+        let docker = self.docker_for(&container.id);
+        // Without the list only the host itself is checked
+        let published = published_ports(&docker).await.unwrap_or_default();
+        let local = docker.is_local();
+        let form = Form::new(format!("Duplicate: {}", container.name))
+            .label("Same image and config; volumes and bind mounts are shared")
+            .text("name", "Name", name)
+            .text("ports", "Published ports (host:container, blank for none)", ports)
+            .note("port_warning", port_warning(&specs, &published, local))
+            .checkbox("start", "Start it", container.state == "running");
+        self.push_modal(Modal::Duplicate { container_id: container.id, container_name: container.name, form, published, local });
+    }
+
+    pub fn start_duplicate(&mut self, container_id: String, container_name: String, name: String, ports: String, start: bool) {
+        let ports = match parse_port_specs(&ports) {
+            Ok(ports) => ports,
+            Err(e) => {
+                self.show_toast(e, true);
+                return;
+            }
+        };
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        let docker = self.docker_for_host(host.as_deref());
+        let toast = self.toast.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+        self.show_toast(format!("Creating {} from {}", name, container_name), false);
+
+        tokio::spawn(async move {
+            let result = async {
+                let info = inspect_container(&docker, &container_id).await?;
+                duplicate_container(&docker, &info, &name, ports, start).await
+            }.await;
+            let toast_msg = match result {
+                Ok(_) => Toast::new(format!("Created {} from {}", name, container_name), false),
+                Err(e) => Toast::new(format!("Duplicating {} failed: {}", container_name, e), true),
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            if let Ok(list) = list_containers(&docker, show_all.load(Ordering::Relaxed)).await {
+                App::replace_host_containers(&containers, host.as_deref(), list);
+            }
+        });
+    }
+
+    /// Opens the migration dialog, offering every other connected host as the target.
+    pub fn open_migrate_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let mut targets: Vec<String> = self.remote_clients.keys()
+            .filter(|name| container.host.as_deref() != Some(name.as_str()))
+            .cloned()
+            .collect();
+        targets.sort();
+        if container.host.is_some() {
+            targets.insert(0, self.docker_context.clone());
+        }
+        if targets.is_empty() {
+            self.show_toast("Migrating needs another host under [[hosts]] in config.toml".to_string(), true);
+            return;
+        }
+        let form = Form::new(format!("Migrate: {}", container.name))
+            .label("Recreated on the target under the same name and config")
+            .label("Volume data is not copied; bind-mounted paths must exist there")
+            .select("target", "Target host", targets, 0)
+            .checkbox("commit", "Commit it first, keeping changes outside volumes", false)
+            .checkbox("pull", "Pull the image on the target instead of copying it", false)
+            .checkbox("stop", "Stop the original once the copy is up", container.state == "running");
+        self.push_modal(Modal::Migrate { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Migrates a container to `target` (a host name, or the primary's context),
+    /// logging each step to the Output pane.
+    pub fn start_migrate(&mut self, container_id: String, container_name: String, target: String, options: MigrateOptions) {
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        let source = self.docker_for_host(host.as_deref());
+        let target_host = self.remote_clients.contains_key(&target).then(|| target.clone());
+        let target_client = self.docker_for_host(target_host.as_deref());
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+
+        output.write().unwrap().clear();
+        self.show_toast(format!("Migrating {} to {} (see Output pane in Images view)", container_name, target), false);
+
+        tokio::spawn(async move {
+            let log_lock = output.clone();
+            let result = async {
+                let info = inspect_container(&source, &container_id).await?;
+                migrate_container(&source, &target_client, &target, &info, options, move |line| App::log_output(&log_lock, line)).await
+            }.await;
+            let toast_msg = match result {
+                Ok(_) => Toast::new(format!("Migrated {} to {}", container_name, target), false),
+                Err(e) => Toast::new(format!("Migrating {} failed and was rolled back: {}", container_name, e), true),
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            for (host, client) in [(host, source), (target_host, target_client)] {
+                if let Ok(list) = list_containers(&client, show_all.load(Ordering::Relaxed)).await {
+                    App::replace_host_containers(&containers, host.as_deref(), list);
+                }
+            }
+        });
+    }
+
+    pub fn open_rename_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let mut name = TextInput::with_validator(validate_container_name);
+        name.set_value(container.name.clone());
+        let form = Form::new(format!("Rename: {}", container.name))
+            .text("name", "New name", name);
+        self.push_modal(Modal::Rename { container_id: container.id, container_name: container.name, form });
+    }
+
+    pub fn start_rename(&mut self, container_id: String, container_name: String, name: String) {
+        if name == container_name {
+            return;
+        }
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        let docker = self.docker_for_host(host.as_deref());
+        let toast = self.toast.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+
+        tokio::spawn(async move {
+            let toast_msg = match rename_container(&docker, &container_id, &name).await {
+                Ok(()) => Toast::new(format!("Renamed {} to {}", container_name, name), false),
+                Err(e) => Toast::new(format!("Renaming {} failed: {}", container_name, e), true),
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            if let Ok(list) = list_containers(&docker, show_all.load(Ordering::Relaxed)).await {
+                App::replace_host_containers(&containers, host.as_deref(), list);
+            }
+        });
+    }
+
+    pub fn open_commit_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let mut image = TextInput::with_validator(validate_image_ref);
+        image.set_value(format!("{}:{}", container.name.to_lowercase(), Utc::now().format("%Y%m%d-%H%M%S")));
+        let form = Form::new(format!("Commit: {}", container.name))
+            .label("Snapshots the filesystem; volumes are not included")
+            .text("image", "Image (repo:tag)", image)
+            .text("comment", "Comment (optional)", TextInput::new())
+            .text("author", "Author (optional)", TextInput::new());
+        self.push_modal(Modal::Commit { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Commits the container in the background and refreshes the image list,
+    /// which shows the primary daemon's images.
+    pub fn start_commit(&mut self, container_id: String, container_name: String, image: String, comment: String, author: String) {
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        let docker = self.docker_for_host(host.as_deref());
+        let primary = self.docker.clone();
+        let toast = self.toast.clone();
+        let images = self.images.clone();
+        let show_dangling = self.show_dangling.clone();
+        let optional = |s: String| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        let (comment, author) = (optional(comment), optional(author));
+        self.show_toast(format!("Committing {} to {}", container_name, image), false);
+
+        tokio::spawn(async move {
+            let toast_msg = match commit_container(&docker, &container_id, &image, comment, author).await {
+                Ok(id) => {
+                    let id: String = id.trim_start_matches("sha256:").chars().take(12).collect();
+                    Toast::new(format!("Committed {} as {} ({})", container_name, image, id), false)
+                }
+                Err(e) => Toast::new(format!("Committing {} failed: {}", container_name, e), true),
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            if let Ok(list) = list_images(&primary, show_dangling.load(Ordering::Relaxed)).await {
+                *images.write().unwrap() = list;
+            }
+        });
+    }
+
+    /// Opens the traffic shaping dialog, set to revert when the container is
+    /// already shaped.
+    pub fn open_netem_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        if container.state != "running" {
+            self.show_toast(format!("{} is not running", container.name), true);
+            return;
+        }
+        let current = self.netem_rules.read().unwrap().get(&container.id).map(|(rule, _)| *rule);
+        let mut form = Form::new(format!("Network shaping: {}", container.name));
+        form = match current {
+            Some(rule) => form.label(format!("Active: {}", rule)),
+            None => form.label("Applies to every interface until reverted or restarted"),
+        };
+        let number = || TextInput::with_validator(validate_optional_number);
+        let form = form
+            .select("action", "Action", vec!["Apply".to_string(), "Revert".to_string()], usize::from(current.is_some()))
+            .text("delay", "Delay (ms)", number())
+            .text("jitter", "Jitter (ms)", number())
+            .text("loss", "Packet loss (%)", TextInput::with_validator(validate_optional_percent))
+            .text("rate", "Bandwidth (kbit/s, blank for unlimited)", number())
+            .text("revert", "Revert after (minutes, blank to keep)", number());
+        self.push_modal(Modal::Netem { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Applies `rule` to the container through the helper, or reverts shaping
+    /// when it is None. With `revert_after`, the rule is reverted after that
+    /// many minutes unless it was changed in the meantime.
+    pub fn start_netem(&mut self, container_id: String, container_name: String, rule: Option<NetemRule>, revert_after: Option<u64>) {
+        let docker = self.docker_for(&container_id);
+        let image = self.config.read().unwrap().netem.image.clone();
+        let rules = self.netem_rules.clone();
+        let toast = self.toast.clone();
+        let errors = self.error_log.clone();
+
+        tokio::spawn(async move {
+            let Some(rule) = rule else {
+                let toast_msg = match crate::docker::netem::clear_netem(&docker, &container_id, &image).await {
+                    Ok(()) => {
+                        rules.write().unwrap().remove(&container_id);
+                        Toast::new(format!("Removed network shaping from {}", container_name), false)
+                    }
+                    Err(e) => Toast::new(format!("Reverting {} failed: {}", container_name, e), true),
+                };
+                *toast.write().unwrap() = Some(toast_msg);
+                return;
+            };
+
+            if let Err(e) = crate::docker::netem::apply_netem(&docker, &container_id, &image, &rule).await {
+                *toast.write().unwrap() = Some(Toast::new(format!("Shaping {} failed: {}", container_name, e), true));
+                return;
+            }
+            let applied_at = Utc::now().timestamp_millis();
+            rules.write().unwrap().insert(container_id.clone(), (rule, applied_at));
+            *toast.write().unwrap() = Some(Toast::new(format!("Shaping {}: {}", container_name, rule), false));
+
+            let Some(minutes) = revert_after.filter(|m| *m > 0) else { return };
+            tokio::time::sleep(Duration::from_secs(minutes * 60)).await;
+            if rules.read().unwrap().get(&container_id).map(|(_, at)| *at) != Some(applied_at) {
+                return;
+            }
+            match crate::docker::netem::clear_netem(&docker, &container_id, &image).await {
+                Ok(()) => {
+                    rules.write().unwrap().remove(&container_id);
+                    *toast.write().unwrap() = Some(Toast::new(format!("Network shaping on {} expired", container_name), false));
+                }
+                Err(e) => App::log_error(&errors, "network shaping", format!("Reverting {} failed: {}", container_name, e)),
+            }
+        });
+    }
+
+    pub fn open_export_filesystem_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let mut path = TextInput::new();
+        path.set_value(format!("{}-fs-{}.tar", container.name, Utc::now().format("%Y%m%d-%H%M%S")));
+        let form = Form::new(format!("Export filesystem: {}", container.name))
+            .label("Uncompressed tar of the whole filesystem; volumes are not included")
+            .text("path", "File", path);
+        self.push_modal(Modal::ExportFilesystem { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Writes the container's filesystem to `path`, with a gauge in the Output pane.
+    pub fn start_export_filesystem(&mut self, container_id: String, container_name: String, path: String) {
+        let docker = self.docker_for(&container_id);
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        output.write().unwrap().clear();
+        App::log_output(&output, format!("Exporting the filesystem of {} to {}", container_name, path));
+        self.show_toast(format!("Exporting {} to {}", container_name, path), false);
+
+        tokio::spawn(async move {
+            let estimate = root_fs_size(&docker, &container_id).await.ok().flatten();
+            App::log_output(&output, export_gauge(0, estimate));
+            // The gauge is the last line, rewritten as the archive grows
+            let progress = |written| {
+                if let Some(line) = output.write().unwrap().last_mut() {
+                    *line = export_gauge(written, estimate);
+                }
+            };
+            let toast_msg = match export_filesystem(&docker, &container_id, std::path::Path::new(&path), progress).await {
+                Ok(written) => {
+                    App::log_output(&output, format!("Wrote {} to {}", format_bytes(written), path));
+                    Toast::new(format!("Exported {} to {} ({})", container_name, path, format_bytes(written)), false)
+                }
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("Exporting {} failed: {}", container_name, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+        });
+    }
+
+    pub fn open_incident_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let form = Form::new(format!("Incident report: {}", container.name))
+            .label("Markdown with image digest, recent errors, health and stats")
+            .select("destination", "Destination", vec!["Clipboard".to_string(), "File".to_string()], 0);
+        self.push_modal(Modal::IncidentReport { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Copies the URL of the commit the selected container's or image's image was
+    /// built from, or the source repository when no revision is recorded.
+    pub async fn copy_commit_url(&self) {
+        let labels = if self.current_view == View::Images {
+            let Some(image) = self.selected_image() else { return };
+            match inspect_image(&self.docker, &image.id).await {
+                Ok(info) => info.config.and_then(|c| c.labels).unwrap_or_default(),
+                Err(e) => {
+                    self.show_toast(format!("Failed to inspect image: {}", e), true);
+                    return;
+                }
+            }
+        } else {
+            let Some(container) = self.selected_container() else { return };
+            container.labels
+        };
+        let provenance = Provenance::from_labels(&labels);
+        let (url, what) = match (provenance.commit_url(), provenance.source_url()) {
+            (Some(url), _) => (url, "commit URL"),
+            (None, Some(url)) => (url, "source URL (no revision label)"),
+            (None, None) => {
+                self.show_toast("No org.opencontainers.image.source label on this image".to_string(), true);
+                return;
+            }
+        };
+        match crate::clipboard::copy_to_clipboard(&url) {
+            Ok(()) => self.show_toast(format!("Copied {}: {}", what, url), false),
+            Err(e) => self.show_toast(format!("Copy failed: {}", e), true),
+        }
+    }
+
+    /// Copies (or writes to `./<name>-incident-<time>.md`) a pre-filled incident report.
+    pub async fn export_incident(&self, container_id: &str, container_name: &str, to_file: bool) {
+        let report = match self.incident_report(container_id).await {
+            Ok(report) => report,
+            Err(e) => {
+                self.show_toast(format!("Incident report failed: {}", e), true);
+                return;
+            }
+        };
+        let result = if to_file {
+            let path = format!("{}-incident-{}.md", container_name, Utc::now().format("%Y%m%d-%H%M%S"));
+            std::fs::write(&path, &report).map(|_| format!("Wrote incident report to {}", path))
+        } else {
+            crate::clipboard::copy_to_clipboard(&report).map(|_| "Copied incident report to clipboard".to_string())
+        };
+        match result {
+            Ok(message) => self.show_toast(message, false),
+            Err(e) => self.show_toast(format!("Export failed: {}", e), true),
+        }
+    }
+
+    async fn incident_report(&self, container_id: &str) -> Result<String> {
+        let docker = self.docker_for(container_id);
+        let info = inspect_container(&docker, container_id).await?;
+        let name = info.name.as_deref().unwrap_or(container_id).trim_start_matches('/').to_string();
+        let config = info.config.clone().unwrap_or_default();
+        let state = info.state.clone().unwrap_or_default();
+        let image_id = info.image.clone().unwrap_or_default();
+        let digests = inspect_image(&docker, &image_id).await
+            .ok()
+            .and_then(|image| image.repo_digests)
+            .unwrap_or_default();
+
+        let mut s = format!("# Incident: {}\n\n", name);
+        s.push_str("## Summary\n\n<!-- What happened, impact, and when it started -->\n\n");
+
+        s.push_str("## Container\n\n");
+        s.push_str(&format!("- **Name**: {}\n", name));
+        s.push_str(&format!("- **ID**: {}\n", info.id.as_deref().unwrap_or(container_id)));
+        let host = self.containers.read().unwrap().iter().find(|c| c.id == container_id).and_then(|c| c.host.clone());
+        s.push_str(&format!("- **Host**: {}\n", host.unwrap_or_else(|| self.docker_context.clone())));
+        s.push_str(&format!("- **Image**: {}\n", config.image.as_deref().unwrap_or("?")));
+        s.push_str(&format!("- **Image ID**: {}\n", image_id));
+        for digest in &digests {
+            s.push_str(&format!("- **Digest**: {}\n", digest));
+        }
+        let provenance = config.labels.as_ref().map(Provenance::from_labels).unwrap_or_default();
+        if let Some(url) = provenance.commit_url().or_else(|| provenance.source_url()) {
+            s.push_str(&format!("- **Built from**: {}\n", url));
+        }
+        s.push_str(&format!("- **State**: {}", state.status.map(|st| st.to_string()).unwrap_or_else(|| "unknown".to_string())));
+        if state.oom_killed == Some(true) {
+            s.push_str(" (OOM killed)");
+        }
+        s.push('\n');
+        if let Some(started) = state.started_at.as_deref().filter(|t| !t.starts_with("0001")) {
+            s.push_str(&format!("- **Started**: {}\n", started));
+        }
+        if let Some(finished) = state.finished_at.as_deref().filter(|t| !t.starts_with("0001")) {
+            s.push_str(&format!("- **Finished**: {} (exit code {})\n", finished, state.exit_code.unwrap_or(0)));
+        }
+        s.push_str(&format!("- **Restarts**: {}\n", info.restart_count.unwrap_or(0)));
+        s.push_str(&format!("- **Report generated**: {}\n\n", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+
+        if let Some(stats) = self.container_stats.read().unwrap().get(container_id) {
+            s.push_str("## Resource Usage\n\n");
+            let taken = chrono::DateTime::from_timestamp(stats.last_updated, 0).unwrap_or_default();
+            s.push_str(&format!("- **CPU**: {:.1}% (user {:.1}%, system {:.1}%)\n", stats.cpu_percent, stats.user_cpu_percent, stats.system_cpu_percent));
+            let limit = if stats.memory_limit > 0 { format!(" of {}", format_bytes(stats.memory_limit)) } else { String::new() };
+            s.push_str(&format!("- **Memory**: {}{} ({} cache)\n", format_bytes(stats.memory_usage), limit, format_bytes(stats.cached_memory)));
+            s.push_str(&format!("- **Sampled**: {}\n\n", taken.format("%Y-%m-%d %H:%M:%S UTC")));
+        }
+
+        let health = fetch_health_info(&docker, container_id).await.ok();
+        if let Some(health) = health.filter(|h| h.status != HealthStatus::NoHealthCheck) {
+            s.push_str("## Health\n\n");
+            s.push_str(&format!("- **Status**: {:?} (failing streak {})\n\n", health.status, health.failing_streak));
+            if !health.check_history.is_empty() {
+                s.push_str("| Time | Exit | Output |\n|---|---|---|\n");
+                for check in &health.check_history {
+                    let output = check.output.trim().replace('|', "\\|").replace('\n', " ");
+                    s.push_str(&format!("| {} | {} | {} |\n", check.timestamp.format("%H:%M:%S"), check.exit_code, output));
+                }
+                s.push('\n');
+            }
+        }
+
+        let timeline = self.container_timelines.read().unwrap().get(container_id).cloned().unwrap_or_default();
+        if !timeline.is_empty() {
+            s.push_str("## Recent Events\n\n");
+            for event in timeline.iter().rev().take(10).rev() {
+                let time = chrono::DateTime::from_timestamp(event.time, 0).unwrap_or_default();
+                let exit = event.exit_code.map(|c| format!(" (exit {})", c)).unwrap_or_default();
+                s.push_str(&format!("- {} {}{}\n", time.format("%Y-%m-%d %H:%M:%S"), event.action, exit));
+            }
+            s.push('\n');
+        }
+
+        let logs = fetch_recent_logs(&docker, container_id, INCIDENT_LOG_TAIL).await.unwrap_or_default();
+        let errors: Vec<&String> = logs.iter()
+            .filter(|line| {
+                let line = line.to_lowercase();
+                ERROR_MARKERS.iter().any(|marker| line.contains(marker))
+            })
+            .collect();
+        s.push_str(&format!("## Recent Errors\n\n{} of the last {} log lines look like errors", errors.len(), logs.len()));
+        if errors.is_empty() {
+            s.push_str(".\n\n");
+        } else {
+            let shown = &errors[errors.len().saturating_sub(INCIDENT_MAX_ERRORS)..];
+            s.push_str(&format!("; the last {}:\n\n```text\n", shown.len()));
+            for line in shown {
+                s.push_str(line);
+                s.push('\n');
+            }
+            s.push_str("```\n\n");
+        }
+
+        s.push_str("## Timeline\n\n<!-- Detection, mitigation and resolution times -->\n\n");
+        s.push_str("## Next Steps\n\n- [ ] \n");
+        Ok(s)
+    }
+
+    pub async fn open_file_browser(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        match self.list_files(&container.id, "/").await {
+            Ok(entries) => self.push_modal(Modal::Files {
+                container_id: container.id,
+                container_name: container.name,
+                path: "/".to_string(),
+                entries,
+                selected: 0,
+            }),
+            Err(e) => self.show_toast(format!("Failed to list files in {}: {}", container.name, e), true),
+        }
+    }
+
+    /// Lists `path` in a container. The `ls` exec behind it is audited like any
+    /// other, and refused when it can't be.
+    async fn list_files(&self, container_id: &str, path: &str) -> Result<Vec<FileEntry>> {
+        let docker = self.docker_for(container_id);
+        let audit = self.config.read().unwrap().exec_audit.clone();
+        let mut session = ExecSession::start(&audit, &docker.endpoint, &self.container_label(container_id), &list_command(path))
+            .map_err(|e| {
+                App::log_error(&self.error_log, "exec audit", format!("{:#}", e));
+                AppError::Other(format!("Exec refused: {:#}", e))
+            })?;
+        let result = list_directory(&docker, container_id, path, session.take_transcript()).await;
+        if let Err(e) = session.finish(result.as_ref().map(|_| ()).map_err(|e| e.to_string())) {
+            App::log_error(&self.error_log, "exec audit", format!("{:#}", e));
+        }
+        result
+    }
+
+    /// Lists `path` in the open file browser, staying put if it can't be read.
+    pub async fn browse_to(&mut self, path: String) {
+        let Some(Modal::Files { container_id, .. }) = self.top_modal() else { return };
+        let result = self.list_files(&container_id.clone(), &path).await;
+        match result {
+            Ok(listed) => {
+                if let Some(Modal::Files { path: current, entries, selected, .. }) = self.top_modal_mut() {
+                    // Going up keeps the directory we came from selected
+                    let came_from = current.strip_prefix(path.trim_end_matches('/')).map(|rest| rest.trim_start_matches('/').to_string());
+                    *selected = came_from.and_then(|name| listed.iter().position(|e| e.name == name)).unwrap_or(0);
+                    *current = path;
+                    *entries = listed;
+                }
+            }
+            Err(e) => self.show_toast(format!("Failed to list {}: {}", path, e), true),
+        }
+    }
+
+    /// Downloads the selected file or directory as `./<container>-<name>-<time>.tar`.
+    pub fn download_selected_file(&self) {
+        let Some(Modal::Files { container_id, container_name, path, entries, selected }) = self.top_modal() else { return };
+        let Some(entry) = entries.get(*selected) else { return };
+        let source = join_container_path(path, &entry.name);
+        let dest = format!("{}-{}-{}.tar", container_name, entry.name, Utc::now().format("%Y%m%d-%H%M%S"));
+        let docker = self.docker_for(container_id);
+        let container_id = container_id.clone();
+        let toast = self.toast.clone();
+        self.show_toast(format!("Downloading {} to {}", source, dest), false);
+
+        tokio::spawn(async move {
+            let toast_msg = match download_path(&docker, &container_id, &source, std::path::Path::new(&dest)).await {
+                Ok(written) => Toast::new(format!("Saved {} to {} ({})", source, dest, format_bytes(written)), false),
+                Err(e) => Toast::new(format!("Downloading {} failed: {}", source, e), true),
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+        });
+    }
+
+    /// Shows the selected container's processes, refreshed on the stats interval.
+    pub fn open_processes(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        if container.state != "running" {
+            self.show_toast(format!("{} is not running", container.name), true);
+            return;
+        }
+        let list = Arc::new(RwLock::new(None));
+        let weak = Arc::downgrade(&list);
+        let docker = self.docker_for(&container.id);
+        let config = self.config.clone();
+        let id = container.id.clone();
+        tokio::spawn(async move {
+            let mut fetch = true;
+            loop {
+                if fetch {
+                    let result = top_processes(&docker, &id).await.map_err(|e| e.to_string());
+                    let Some(list) = weak.upgrade() else { break };
+                    *list.write().unwrap() = Some(result);
+                }
+                let refresh_rate = config.read().unwrap().refresh_rate.clone();
+                // With manual refresh only `r` in the modal fetches again
+                let (wait, next) = match refresh_rate {
+                    RefreshRate::Interval(d) => (d, true),
+                    RefreshRate::Manual => (Duration::from_millis(500), false),
+                };
+                tokio::time::sleep(wait).await;
+                if weak.strong_count() == 0 {
+                    break;
+                }
+                fetch = next;
+            }
+        });
+        self.push_modal(Modal::Processes { container_id: container.id, container_name: container.name, list, selected: 0 });
+    }
+
+    pub async fn refresh_processes(&self) {
+        let Some(Modal::Processes { container_id, list, .. }) = self.top_modal() else { return };
+        let result = top_processes(&self.docker_for(container_id), container_id).await.map_err(|e| e.to_string());
+        *list.write().unwrap() = Some(result);
+    }
+
+    /// Lists what the selected container changed relative to its image, or
+    /// reloads the list when it's already open.
+    pub async fn open_fs_diff(&mut self) {
+        let (container_id, container_name) = match self.top_modal() {
+            Some(Modal::FsDiff { container_id, container_name, .. }) => (container_id.clone(), container_name.clone()),
+            _ => match self.selected_container() {
+                Some(container) => (container.id, container.name),
+                None => return,
+            },
+        };
+        let changes = match filesystem_changes(&self.docker_for(&container_id), &container_id).await {
+            Ok(changes) => changes,
+            Err(e) => {
+                self.show_toast(format!("Failed to diff {}: {}", container_name, e), true);
+                return;
+            }
+        };
+        if let Some(Modal::FsDiff { changes: current, selected, .. }) = self.top_modal_mut() {
+            *selected = (*selected).min(changes.len().saturating_sub(1));
+            *current = changes;
+        } else {
+            self.push_modal(Modal::FsDiff { container_id, container_name, changes, filter: None, selected: 0 });
+        }
+    }
+
+    /// Upload form for the open file browser, targeting the directory being viewed.
+    pub fn open_upload_dialog(&mut self) {
+        let Some(Modal::Files { container_id, container_name, path, .. }) = self.top_modal() else { return };
+        let mut dest = TextInput::with_validator(validate_container_dir);
+        dest.set_value(path.clone());
+        let form = Form::new(format!("Upload to {}", container_name))
+            .label("Directories are copied with their contents")
+            .text("source", "Local file or directory", TextInput::with_validator(validate_local_path))
+            .text("dest", "Container directory", dest);
+        let (container_id, container_name) = (container_id.clone(), container_name.clone());
+        self.push_modal(Modal::Upload { container_id, container_name, form });
+    }
+
+    /// Uploads right away unless `dest` already has an entry with the source's
+    /// name, in which case the overwrite has to be confirmed first.
+    pub async fn request_upload(&mut self, container_id: String, container_name: String, source: String, dest: String) {
+        let name = std::path::Path::new(&source).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        match self.list_files(&container_id, &dest).await {
+            Ok(entries) if entries.iter().any(|e| e.name == name) => {
+                self.push_modal(Modal::UploadOverwriteConfirm { container_id, container_name, source, dest });
+            }
+            Ok(_) => self.start_upload(container_id, container_name, source, dest),
+            Err(e) => self.show_toast(format!("Can't upload to {}: {}", dest, e), true),
+        }
+    }
+
+    pub fn start_upload(&self, container_id: String, container_name: String, source: String, dest: String) {
+        let docker = self.docker_for(&container_id);
+        let toast = self.toast.clone();
+        self.show_toast(format!("Uploading {} to {}:{}", source, container_name, dest), false);
+
+        tokio::spawn(async move {
+            let toast_msg = match upload_path(&docker, &container_id, std::path::Path::new(&source), &dest).await {
+                Ok(size) => Toast::new(format!("Uploaded {} to {}:{} ({}, r reloads the browser)", source, container_name, dest, format_bytes(size)), false),
+                Err(e) => Toast::new(format!("Uploading {} failed: {}", source, e), true),
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+        });
+    }
+
+    pub fn open_export_stats_dialog(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let mut minutes = TextInput::with_validator(validate_minutes);
+        minutes.set_value("5");
+        let form = Form::new(format!("Export stats: {}", container.name))
+            .label("CSV of CPU/memory samples (history keeps the last 100)")
+            .text("minutes", "Last N minutes", minutes)
+            .select("destination", "Destination", vec!["Clipboard".to_string(), "File".to_string()], 0);
+        self.push_modal(Modal::ExportStats { container_id: container.id, container_name: container.name, form });
+    }
+
+    /// Copies (or writes to `./<name>-stats-<time>.csv`) the stats samples of the
+    /// last `minutes` minutes.
+    pub fn export_stats(&self, container_id: &str, container_name: &str, minutes: u32, to_file: bool) {
+        let since = Utc::now().timestamp() - i64::from(minutes) * 60;
+        let csv = match self.container_stats.read().unwrap().get(container_id) {
+            Some(stats) => stats.to_csv(since),
+            None => {
+                self.show_toast(format!("No stats collected for {} yet", container_name), true);
+                return;
+            }
+        };
+        let samples = csv.lines().count().saturating_sub(1);
+
+        let result = if to_file {
+            let path = format!("{}-stats-{}.csv", container_name, Utc::now().format("%Y%m%d-%H%M%S"));
+            std::fs::write(&path, &csv).map(|_| format!("Wrote {} samples to {}", samples, path))
+        } else {
+            crate::clipboard::copy_to_clipboard(&csv).map(|_| format!("Copied {} samples to clipboard", samples))
+        };
+        match result {
+            Ok(message) => self.show_toast(message, false),
+            Err(e) => self.show_toast(format!("Export failed: {}", e), true),
+        }
+    }
+
+    /// Writes run scripts for every running container into a new snapshot directory.
+    pub fn export_snapshot(&self) {
+        let docker = self.docker.clone();
+        let toast = self.toast.clone();
+        let dir = crate::default_snapshot_dir();
+        self.show_toast(format!("Exporting running containers to {}", dir.display()), false);
+
+        tokio::spawn(async move {
+            let toast_msg = match export_running_containers(&docker, &dir).await {
+                Ok(written) => Toast::new(format!("Exported {} container(s) to {}", written.len().saturating_sub(SNAPSHOT_SHARED_FILES), dir.display()), false),
+                Err(e) => Toast::new(format!("Export failed: {}", e), true),
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+        });
+    }
+
+    pub fn selected_project(&self) -> Option<String> {
+        self.selected_container()
+            .and_then(|c| c.compose_project().map(|p| p.to_string()))
+    }
+
+    pub fn show_toast(&self, message: String, is_error: bool) {
+        if is_error {
+            tracing::warn!(%message, "error shown to user");
+        }
+        *self.toast.write().unwrap() = Some(Toast::new(message, is_error));
+    }
+
+    /// Loads the compose file(s) of the selected container's project into a viewer.
+    pub fn open_compose_file(&mut self) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        let files = container.compose_config_files();
+        if files.is_empty() {
+            self.show_toast("No compose config files recorded in container labels".to_string(), true);
+            return;
+        }
+
+        let mut content = String::new();
+        for path in &files {
+            match std::fs::read_to_string(path) {
+                Ok(text) => {
+                    if files.len() > 1 {
+                        content.push_str(&format!("# --- {} ---\n", path.display()));
+                    }
+                    content.push_str(&text);
+                    if !text.ends_with('\n') {
+                        content.push('\n');
+                    }
+                }
+                Err(e) => {
+                    self.show_toast(format!("Cannot read {}: {}", path.display(), e), true);
+                    return;
+                }
+            }
+        }
+
+        let title = files.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", ");
+        self.push_modal(Modal::TextViewer { title, content, syntax: Syntax::Yaml });
+    }
+
+    /// Shows a `docker run` command that recreates the selected container from
+    /// its inspect data; `c` in the viewer copies it.
+    pub async fn open_run_command(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let docker = self.docker_for(&container.id);
+        let info = match inspect_container(&docker, &container.id).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.show_toast(format!("Failed to inspect {}: {}", container.name, e), true);
+                return;
+            }
+        };
+        // Without the image, settings it provides are repeated in the command
+        let image = match info.image.as_deref() {
+            Some(id) => inspect_image(&docker, id).await.ok(),
+            None => None,
+        };
+        let content = format!("{}\n", run_command(&info, image.as_ref()));
+        self.push_modal(Modal::TextViewer { title: format!("docker run: {}", container.name), content, syntax: Syntax::Plain });
+    }
+
+    /// Shows the selected container as a compose service, for moving an ad-hoc
+    /// container into a compose file; `c` in the viewer copies it.
+    pub async fn open_compose_snippet(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        let docker = self.docker_for(&container.id);
+        let info = match inspect_container(&docker, &container.id).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.show_toast(format!("Failed to inspect {}: {}", container.name, e), true);
+                return;
+            }
+        };
+        let image = match info.image.as_deref() {
+            Some(id) => inspect_image(&docker, id).await.ok(),
+            None => None,
+        };
+        let content = compose_file(&[(info, image)]);
+        self.push_modal(Modal::TextViewer { title: format!("compose service: {}", container.name), content, syntax: Syntax::Yaml });
+    }
+
+    pub fn run_project_action(&mut self, action: ContainerAction) {
+        let Some(project) = self.selected_project() else {
+            return;
+        };
+        let host = self.selected_container().and_then(|c| c.host);
+        let ids: Vec<String> = self.containers.read().unwrap()
+            .iter()
+            .filter(|c| c.host == host && c.compose_project() == Some(project.as_str()))
+            .map(|c| c.id.clone())
+            .collect();
+        self.container_action(format!("project '{}'", project), ids, action);
+    }
+
+    /// Opens the chaos menu for the selected container's project. Running it
+    /// takes typing the project name, so it can't be set off by a stray key.
+    pub fn open_chaos_dialog(&mut self) {
+        let (Some(project), Some(container)) = (self.selected_project(), self.selected_container()) else { return };
+        let mut secs = TextInput::with_validator(validate_seconds);
+        secs.set_value("10");
+        let form = Form::new(format!("Chaos: {}", project))
+            .label("Disrupts a running container of this project")
+            .select("action", "Action", ChaosAction::NAMES.iter().map(|s| s.to_string()).collect(), 0)
+            .select("target", "Target", vec!["Random container".to_string(), container.name.clone()], 0)
+            .text("secs", "N (seconds)", secs)
+            .text("confirm", "Type the project name to confirm", TextInput::new());
+        self.push_modal(Modal::Chaos { project, form });
+    }
+
+    /// Runs a chaos action against `target` (a container name), or a random
+    /// running container of the project when None. Each step goes to the Output pane.
+    pub fn start_chaos(&mut self, project: String, action: ChaosAction, target: Option<String>) {
+        let host = self.selected_container().and_then(|c| c.host);
+        let candidates: Vec<(String, String)> = self.containers.read().unwrap()
+            .iter()
+            .filter(|c| c.host == host && c.compose_project() == Some(project.as_str()) && c.state == "running")
+            .filter(|c| target.as_ref().is_none_or(|t| *t == c.name))
+            .map(|c| (c.id.clone(), c.name.clone()))
+            .collect();
+        if candidates.is_empty() {
+            self.show_toast(format!("No running container to disrupt in '{}'", project), true);
+            return;
+        }
+        // Randomly seeded hasher, to avoid a dependency just for one pick
+        let pick = std::hash::BuildHasher::hash_one(&std::hash::RandomState::new(), Utc::now().timestamp_nanos_opt()) as usize % candidates.len();
+        let (id, name) = candidates[pick].clone();
+
+        let docker = self.docker_for_host(host.as_deref());
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+        let step = move |output: &Arc<RwLock<Vec<String>>>, line: String| {
+            tracing::info!(%line, "chaos");
+            App::log_output(output, format!("[chaos {}] {}", chrono::Local::now().format("%H:%M:%S"), line));
+        };
+
+        tokio::spawn(async move {
+            let result = match action {
+                ChaosAction::Kill => {
+                    step(&output, format!("killing {}", name));
+                    kill_container(&docker, &id, "SIGKILL").await
+                }
+                ChaosAction::Pause(secs) => {
+                    step(&output, format!("pausing {} for {}s", name, secs));
+                    match pause_container(&docker, &id).await {
+                        Ok(()) => {
+                            tokio::time::sleep(Duration::from_secs(secs)).await;
+                            step(&output, format!("unpausing {}", name));
+                            unpause_container(&docker, &id).await
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                ChaosAction::Restart(secs) => {
+                    step(&output, format!("stopping {}, starting again in {}s", name, secs));
+                    match stop_container(&docker, &id).await {
+                        Ok(()) => {
+                            tokio::time::sleep(Duration::from_secs(secs)).await;
+                            step(&output, format!("starting {}", name));
+                            start_container(&docker, &id).await
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+            };
+            let toast_msg = match result {
+                Ok(()) => {
+                    step(&output, format!("done with {}", name));
+                    Toast::new(format!("Chaos on {} finished", name), false)
+                }
+                Err(e) => {
+                    step(&output, format!("failed on {}: {}", name, e));
+                    Toast::new(format!("Chaos on {} failed: {}", name, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+
+            if let Ok(list) = list_containers(&docker, show_all.load(Ordering::Relaxed)).await {
+                App::replace_host_containers(&containers, host.as_deref(), list);
+            }
+        });
+    }
+
+    /// Runs `action` against every id concurrently, reporting progress through
+    /// the toast. Failures are named in the final toast and logged with their
+    /// errors to the error console.
+    pub fn run_bulk_action(&self, label: String, ids: Vec<String>, action: ContainerAction) {
+        if ids.is_empty() {
+            return;
+        }
+
+        // Each container is acted on through the client of the host it runs on
+        let targets: Vec<(DockerClient, String, String)> = {
+            let containers = self.containers.read().unwrap();
+            ids.into_iter().map(|id| {
+                let name = containers.iter().find(|c| c.id == id).map_or_else(|| id.chars().take(12).collect(), |c| c.name.clone());
+                (self.docker_for(&id), id, name)
+            }).collect()
+        };
+        let mut hosts: Vec<Option<String>> = self.containers.read().unwrap().iter()
+            .filter(|c| targets.iter().any(|(_, id, _)| *id == c.id))
+            .map(|c| c.host.clone())
+            .collect();
+        hosts.sort();
+        hosts.dedup();
+        let hosts: Vec<(Option<String>, DockerClient)> = hosts.into_iter().map(|h| {
+            let client = self.docker_for_host(h.as_deref());
+            (h, client)
+        }).collect();
+        let toast = self.toast.clone();
+        let errors = self.error_log.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+        let total = targets.len();
+
+        *toast.write().unwrap() = Some(Toast::new(format!("{} {}: 0/{}", action.verb(), label, total), false));
+
+        tokio::spawn(async move {
+            let done = Arc::new(AtomicUsize::new(0));
+
+            let tasks = targets.into_iter().map(|(docker, id, name)| {
+                let toast = toast.clone();
+                let done = done.clone();
+                let label = label.clone();
+                async move {
+                    let result = apply_container_action(&docker, &id, action).await;
+                    let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+                    *toast.write().unwrap() = Some(Toast::new(format!("{} {}: {}/{}", action.verb(), label, finished, total), false));
+                    result.err().map(|e| (name, e.to_string()))
+                }
+            });
+            let failures: Vec<(String, String)> = futures::future::join_all(tasks).await.into_iter().flatten().collect();
+
+            let mut message = format!("{} {}: {} ok, {} failed", action.verb(), label, total - failures.len(), failures.len());
+            if !failures.is_empty() {
+                let names: Vec<&str> = failures.iter().map(|(name, _)| name.as_str()).collect();
+                message.push_str(&format!(" ({}; see ! for errors)", names.join(", ")));
+            }
+            for (name, error) in &failures {
+                App::log_error(&errors, &format!("{} {}", action.verb().to_lowercase(), name), error.clone());
+            }
+            *toast.write().unwrap() = Some(Toast::new(message, !failures.is_empty()));
+
+            for (host, client) in hosts {
+                if let Ok(list) = list_containers(&client, show_all.load(Ordering::Relaxed)).await {
+                    App::replace_host_containers(&containers, host.as_deref(), list);
+                }
+            }
+        });
+    }
+
+    /// Marks or unmarks the selected container and moves to the next row.
+    pub fn toggle_mark(&mut self) {
+        let Some(container) = self.selected_container() else { return };
+        if !self.marked.remove(&container.id) {
+            self.marked.insert(container.id);
+        }
+        self.next();
+    }
+
+    /// Marks every listed container, or clears the marks when they all are.
+    pub fn toggle_mark_all(&mut self) {
+        let all_marked = self.filtered_containers.iter().all(|c| self.marked.contains(&c.id));
+        if all_marked {
+            self.marked.clear();
+        } else {
+            self.marked.extend(self.filtered_containers.iter().map(|c| c.id.clone()));
+        }
+    }
+
+    /// Runs `action` on the marked containers that still exist, then clears the marks.
+    pub fn run_marked_action(&mut self, action: ContainerAction) {
+        let ids: Vec<String> = {
+            let containers = self.containers.read().unwrap();
+            self.marked.iter().filter(|id| containers.iter().any(|c| &c.id == *id)).cloned().collect()
+        };
+        self.marked.clear();
+        let label = format!("{} marked container{}", ids.len(), if ids.len() == 1 { "" } else { "s" });
+        self.container_action(label, ids, action);
+    }
+
+    /// Like [`App::run_bulk_action`], but removals wait out the undo window.
+    pub fn container_action(&mut self, label: String, ids: Vec<String>, action: ContainerAction) {
+        if action == ContainerAction::Remove {
+            if !ids.is_empty() {
+                self.defer(format!("Remove {}", label), DeferredAction::RemoveContainers { label, ids });
+            }
+        } else {
+            self.run_bulk_action(label, ids, action);
+        }
+    }
+
+    /// Queues the selected container's removal.
+    pub fn remove_selected_container(&mut self) {
+        if let Some(container) = self.selected_container() {
+            self.container_action(container.name, vec![container.id], ContainerAction::Remove);
+        }
+    }
+
+    /// Resolves the command at the `:` prompt to containers and asks to run it
+    /// on them. Stopped containers are listed first when the command asks for
+    /// them, and containers are inspected when it asks how long they have been
+    /// in a state.
+    pub async fn resolve_bulk_command(&mut self) {
+        let input = self.bulk_command.value().trim().to_string();
+        let command = match crate::command::parse(&input) {
+            Ok(command) => command,
+            Err(e) => return self.show_toast(e, true),
+        };
+        if command.needs_stopped() && !self.show_all.load(Ordering::Relaxed) {
+            self.show_all.store(true, Ordering::Relaxed);
+            if let Err(e) = self.refresh_containers().await {
+                return self.show_toast(format!("Failed to list containers: {}", e), true);
+            }
+            self.update_filtered_containers();
+        }
+
+        let mut candidates: Vec<ContainerInfo> = self.containers.read().unwrap().iter()
+            .filter(|c| command.matches(c))
+            .cloned()
+            .collect();
+        if command.needs_inspect() {
+            let now = Utc::now();
+            let checks = candidates.iter().map(|c| {
+                let docker = self.docker_for_host(c.host.as_deref());
+                let id = c.id.clone();
+                async move { inspect_container(&docker, &id).await.ok() }
+            });
+            let infos = futures::future::join_all(checks).await;
+            candidates = candidates.into_iter().zip(infos)
+                .filter(|(_, info)| info.as_ref().is_some_and(|info| command.matches_age(info, now)))
+                .map(|(c, _)| c)
+                .collect();
+        }
+        candidates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.bulk_command.commit_history();
+        if candidates.is_empty() {
+            self.show_toast(format!("No containers match '{}'", input), false);
+            return;
+        }
+        let targets = candidates.into_iter().map(|c| (c.id, c.name)).collect();
+        self.pop_modal();
+        self.push_modal(Modal::BulkCommandConfirm { command: input, action: command.action, targets });
+    }
+
+    /// Queues `action` to run once [`UNDO_WINDOW`] has passed, unless undone first.
+    pub fn defer(&mut self, description: String, action: DeferredAction) {
+        let due = std::time::Instant::now() + UNDO_WINDOW;
+        *self.toast.write().unwrap() = Some(Toast {
+            message: format!("{} in {}s (Ctrl+Z to undo)", description, UNDO_WINDOW.as_secs()),
+            is_error: false,
+            expires_at: due,
+        });
+        self.pending_actions.push_back(PendingAction { description, due, action });
+    }
+
+    /// Drops the most recently queued action before it reaches the daemon.
+    pub fn undo_last(&mut self) {
+        match self.pending_actions.pop_back() {
+            Some(pending) => self.show_toast(format!("Undone: {}", pending.description), false),
+            None => self.show_toast("Nothing to undo".to_string(), false),
+        }
+    }
+
+    /// Runs the queued actions whose undo window has passed. Called every tick.
+    pub async fn run_due_actions(&mut self) {
+        let now = std::time::Instant::now();
+        while self.pending_actions.front().is_some_and(|p| p.due <= now) {
+            let Some(pending) = self.pending_actions.pop_front() else { break };
+            tracing::info!(description = %pending.description, "running deferred action");
+            match pending.action {
+                DeferredAction::RemoveContainers { label, ids } => self.run_bulk_action(label, ids, ContainerAction::Remove),
+                DeferredAction::RemoveImage { id, name, force } => {
+                    match remove_image(&self.docker, &id, force).await {
+                        Ok(()) => self.show_toast(format!("Removed {}", name), false),
+                        Err(e) => self.show_toast(format!("Removing {} failed: {}", name, e), true),
+                    }
+                    let _ = self.refresh_images().await;
+                }
+                DeferredAction::Cleanup(kind) => self.start_cleanup(kind),
+                DeferredAction::Prune(kinds) => self.start_prune(kinds),
+                DeferredAction::PruneBuildCache { keep_mb, older_than_hours } => self.start_build_cache_prune(keep_mb, older_than_hours),
+                DeferredAction::RemoveStack(name) => self.start_remove_stack(name),
+            }
+        }
+    }
+
+    /// Compares the selected container's clock with the host's by running
+    /// `date` in it, and appends the result to its details. This is an exec
+    /// like any other, so it is refused in read-only mode and audited.
+    pub fn check_clock(&mut self) {
+        if self.deny_read_only("exec") {
+            return;
+        }
+        let Some(container) = self.selected_container() else { return };
+        if container.state != "running" {
+            self.show_toast("The clock check needs a running container".to_string(), true);
+            return;
+        }
+        let docker = self.docker_for(&container.id);
+        let audit = self.config.read().unwrap().exec_audit.clone();
+        let mut session = match ExecSession::start(&audit, &docker.endpoint, &container.name, CLOCK_COMMAND) {
+            Ok(session) => session,
+            Err(e) => {
+                App::log_error(&self.error_log, "exec audit", format!("{:#}", e));
+                self.show_toast(format!("Exec refused: {:#}", e), true);
+                return;
+            }
+        };
+        let transcript = session.take_transcript();
+        let details_lock = self.selected_container_details.clone();
+        let errors = self.error_log.clone();
+        tokio::spawn(async move {
+            let check = check_container_clock(&docker, &container.id).await;
+            let outcome = check.as_ref().map(|_| ()).map_err(|e| e.to_string());
+            let section = format_clock_check(check);
+            if let Some(mut transcript) = transcript {
+                if let Err(e) = transcript.write(section.trim_start().as_bytes()).and_then(|()| transcript.finish()) {
+                    App::log_error(&errors, "exec audit", format!("Failed to write transcript: {}", e));
+                }
+            }
+            if let Err(e) = session.finish(outcome) {
+                App::log_error(&errors, "exec audit", format!("{:#}", e));
+            }
+            let mut details = details_lock.write().unwrap();
+            // Selection may have moved on meanwhile
+            if let Some(details) = details.as_mut().filter(|d| d.starts_with(&format!("ID: {}\n", container.id))) {
+                if let Some(start) = details.find(CLOCK_SECTION) {
+                    details.truncate(start);
+                }
+                details.push_str(&section);
+            }
+        });
+    }
+
+    pub fn trigger_fetch(&mut self, container_id: String) {
+        if self.last_fetched_id.as_ref() == Some(&container_id) {
+            return;
+        }
+        
+        self.last_fetched_id = Some(container_id.clone());
+        self.details_scroll = 0;
+        
+        {
+            let mut details = self.selected_container_details.write().unwrap();
+            *details = None;
+            let mut logs = self.selected_container_logs.write().unwrap();
+            logs.clear();
+        }
+
+        let docker = self.docker_for(&container_id);
+        let details_lock = self.selected_container_details.clone();
+        let id_clone = container_id.clone();
+        let shared_host = self.running_count > 1;
+        let timelines = self.container_timelines.clone();
+        let max_events = self.config.read().unwrap().buffer_limits(self.low_memory).timeline_events;
+
+        tokio::spawn(async move {
+            let details_res = inspect_container(&docker, &id_clone).await;
+            let state = details_res.as_ref().ok().and_then(|info| info.state.clone()).unwrap_or_default();
+            // finished_at is the zero time for containers that never exited
+            let last_exit = state.finished_at.as_deref()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.timestamp())
+                .filter(|t| *t > 0)
+                .map(|t| (t, state.exit_code.unwrap_or(0)));
+            let image = match details_res.as_ref().ok().and_then(|info| info.image.clone()) {
+                Some(image_id) => inspect_image(&docker, &image_id).await.ok(),
+                None => None,
+            };
+            let details_str = match details_res {
+                Ok(info) => format_details(info, image.as_ref(), shared_host),
+                Err(e) => format!("Error fetching details: {}", e),
+            };
+            *details_lock.write().unwrap() = Some(details_str.clone());
+
+            // Exit history needs more API calls, so append it once back
+            let since = Utc::now().timestamp() - TIMELINE_BACKFILL_HOURS * 3600;
+            if let Ok(entries) = container_history(&docker, &id_clone, since).await {
+                App::record_lifecycle(&timelines, &id_clone, entries, max_events);
+            }
+            let exits: Vec<(i64, i64)> = timelines.read().unwrap()
+                .get(&id_clone)
+                .map(|t| t.iter().filter_map(|e| Some((e.time, e.exit_code?))).collect())
+                .unwrap_or_default();
+            let extra = format_exit_history(exits, last_exit);
+
+            let mut details = details_lock.write().unwrap();
+            // Selection may have moved on meanwhile
+            if details.as_deref() == Some(details_str.as_str()) {
+                *details = Some(format!("{}{}", details_str, extra));
+            }
+        });
+
+        self.start_log_stream(container_id);
+    }
+
+    fn start_log_stream(&mut self, container_id: String) {
+        if let Some(handle) = self.log_stream_task.take() {
+            handle.abort();
+        }
+
+        if self.project_logs {
+            if let Some(project) = self.selected_project() {
+                self.start_project_log_stream(project);
+                return;
+            }
+        }
+
+        let docker = self.docker_for(&container_id);
+        let logs_lock = self.selected_container_logs.clone();
+        let limits = self.config.read().unwrap().buffer_limits(self.low_memory);
+        
+        let task = tokio::spawn(async move {
+            let mut stream = stream_logs(&docker, &container_id, &limits.log_tail.to_string());
+            
+            while let Some(log_result) = stream.next().await {
+                match log_result {
+                    Ok(log) => {
+                        let mut logs = logs_lock.write().unwrap();
+                        logs.push(log.to_string());
+                        if logs.len() > limits.log_lines {
+                            logs.remove(0);
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+        
+        self.log_stream_task = Some(task);
+    }
+
+    /// Merges the log streams of every container in `project`, prefixing each line
+    /// with its service name the way `docker compose logs -f` does.
+    fn start_project_log_stream(&mut self, project: String) {
+        let host = self.selected_container().and_then(|c| c.host);
+        let members: Vec<(String, String)> = self.containers.read().unwrap()
+            .iter()
+            .filter(|c| c.host == host && c.compose_project() == Some(project.as_str()))
+            .map(|c| (c.id.clone(), c.compose_service().unwrap_or(&c.name).to_string()))
+            .collect();
+        let width = members.iter().map(|(_, svc)| svc.len()).max().unwrap_or(0);
+
+        let docker = self.docker_for_host(host.as_deref());
+        let logs_lock = self.selected_container_logs.clone();
+        let limits = self.config.read().unwrap().buffer_limits(self.low_memory);
+
+        let task = tokio::spawn(async move {
+            let streams = members.into_iter().map(|(id, service)| {
+                let prefix = format!("{:<width$} | ", service, width = width);
+                stream_logs(&docker, &id, &limits.project_log_tail.to_string())
+                    .map(move |res| res.map(|log| format!("{}{}", prefix, log)))
+                    .boxed()
+            });
+            let mut merged = futures::stream::select_all(streams);
+
+            while let Some(log_result) = merged.next().await {
+                if let Ok(line) = log_result {
+                    let mut logs = logs_lock.write().unwrap();
+                    logs.push(line);
+                    if logs.len() > limits.log_lines {
+                        logs.remove(0);
+                    }
+                }
+            }
+        });
+
+        self.log_stream_task = Some(task);
+    }
+
+    pub fn open_jump_to_time_dialog(&mut self) {
+        if self.selected_container_logs.read().unwrap().is_empty() {
+            self.show_toast("No log lines loaded".to_string(), true);
+            return;
+        }
+        let form = Form::new("Jump to Time")
+            .text("time", "Time (HH:MM[:SS], optionally after YYYY-MM-DD)", TextInput::with_validator(validate_log_time));
+        self.push_modal(Modal::JumpToTime { form });
+    }
+
+    pub fn open_group_by_dialog(&mut self) {
+        let mut input = TextInput::new();
+        input.set_value(self.group_by.as_ref().map(|g| g.label().to_string()).unwrap_or_default());
+        let form = Form::new("Group Containers")
+            .text("key", "Group by (project, a label key, or empty for none)", input);
+        self.push_modal(Modal::GroupBy { form });
+    }
+
+    /// Selects the loaded log line whose timestamp is closest to `target`.
+    pub fn jump_logs_to_time(&mut self, target: chrono::DateTime<chrono::Local>) {
+        let Some(target_nanos) = target.timestamp_nanos_opt() else { return };
+        let nearest = self.selected_container_logs.read().unwrap().iter()
+            .enumerate()
+            .filter_map(|(i, line)| line_time(line).map(|t| (i, t)))
+            .min_by_key(|(_, t)| (t - target_nanos).abs());
+        let Some((i, time)) = nearest else {
+            self.show_toast("The loaded log lines have no timestamps".to_string(), true);
+            return;
+        };
+        self.auto_scroll = false;
+        self.logs_state.select(Some(i));
+        let found = chrono::DateTime::from_timestamp_nanos(time).with_timezone(&chrono::Local);
+        // Further off than a minute means the time is outside what is loaded
+        let note = if (time - target_nanos).abs() > 60_000_000_000 { " (nearest loaded line)" } else { "" };
+        self.show_toast(format!("Jumped to {}{}", found.format("%Y-%m-%d %H:%M:%S"), note), false);
+    }
+
+    pub fn toggle_project_logs(&mut self) {
+        self.project_logs = !self.project_logs;
+        // Force the log stream to be rebuilt on the next fetch
+        self.last_fetched_id = None;
+    }
+
+    pub async fn restart_container(&mut self) -> Result<()> {
+        if let Some(container) = self.selected_container() {
+            let result = restart_container(&self.docker_for(&container.id), &container.id).await;
+            self.report_timeout(&container.name, &result);
+            result?;
+        }
+        Ok(())
+    }
+
+    pub async fn stop_container(&mut self) -> Result<()> {
+        if let Some(container) = self.selected_container() {
+            let result = stop_container(&self.docker_for(&container.id), &container.id).await;
+            self.report_timeout(&container.name, &result);
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Tells the user when the daemon stopped answering; the key handlers
+    /// otherwise drop action errors and the next refresh shows the state.
+    fn report_timeout<T>(&self, name: &str, result: &Result<T>) {
+        if let Err(e @ AppError::Timeout { .. }) = result {
+            self.show_toast(format!("{}: {} (the daemon may be hung)", name, e), true);
+        }
+    }
+
+    pub async fn start_container(&mut self) -> Result<()> {
+        if let Some(container) = self.selected_container() {
+            start_container(&self.docker_for(&container.id), &container.id).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn pause_container(&mut self) -> Result<()> {
+        if let Some(container) = self.selected_container() {
+            if container.state == "running" {
+                pause_container(&self.docker_for(&container.id), &container.id).await?;
+                self.refresh_containers().await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn unpause_container(&mut self) -> Result<()> {
+        if let Some(container) = self.selected_container() {
+            if container.state == "paused" {
+                unpause_container(&self.docker_for(&container.id), &container.id).await?;
+                self.refresh_containers().await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn refresh_images(&mut self) -> Result<()> {
+        let show_dangling = self.show_dangling.load(Ordering::Relaxed);
+        let images_result = list_images(&self.docker, show_dangling).await?;
+        
+        self.total_images = images_result.len();
+        self.total_image_size = images_result.iter().map(|i| i.size as u64).sum();
+        self.images_updated.store(Utc::now().timestamp(), Ordering::Relaxed);
+
+        // Usage needs a pass over the event backlog, so it fills in after the list
+        if self.config.read().unwrap().buffer_limits(self.low_memory).image_usage {
+            let docker = self.docker.clone();
+            let usage = self.image_usage.clone();
+            let errors = self.error_log.clone();
+            tokio::spawn(async move {
+                match image_usage(&docker, IMAGE_USAGE_DAYS).await {
+                    Ok(result) => *usage.write().unwrap() = result,
+                    Err(e) => App::log_error(&errors, "image usage", e.to_string()),
+                }
+            });
+        }
+
+        let mut images = self.images.write().unwrap();
+        *images = images_result;
+        
+        match self.image_sort {
+            SortOrder::CreatedDesc => images.sort_by_key(|i| std::cmp::Reverse(i.created)),
+            SortOrder::CreatedAsc => images.sort_by_key(|i| i.created),
+            SortOrder::SizeDesc => images.sort_by_key(|i| std::cmp::Reverse(i.size)),
+            SortOrder::SizeAsc => images.sort_by_key(|i| i.size),
+            SortOrder::HealthDesc | SortOrder::HealthAsc | SortOrder::CpuDesc | SortOrder::MemDesc | SortOrder::NameAsc | SortOrder::UptimeDesc => {
+                images.sort_by_key(|i| std::cmp::Reverse(i.created));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.image_sort = match self.image_sort {
+            SortOrder::CreatedDesc => SortOrder::CreatedAsc,
+            SortOrder::CreatedAsc => SortOrder::SizeDesc,
+            SortOrder::SizeDesc => SortOrder::SizeAsc,
+            SortOrder::SizeAsc => SortOrder::CreatedDesc,
+            _ => SortOrder::CreatedDesc,
+        };
+    }
+
+    pub fn next_image(&mut self) {
+        if self.total_images == 0 { return; }
+        let i = match self.table_state_images.selected() {
+            Some(i) => if i >= self.total_images - 1 { 0 } else { i + 1 },
+            None => 0,
+        };
+        self.table_state_images.select(Some(i));
+    }
+
+    pub fn previous_image(&mut self) {
+        if self.total_images == 0 { return; }
+        let i = match self.table_state_images.selected() {
+            Some(i) => if i == 0 { self.total_images - 1 } else { i - 1 },
+            None => 0,
+        };
+        self.table_state_images.select(Some(i));
+    }
+
+    pub fn selected_image(&self) -> Option<ImageInfo> {
+        self.images.read().unwrap().get(self.table_state_images.selected()?).cloned()
+    }
+
+    /// Shows the selected image's details. With `check_registry`, also looks
+    /// up its pull size unless that is already known.
+    pub fn trigger_image_details(&mut self, check_registry: bool) {
+        if let Some(image) = self.selected_image() {
+            let docker = self.docker.clone();
+            let details_lock = self.selected_image_details.clone();
+            let pull_sizes = self.image_pull_sizes.clone();
+            let max_rate = self.config.read().unwrap().pull.max_kib_per_sec;
+            let proxy = self.proxy_config();
+            tokio::spawn(async move {
+                let info = match inspect_image(&docker, &image.id).await {
+                    Ok(info) => info,
+                    Err(e) => {
+                        *details_lock.write().unwrap() = Some(format!("Error: {}", e));
+                        return;
+                    }
+                };
+                let details = format_image_details(info.clone());
+                // The manifest lookup goes out to the registry, so it waits for Enter
+                let cached = pull_sizes.read().unwrap().get(&image.id).cloned();
+                let pull_size = match cached {
+                    Some(line) => line,
+                    None if !check_registry => PULL_SIZE_PROMPT.to_string(),
+                    None => {
+                        *details_lock.write().unwrap() = Some(format!("{}Pull size: checking registry...\n", details));
+                        let line = format_pull_size(&info, max_rate, &proxy).await;
+                        pull_sizes.write().unwrap().insert(image.id.clone(), line.clone());
+                        line
+                    }
+                };
+                let mut current = details_lock.write().unwrap();
+                // Selection may have moved on while the registry answered
+                if !check_registry || current.as_deref().is_some_and(|c| c.starts_with(&details)) {
+                    *current = Some(format!("{}{}", details, pull_size));
+                }
+            });
+        }
+    }
+
+    /// Queues the selected image's removal.
+    pub fn remove_current_image(&mut self, force: bool) {
+        if let Some(image) = self.selected_image() {
+            let name = image.repo_tags.iter()
+                .find(|t| *t != "<none>:<none>")
+                .cloned()
+                .unwrap_or_else(|| image.id.trim_start_matches("sha256:").chars().take(12).collect());
+            self.defer(format!("Remove {}", name), DeferredAction::RemoveImage { id: image.id, name, force });
+        }
+    }
+
+    pub async fn prune_images(&mut self) -> Result<()> {
+        prune_images(&self.docker).await?;
+        self.refresh_images().await?;
+        Ok(())
+    }
+
+    /// Registry the pull dialog currently points at, if not pulling as typed.
+    pub fn pull_registry(&self) -> Option<String> {
+        self.pull_target.checked_sub(1).and_then(|i| self.registry_info.targets().get(i).cloned())
+    }
+
+    pub fn cycle_pull_target(&mut self) {
+        self.pull_target = (self.pull_target + 1) % (self.registry_info.targets().len() + 1);
+    }
+
+    /// Steps the pulling daemon through the primary and then each extra host.
+    pub fn cycle_pull_host(&mut self) {
+        let mut hosts: Vec<&String> = self.remote_clients.keys().collect();
+        hosts.sort();
+        self.pull_host = match &self.pull_host {
+            None => hosts.first().map(|h| h.to_string()),
+            Some(current) => hosts.iter().skip_while(|h| h.as_str() != current.as_str()).nth(1).map(|h| h.to_string()),
+        };
+    }
+
+    /// Where the pull runs, for the dialog: a daemon fetching from the registry
+    /// itself, or dockyard downloading here when pulls are rate-capped.
+    pub fn pull_side(&self) -> String {
+        match &self.pull_host {
+            Some(host) => format!("{}'s daemon, from that host's network", host),
+            None if self.config.read().unwrap().pull.max_kib_per_sec.is_some() => "dockyard on this machine (rate-capped)".to_string(),
+            None => format!("{}'s daemon", self.docker_context),
+        }
+    }
+
+    /// Image reference to pull, with the chosen mirror/registry prefix applied.
+    pub fn pull_reference(&self) -> String {
+        let image = self.pull_input.value().trim();
+        match self.pull_registry() {
+            Some(registry) => with_registry_prefix(&registry, image),
+            None => image.to_string(),
+        }
+    }
+
+    /// Pulls `image_name` with the daemon of the chosen host, relaying its
+    /// progress to the Output pane. A remote host's daemon fetches the image
+    /// itself, so registries only reachable from there (e.g. through the ssh://
+    /// connection) work.
+    pub fn start_pull_image(&mut self, image_name: String) {
+        let host = self.pull_host.clone().filter(|h| self.remote_clients.contains_key(h));
+        let docker = self.docker_for_host(host.as_deref());
+        let progress_lock = self.output_log.clone();
+        let is_pulling = self.is_pulling.clone();
+        let images_ref = self.images.clone();
+        let toast = self.toast.clone();
+        let notifications = self.notifications.clone();
+        // The Hub limit is per IP, and a remote daemon may pull from another one
+        let hub_limit = (host.is_none() && self.config.read().unwrap().hub_rate_limit.enabled && crate::ratelimit::is_docker_hub(&image_name))
+            .then(|| self.hub_rate_limit.clone());
+        let proxy = self.proxy_config();
+        if hub_limit.is_some() {
+            if let Some(warning) = self.hub_pull_warning(1) {
+                self.show_toast(warning, true);
+            }
+        }
+        
+        is_pulling.store(true, Ordering::Relaxed);
+        progress_lock.write().unwrap().clear();
+
+        // The capped pull downloads here, which can't reach registries that only the remote host can
+        let max_rate = self.config.read().unwrap().pull.max_kib_per_sec.filter(|_| host.is_none());
+        if let Some(host) = &host {
+            App::log_output(&progress_lock, format!("Pulling {} with the daemon on {}", image_name, host));
+        }
+        let primary = self.docker.clone();
+
+        tokio::spawn(async move {
+            let mut failed = false;
+            if let Some(rate) = max_rate {
+                let progress = |line| App::log_output(&progress_lock, line);
+                if let Err(e) = pull_image_throttled(&docker, &image_name, rate, &proxy, progress).await {
+                    failed = true;
+                    *toast.write().unwrap() = Some(Toast::new(format!("Pull failed: {:#}", e), true));
+                    App::log_output(&progress_lock, format!("Error: {:#}", e));
+                    if e.is::<crate::registry::NeedsDaemonPull>() {
+                        App::log_output(&progress_lock, "Capped pulls only work for anonymously pullable images without foreign layers; remove [pull] max_kib_per_sec to pull it with the daemon".to_string());
+                    }
+                }
+            } else {
+                let mut stream = pull_image(&docker, image_name.clone());
+                while let Some(res) = stream.next().await {
+                    let mut progress = progress_lock.write().unwrap();
+                    match res {
+                        Ok(info) => {
+                            let msg = format!("{:?}", info); // Simplest conversion
+                            progress.push(msg);
+                        }
+                        Err(e) => {
+                            failed = true;
+                            if matches!(e, AppError::Timeout { .. }) {
+                                *toast.write().unwrap() = Some(Toast::new(format!("Pull stopped: {}", e), true));
+                            }
+                            progress.push(format!("Error: {}", e));
+                        }
+                    }
+                    if progress.len() > 100 { progress.remove(0); }
+                }
+            }
+            is_pulling.store(false, Ordering::Relaxed);
+            notifications.write().unwrap().push(NotifyEvent::PullDone);
+            // The Images view lists the primary's images only
+            if let (Some(host), false) = (&host, failed) {
+                *toast.write().unwrap() = Some(Toast::new(format!("Pulled {} on {}", image_name, host), false));
+            }
+
+            if let Ok(imgs) = list_images(&primary, false).await {
+                *images_ref.write().unwrap() = imgs;
+            }
+            if let Some(hub_limit) = hub_limit {
+                if let Ok(limit) = crate::ratelimit::check_hub(&proxy).await {
+                    *hub_limit.write().unwrap() = Some(limit);
+                }
+            }
+        });
+    }
+
+    /// A warning when `pulls` Docker Hub pulls would exceed what is left of
+    /// the last known limit.
+    pub fn hub_pull_warning(&self, pulls: u64) -> Option<String> {
+        let limit = (*self.hub_rate_limit.read().unwrap())?;
+        if pulls <= u64::from(limit.remaining) {
+            return None;
+        }
+        let window = if limit.window_secs > 0 {
+            format!(" per {}h", limit.window_secs.div_ceil(3600))
+        } else {
+            String::new()
+        };
+        Some(format!(
+            "Up to {} Docker Hub pull{} needed but only {} of {}{} left",
+            pulls, if pulls == 1 { "" } else { "s" }, limit.remaining, limit.limit, window,
+        ))
+    }
+
+    pub async fn refresh_services(&mut self) {
+        if self.docker.podman {
+            self.services.clear();
+            self.table_state_services.select(None);
+            self.services_error = Some(PODMAN_NO_SWARM.to_string());
+            return;
+        }
+        match list_services(&self.docker).await {
+            Ok(services) => {
+                self.services = services;
+                self.services_error = None;
+                if self.services.is_empty() {
+                    self.table_state_services.select(None);
+                } else {
+                    let i = self.table_state_services.selected().unwrap_or(0).min(self.services.len() - 1);
+                    self.table_state_services.select(Some(i));
+                }
+            }
+            Err(e) => {
+                self.services.clear();
+                self.table_state_services.select(None);
+                self.services_error = Some(e.to_string());
+            }
+        }
+    }
+
+    pub fn next_service(&mut self) {
+        if self.services.is_empty() { return; }
+        let i = match self.table_state_services.selected() {
+            Some(i) => if i >= self.services.len() - 1 { 0 } else { i + 1 },
+            None => 0,
+        };
+        self.table_state_services.select(Some(i));
+    }
+
+    pub fn previous_service(&mut self) {
+        if self.services.is_empty() { return; }
+        let i = match self.table_state_services.selected() {
+            Some(i) => if i == 0 { self.services.len() - 1 } else { i - 1 },
+            None => 0,
+        };
+        self.table_state_services.select(Some(i));
+    }
+
+    pub fn selected_service(&self) -> Option<&ServiceInfo> {
+        self.services.get(self.table_state_services.selected()?)
+    }
+
+    pub fn open_service_update_dialog(&mut self) {
+        let Some(service) = self.selected_service().cloned() else { return };
+        let mut image = TextInput::with_validator(validate_image_ref);
+        image.set_value(service.image_ref());
+        let mut form = Form::new(format!("Update {}", service.name))
+            .label("Tasks are recreated even if the image is unchanged");
+        // Every task's node pulls the image again to resolve the tag
+        if crate::ratelimit::is_docker_hub(service.image_ref()) {
+            if let Some(warning) = self.hub_pull_warning(service.desired_tasks) {
+                form = form.label(warning);
+            }
+        }
+        let form = form.text("image", "Image", image);
+        self.push_modal(Modal::UpdateService { service_id: service.id, service_name: service.name, form });
+    }
+
+    /// Force-updates (`image` set) or rolls back (`image` None) a service in the
+    /// background, streaming task state changes into the Output pane.
+    pub fn start_service_update(&mut self, service_id: String, service_name: String, image: Option<String>) {
+        let docker = self.docker.clone();
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+
+        output.write().unwrap().clear();
+        let action = if image.is_some() { "Updating" } else { "Rolling back" };
+        App::log_output(&output, format!("{} {}", action, service_name));
+        self.show_toast(format!("{} {}", action, service_name), false);
+
+        tokio::spawn(async move {
+            let result = match &image {
+                Some(image) => update_service_image(&docker, &service_id, Some(image)).await,
+                None => rollback_service(&docker, &service_id).await,
+            };
+            let result = match result {
+                Ok(()) => {
+                    let log_lock = output.clone();
+                    watch_service_update(&docker, &service_id, Duration::from_secs(300), move |line| App::log_output(&log_lock, line)).await
+                }
+                Err(e) => Err(e),
+            };
+            let toast_msg = match result {
+                Ok(()) => Toast::new(format!("{} finished", service_name), false),
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("{} {} failed: {}", action, service_name, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+        });
+    }
+
+    pub async fn refresh_stacks(&mut self) {
+        if self.docker.podman {
+            self.stacks.clear();
+            self.table_state_stacks.select(None);
+            self.stacks_error = Some(PODMAN_NO_SWARM.to_string());
+            return;
+        }
+        match list_stacks(&self.docker).await {
+            Ok(stacks) => {
+                self.stacks = stacks;
+                self.stacks_error = None;
+                if self.stacks.is_empty() {
+                    self.table_state_stacks.select(None);
+                } else {
+                    let i = self.table_state_stacks.selected().unwrap_or(0).min(self.stacks.len() - 1);
+                    self.table_state_stacks.select(Some(i));
+                }
+            }
+            Err(e) => {
+                self.stacks.clear();
+                self.table_state_stacks.select(None);
+                self.stacks_error = Some(e.to_string());
+            }
+        }
+    }
+
+    pub fn next_stack(&mut self) {
+        if self.stacks.is_empty() { return; }
+        let i = match self.table_state_stacks.selected() {
+            Some(i) => if i >= self.stacks.len() - 1 { 0 } else { i + 1 },
+            None => 0,
+        };
+        self.table_state_stacks.select(Some(i));
+    }
+
+    pub fn previous_stack(&mut self) {
+        if self.stacks.is_empty() { return; }
+        let i = match self.table_state_stacks.selected() {
+            Some(i) => if i == 0 { self.stacks.len() - 1 } else { i - 1 },
+            None => 0,
+        };
+        self.table_state_stacks.select(Some(i));
+    }
+
+    pub fn selected_stack(&self) -> Option<&StackInfo> {
+        self.stacks.get(self.table_state_stacks.selected()?)
+    }
+
+    /// Removes a stack in the background, logging each removed object to the Output pane.
+    pub fn start_remove_stack(&mut self, name: String) {
+        let docker = self.docker.clone();
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+
+        output.write().unwrap().clear();
+        self.show_toast(format!("Removing stack {}", name), false);
+
+        tokio::spawn(async move {
+            let log_lock = output.clone();
+            let result = remove_stack(&docker, &name, move |line| App::log_output(&log_lock, line)).await;
+            let toast_msg = match result {
+                Ok(()) => Toast::new(format!("Removed stack {}", name), false),
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("Removing stack {} failed: {}", name, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+        });
+    }
+
+    /// Re-analyzes disk usage and the cleanup checklist in the background.
+    pub fn refresh_system(&self) {
+        App::spawn_system_analysis(self.docker.clone(), self.system_report.clone(), self.system_error.clone());
+    }
+
+    fn spawn_system_analysis(docker: DockerClient, report: Arc<RwLock<Option<SystemReport>>>, error: Arc<RwLock<Option<String>>>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            match analyze_host(&docker).await {
+                Ok(result) => {
+                    *report.write().unwrap() = Some(result);
+                    *error.write().unwrap() = None;
+                }
+                Err(e) => *error.write().unwrap() = Some(e.to_string()),
+            }
+        })
+    }
+
+    pub fn next_cleanup_item(&mut self) {
+        let len = self.system_report.read().unwrap().as_ref().map_or(0, |r| r.items.len());
+        if len == 0 { return; }
+        let i = self.cleanup_state.selected().map_or(0, |i| (i + 1) % len);
+        self.cleanup_state.select(Some(i));
+    }
+
+    pub fn previous_cleanup_item(&mut self) {
+        let len = self.system_report.read().unwrap().as_ref().map_or(0, |r| r.items.len());
+        if len == 0 { return; }
+        let i = self.cleanup_state.selected().map_or(0, |i| if i == 0 { len - 1 } else { i - 1 });
+        self.cleanup_state.select(Some(i));
+    }
+
+    /// Queues the selected cleanup checklist item.
+    pub fn run_selected_cleanup(&mut self) {
+        let item = {
+            let report = self.system_report.read().unwrap();
+            report.as_ref().and_then(|r| r.items.get(self.cleanup_state.selected()?).cloned())
+        };
+        let Some(item) = item else { return };
+        if item.count == 0 {
+            self.show_toast(format!("{}: nothing to clean", item.kind.label()), false);
+            return;
+        }
+        // Named volumes go too, and may hold a database, so they are listed first
+        if item.kind == CleanupKind::UnusedVolumes {
+            self.push_modal(Modal::CleanupVolumesConfirm(item.detail.split(", ").map(String::from).collect()));
+            return;
+        }
+        self.defer(format!("Clean up {}", item.kind.label().to_lowercase()), DeferredAction::Cleanup(item.kind));
+    }
+
+    /// Executes a cleanup checklist item, then refreshes the report.
+    fn start_cleanup(&self, kind: CleanupKind) {
+        let docker = self.docker.clone();
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let report = self.system_report.clone();
+        let error = self.system_error.clone();
+        App::log_output(&output, format!("Cleanup: {} ({})", kind.label(), kind.action()));
+
+        tokio::spawn(async move {
+            let toast_msg = match run_cleanup(&docker, kind).await {
+                Ok(result) => {
+                    let message = format!("{}: {} removed, {} reclaimed", kind.label(), result.deleted.len(), format_bytes(result.reclaimed));
+                    App::log_output(&output, message.clone());
+                    Toast::new(message, false)
+                }
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("{} cleanup failed: {}", kind.label(), e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+            App::spawn_system_analysis(docker, report, error);
+        });
+    }
+
+    pub fn open_build_cache_prune_dialog(&mut self) {
+        if self.docker.podman {
+            self.show_toast("Podman has no BuildKit build cache to prune".to_string(), true);
+            return;
+        }
+        if let Err(e) = self.docker.require(ApiFeature::BuildCache) {
+            self.show_toast(e.to_string(), true);
+            return;
+        }
+        let form = Form::new("Prune Build Cache")
+            .label("Leave a field blank to ignore that threshold")
+            .text("keep", "Keep most recent (MB)", TextInput::with_validator(validate_optional_number))
+            .text("age", "Only unused for (hours)", TextInput::with_validator(validate_optional_number));
+        self.push_modal(Modal::PruneBuildCache { form });
+    }
+
+    /// Template names as offered in the picker: `volume: <name>` and `network: <name>`.
+    fn template_choices(&self) -> Vec<String> {
+        let config = self.config.read().unwrap();
+        config.volume_templates.iter().map(|t| format!("volume: {}", t.name))
+            .chain(config.network_templates.iter().map(|t| format!("network: {}", t.name)))
+            .collect()
+    }
+
+    pub fn open_template_dialog(&mut self) {
+        let choices = self.template_choices();
+        if choices.is_empty() {
+            self.show_toast("No volume_templates or network_templates in config.toml".to_string(), true);
+            return;
+        }
+        let form = Form::new("Create from Template")
+            .label("Templates are defined in config.toml")
+            .select("template", "Template", choices, 0);
+        self.push_modal(Modal::CreateFromTemplate { form });
+    }
+
+    /// Creates the volume or network picked as `choice` (one of `template_choices`).
+    pub fn start_create_from_template(&self, choice: String) {
+        let (volume, network) = {
+            let config = self.config.read().unwrap();
+            match choice.split_once(": ") {
+                Some(("volume", name)) => (config.volume_templates.iter().find(|t| t.name == name).cloned(), None),
+                Some(("network", name)) => (None, config.network_templates.iter().find(|t| t.name == name).cloned()),
+                _ => (None, None),
+            }
+        };
+        let docker = self.docker.clone();
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let report = self.system_report.clone();
+        let error = self.system_error.clone();
+        App::log_output(&output, format!("Creating {}", choice));
+
+        tokio::spawn(async move {
+            let result = match (volume, network) {
+                (Some(template), _) => create_volume(&docker, &template).await.map(|name| format!("Volume {} is ready", name)),
+                (_, Some(template)) => create_network(&docker, &template).await
+                    .map(|id| format!("Created network {} ({})", template.name, id.chars().take(12).collect::<String>())),
+                _ => return,
+            };
+            let toast_msg = match result {
+                Ok(message) => {
+                    App::log_output(&output, message.clone());
+                    Toast::new(message, false)
+                }
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("Creating {} failed: {}", choice, e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+            App::spawn_system_analysis(docker, report, error);
+        });
+    }
+
+    /// Prunes build cache in the background, keeping `keep_mb` of recent cache
+    /// and only touching records unused for `older_than_hours`.
+    pub fn start_build_cache_prune(&self, keep_mb: Option<u64>, older_than_hours: Option<u64>) {
+        let docker = self.docker.clone();
+        let output = self.output_log.clone();
+        let toast = self.toast.clone();
+        let report = self.system_report.clone();
+        let error = self.system_error.clone();
+        App::log_output(&output, format!(
+            "Pruning build cache (keep: {}, older than: {})",
+            keep_mb.map_or("none".to_string(), |mb| format!("{} MB", mb)),
+            older_than_hours.map_or("any age".to_string(), |h| format!("{}h", h)),
+        ));
+
+        tokio::spawn(async move {
+            let toast_msg = match prune_build_cache(&docker, keep_mb.map(|mb| mb.saturating_mul(1024 * 1024)), older_than_hours).await {
+                Ok(result) => {
+                    let message = format!("Build cache: {} records removed, {} reclaimed", result.deleted.len(), format_bytes(result.reclaimed));
+                    App::log_output(&output, message.clone());
+                    Toast::new(message, false)
+                }
+                Err(e) => {
+                    App::log_output(&output, format!("Error: {}", e));
+                    Toast::new(format!("Build cache prune failed: {}", e), true)
+                }
+            };
+            *toast.write().unwrap() = Some(toast_msg);
+            App::spawn_system_analysis(docker, report, error);
+        });
+    }
+
+    /// Opens the prune wizard with per-class estimates from the last system report.
+    pub fn open_prune_wizard(&mut self) {
+        let (estimates, volumes): (Vec<(CleanupKind, u64)>, Vec<String>) = {
+            let report = self.system_report.read().unwrap();
+            let estimates = CleanupKind::PRUNE_CLASSES.iter()
+                .filter(|kind| **kind != CleanupKind::BuildCache || (!self.docker.podman && self.docker.supports(ApiFeature::BuildCache)))
+                .map(|kind| {
+                    let reclaimable = report.as_ref()
+                        .and_then(|r| r.items.iter().find(|i| i.kind == *kind))
+                        .map_or(0, |i| i.reclaimable);
+                    (*kind, reclaimable)
+                })
+                .collect();
+            // Named volumes go too, so the confirm step lists them
+            let volumes = report.as_ref()
+                .and_then(|r| r.items.iter().find(|i| i.kind == CleanupKind::UnusedVolumes && i.count > 0))
+                .map(|i| i.detail.split(", ").map(String::from).collect())
+                .unwrap_or_default();
+            (estimates, volumes)
+        };
+
+        let mut form = Form::new("System Prune").label("Select what to prune (Space toggles)");
+        for (kind, reclaimable) in &estimates {
+            let label = match kind {
+                CleanupKind::UnusedNetworks => kind.label().to_string(),
+                _ => format!("{} (~{})", kind.label(), format_bytes(*reclaimable)),
+            };
+            // Volumes may hold data and build cache is costly to rebuild, so both are opt-in
+            let checked = !matches!(kind, CleanupKind::UnusedVolumes | CleanupKind::BuildCache);
+            form = form.checkbox(kind.key(), label, checked);
+        }
+        self.push_modal(Modal::PruneWizard { step: PruneStep::Select(form), estimates, volumes });
+    }
+
+    /// Prunes `kinds` in the background behind the wizard's summary step, then
+    /// reloads the system report and the container and image lists.
+    fn start_prune(&mut self, kinds: Vec<CleanupKind>) {
+        let summary = Arc::new(RwLock::new(None));
+        self.push_modal(Modal::PruneWizard { step: PruneStep::Summary(summary.clone()), estimates: Vec::new(), volumes: Vec::new() });
+
+        let docker = self.docker.clone();
+        let output = self.output_log.clone();
+        let report = self.system_report.clone();
+        let error = self.system_error.clone();
+        let containers = self.containers.clone();
+        let show_all = self.show_all.clone();
+        let images = self.images.clone();
+        let show_dangling = self.show_dangling.clone();
+        tokio::spawn(async move {
+            *summary.write().unwrap() = Some(App::run_prune(&docker, &output, &kinds).await);
+            App::spawn_system_analysis(docker.clone(), report, error);
+            if let Ok(list) = list_containers(&docker, show_all.load(Ordering::Relaxed)).await {
+                *containers.write().unwrap() = list;
+            }
+            if let Ok(list) = list_images(&docker, show_dangling.load(Ordering::Relaxed)).await {
+                *images.write().unwrap() = list;
+            }
+        });
+    }
+
+    async fn run_prune(docker: &DockerClient, output: &Arc<RwLock<Vec<String>>>, kinds: &[CleanupKind]) -> Vec<String> {
+        let mut summary = Vec::new();
+        let mut total = 0;
+        for kind in kinds {
+            match run_cleanup(docker, *kind).await {
+                Ok(result) => {
+                    total += result.reclaimed;
+                    summary.push(format!("{}: {} removed, {} reclaimed", kind.label(), result.deleted.len(), format_bytes(result.reclaimed)));
+                    for name in result.deleted.iter().take(5) {
+                        summary.push(format!("  - {}", name));
+                    }
+                    if result.deleted.len() > 5 {
+                        summary.push(format!("  ... and {} more", result.deleted.len() - 5));
+                    }
+                }
+                Err(e) => summary.push(format!("{}: failed: {}", kind.label(), e)),
+            }
+        }
+        summary.push(String::new());
+        summary.push(format!("Total reclaimed: {}", format_bytes(total)));
+        for line in &summary {
+            App::log_output(output, line.clone());
+        }
+        summary
+    }
+
+    pub async fn refresh_nodes(&mut self) {
+        if self.docker.podman {
+            self.nodes.clear();
+            self.table_state_nodes.select(None);
+            self.nodes_error = Some(PODMAN_NO_SWARM.to_string());
+            return;
+        }
+        match list_nodes(&self.docker).await {
+            Ok(nodes) => {
+                self.nodes = nodes;
+                self.nodes_error = None;
+                if self.nodes.is_empty() {
+                    self.table_state_nodes.select(None);
+                } else {
+                    let i = self.table_state_nodes.selected().unwrap_or(0).min(self.nodes.len() - 1);
+                    self.table_state_nodes.select(Some(i));
+                }
+            }
+            Err(e) => {
+                self.nodes.clear();
+                self.table_state_nodes.select(None);
+                self.nodes_error = Some(e.to_string());
+            }
+        }
+    }
+
+    pub fn next_node(&mut self) {
+        if self.nodes.is_empty() { return; }
+        let i = match self.table_state_nodes.selected() {
+            Some(i) => if i >= self.nodes.len() - 1 { 0 } else { i + 1 },
+            None => 0,
+        };
+        self.table_state_nodes.select(Some(i));
+    }
+
+    pub fn previous_node(&mut self) {
+        if self.nodes.is_empty() { return; }
+        let i = match self.table_state_nodes.selected() {
+            Some(i) => if i == 0 { self.nodes.len() - 1 } else { i - 1 },
+            None => 0,
+        };
+        self.table_state_nodes.select(Some(i));
+    }
+
+    pub fn selected_node(&self) -> Option<&NodeInfo> {
+        self.nodes.get(self.table_state_nodes.selected()?)
+    }
+
+    /// Drains or re-activates the selected node and reports the result as a toast.
+    pub async fn set_selected_node_availability(&mut self, availability: NodeAvailability) {
+        let Some(node) = self.selected_node().cloned() else { return };
+        self.set_node_availability(node, availability).await;
+    }
+
+    /// Asks before draining the selected node, since swarm moves every task
+    /// off it straight away.
+    pub fn confirm_drain_selected_node(&mut self) {
+        let Some(node) = self.selected_node().cloned() else { return };
+        if node.availability != NodeAvailability::Drain {
+            self.push_modal(Modal::DrainNodeConfirm(node));
+        }
+    }
+
+    pub async fn set_node_availability(&mut self, node: NodeInfo, availability: NodeAvailability) {
+        if node.availability == availability {
+            return;
+        }
+        match set_node_availability(&self.docker, &node.id, availability).await {
+            Ok(()) => self.show_toast(format!("Node {} set to {}", node.hostname, availability.label()), false),
+            Err(e) => self.show_toast(format!("Failed to update {}: {}", node.hostname, e), true),
+        }
+        self.refresh_nodes().await;
+    }
+
+    pub fn toggle_filter(&mut self) {
+        let current = self.show_all.load(Ordering::Relaxed);
+        self.show_all.store(!current, Ordering::Relaxed);
+    }
+
+    pub fn apply_turbo_preset(&mut self) {
+        let mut config = self.config.write().unwrap();
+        if config.turbo_mode {
+            config.refresh_rate = RefreshRate::Interval(Duration::from_secs(2));
+            config.stats_view = StatsView::Minimal;
+            config.poll_strategy = PollStrategy::VisibleOnly;
+        } else {
+            config.refresh_rate = RefreshRate::Interval(Duration::from_secs(1));
             config.stats_view = StatsView::Detailed;
             config.poll_strategy = PollStrategy::AllContainers;
         }
     }
 
+    /// Uses `rate` for this session without saving it to the config.
+    pub fn set_refresh_flag(&mut self, rate: RefreshRate) {
+        let mut config = self.config.write().unwrap();
+        let configured = std::mem::replace(&mut config.refresh_rate, rate.clone());
+        self.refresh_flag = Some((rate, configured));
+    }
+
     pub fn save_config(&self) {
-        let config = self.config.read().unwrap();
+        let mut config = self.config.read().unwrap().clone();
+        if let Some((flag, configured)) = &self.refresh_flag {
+            if config.refresh_rate == *flag {
+                config.refresh_rate = configured.clone();
+            }
+        }
         let _ = save_config(&config);
     }
 }
 
-pub fn format_details(info: ContainerInspectResponse) -> String {
+/// Formats container inspect output for the details pane. `image` is used to
+/// mark entrypoint/cmd overrides; with `shared_host` (other containers running
+/// alongside), unlimited PIDs/memory are flagged.
+pub fn format_details(info: ContainerInspectResponse, image: Option<&bollard::models::ImageInspect>, shared_host: bool) -> String {
     let mut s = String::new();
     s.push_str(&format!("ID: {}\n", info.id.as_deref().unwrap_or("Unknown")));
     s.push_str(&format!("Name: {}\n", info.name.as_deref().unwrap_or("Unknown")));
     s.push_str(&format!("Image: {}\n", info.image.as_deref().unwrap_or("Unknown")));
     s.push_str(&format!("Status: {}\n", info.state.as_ref().map(|st| format!("{:?}", st.status)).unwrap_or_else(|| "Unknown".to_string())));
+    // Containers inherit image labels, so these are normally the image's own
+    let labels = info.config.as_ref().and_then(|c| c.labels.as_ref())
+        .or_else(|| image.and_then(|i| i.config.as_ref()).and_then(|c| c.labels.as_ref()));
+    if let Some(labels) = labels {
+        s.push_str(&format_provenance(&Provenance::from_labels(labels)));
+    }
     
+    if let Some(config) = info.config.as_ref() {
+        let image_config = image.and_then(|i| i.config.as_ref());
+        let show = |v: Option<&Vec<String>>| match v {
+            Some(args) if !args.is_empty() => format!("{:?}", args),
+            _ => "(none)".to_string(),
+        };
+        s.push_str("\nCommand:\n");
+        for (label, actual, default) in [
+            ("Entrypoint", config.entrypoint.as_ref(), image_config.and_then(|c| c.entrypoint.as_ref())),
+            ("Cmd", config.cmd.as_ref(), image_config.and_then(|c| c.cmd.as_ref())),
+        ] {
+            match image_config {
+                Some(_) if actual != default => s.push_str(&format!(
+                    "  ⚠ {}: {} (overrides image default {})\n", label, show(actual), show(default)
+                )),
+                Some(_) => s.push_str(&format!("  {}: {} (image default)\n", label, show(actual))),
+                None => s.push_str(&format!("  {}: {}\n", label, show(actual))),
+            }
+        }
+        // A custom entrypoint makes Docker drop the image CMD unless one is given
+        if image_config.is_some_and(|c| config.entrypoint.as_ref() != c.entrypoint.as_ref()) && config.cmd.as_ref().is_none_or(|c| c.is_empty()) {
+            s.push_str("  Image CMD is ignored because the entrypoint was overridden\n");
+        }
+    }
+
     if let Some(config) = info.config {
         if let Some(env) = config.env {
             s.push_str("\nEnvironment:\n");
@@ -897,6 +5055,68 @@ pub fn format_details(info: ContainerInspectResponse) -> String {
         }
     }
     
+    if let Some(host) = info.host_config.as_ref() {
+        s.push_str("\nResources:\n");
+        let warn = |unlimited: bool| if unlimited && shared_host { "  ⚠ " } else { "  " };
+        let memory = host.memory.unwrap_or(0);
+        s.push_str(&format!("{}Memory: {}\n", warn(memory <= 0), if memory > 0 { format_bytes(memory as u64) } else { "unlimited".to_string() }));
+        match host.nano_cpus.filter(|n| *n > 0) {
+            Some(n) => s.push_str(&format!("  CPUs: {}\n", n as f64 / 1e9)),
+            None => s.push_str("  CPUs: unlimited\n"),
+        }
+        // 0 and -1 both mean no limit
+        let pids = host.pids_limit.unwrap_or(0);
+        s.push_str(&format!("{}PIDs limit: {}\n", warn(pids <= 0), if pids > 0 { pids.to_string() } else { "unlimited".to_string() }));
+        s.push_str(&format!("  Init: {}\n", if host.init == Some(true) { "yes (docker-init as PID 1)" } else { "no" }));
+        for u in host.ulimits.iter().flatten() {
+            let limit = |v: Option<i64>| match v {
+                Some(-1) | None => "unlimited".to_string(),
+                Some(n) => n.to_string(),
+            };
+            s.push_str(&format!("  ulimit {}: soft {} / hard {}\n", u.name.as_deref().unwrap_or("?"), limit(u.soft), limit(u.hard)));
+        }
+    }
+
+    if let Some(host) = info.host_config {
+        let cap_add = host.cap_add.unwrap_or_default();
+        let cap_drop = host.cap_drop.unwrap_or_default();
+        if host.privileged == Some(true) || !cap_add.is_empty() || !cap_drop.is_empty() {
+            s.push_str("\nCapabilities:\n");
+            if host.privileged == Some(true) { s.push_str("  privileged (all capabilities and devices)\n"); }
+            for c in cap_add { s.push_str(&format!("  + {}\n", c)); }
+            for c in cap_drop { s.push_str(&format!("  - {}\n", c)); }
+        }
+
+        let devices = host.devices.unwrap_or_default();
+        if !devices.is_empty() {
+            s.push_str("\nDevices:\n");
+            for d in devices {
+                s.push_str(&format!("  {} -> {} ({})\n",
+                    d.path_on_host.as_deref().unwrap_or("?"),
+                    d.path_in_container.as_deref().unwrap_or("?"),
+                    d.cgroup_permissions.as_deref().unwrap_or("rwm")));
+            }
+        }
+
+        let requests = host.device_requests.unwrap_or_default();
+        if !requests.is_empty() {
+            s.push_str("\nDevice Requests:\n");
+            for r in requests {
+                let devices = match (r.count, r.device_ids.filter(|ids| !ids.is_empty())) {
+                    (_, Some(ids)) => ids.join(","),
+                    (Some(-1), None) => "all".to_string(),
+                    (Some(n), None) => n.to_string(),
+                    (None, None) => "?".to_string(),
+                };
+                let caps = r.capabilities.unwrap_or_default().into_iter()
+                    .map(|set| set.join("+"))
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                s.push_str(&format!("  {} devices={} [{}]\n", r.driver.as_deref().filter(|d| !d.is_empty()).unwrap_or("default"), devices, caps));
+            }
+        }
+    }
+
     if let Some(mounts) = info.mounts {
         s.push_str("\nMounts:\n");
         for m in mounts {
@@ -907,6 +5127,62 @@ pub fn format_details(info: ContainerInspectResponse) -> String {
     s
 }
 
+/// Exits listed in the details pane, newest first.
+pub const EXIT_HISTORY_LEN: usize = 5;
+
+/// Formats `(timestamp, exit code)` pairs from events, plus the last exit known
+/// from inspect in case it predates the event backlog.
+fn format_exit_history(mut exits: Vec<(i64, i64)>, last_exit: Option<(i64, i64)>) -> String {
+    if let Some((time, code)) = last_exit {
+        // Inspect and event timestamps can differ by a second
+        if !exits.iter().any(|(t, _)| (t - time).abs() <= 1) {
+            exits.push((time, code));
+        }
+    }
+    if exits.is_empty() {
+        return String::new();
+    }
+    exits.sort_by_key(|(t, _)| std::cmp::Reverse(*t));
+
+    let hour_ago = Utc::now().timestamp() - 3600;
+    let recent = exits.iter().filter(|(t, _)| *t >= hour_ago).count();
+    let mut s = String::from("\nExit History:\n");
+    if recent >= 3 {
+        s.push_str(&format!("  ⚠ {} exits in the last hour (flapping)\n", recent));
+    }
+    for (time, code) in exits.iter().take(EXIT_HISTORY_LEN) {
+        let when = chrono::DateTime::from_timestamp(*time, 0)
+            .map(|t| t.with_timezone(&chrono::Local).format("%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        s.push_str(&format!("  {}  exit {}\n", when, code));
+    }
+    if exits.len() > EXIT_HISTORY_LEN {
+        s.push_str(&format!("  ... {} earlier\n", exits.len() - EXIT_HISTORY_LEN));
+    }
+    s
+}
+
+/// Skew beyond this many seconds is flagged; TLS and token validation tend to break soon after.
+pub const CLOCK_SKEW_WARN_SECS: i64 = 5;
+
+/// Heading of the clock section, which the clock check replaces on a re-run.
+const CLOCK_SECTION: &str = "\nClock:\n";
+
+fn format_clock_check(check: Result<ClockCheck>) -> String {
+    match check {
+        Ok(check) => {
+            let skew = match check.skew_secs {
+                0 => "in sync with host".to_string(),
+                s if s > 0 => format!("{}s ahead of host", s),
+                s => format!("{}s behind host", -s),
+            };
+            let warning = if check.skew_secs.abs() > CLOCK_SKEW_WARN_SECS { "⚠ " } else { "" };
+            format!("{}  {}{}\n  UTC offset: {}\n", CLOCK_SECTION, warning, skew, check.utc_offset)
+        }
+        Err(e) => format!("{}  unavailable ({})\n", CLOCK_SECTION, e),
+    }
+}
+
 pub fn format_image_details(info: bollard::models::ImageInspect) -> String {
     let mut s = String::new();
     s.push_str(&format!("ID: {}\n", info.id.as_deref().unwrap_or("Unknown")));
@@ -915,9 +5191,158 @@ pub fn format_image_details(info: bollard::models::ImageInspect) -> String {
         for t in tags { s.push_str(&format!("  {}\n", t)); }
     }
     s.push_str(&format!("Size: {}\n", format_bytes(info.size.unwrap_or(0) as u64)));
+    if let Some(labels) = info.config.and_then(|c| c.labels) {
+        s.push_str(&format_provenance(&Provenance::from_labels(&labels)));
+    }
+    s
+}
+
+const PULL_SIZE_PROMPT: &str = "Pull size: Enter to look it up in the registry\n";
+
+/// The compressed size a pull of the image transfers, next to its size on
+/// disk, and how long it takes at the pull cap when one is set. Looked up by
+/// repo digest, so it matches the local image; images that were never pulled
+/// fall back to their tag.
+async fn format_pull_size(info: &bollard::models::ImageInspect, max_kib_per_sec: Option<u64>, proxy: &ProxyConfig) -> String {
+    let reference = info.repo_digests.iter().flatten().next()
+        .or_else(|| info.repo_tags.iter().flatten().find(|t| *t != "<none>:<none>"));
+    let Some(reference) = reference else {
+        return "Pull size: unknown (untagged image)\n".to_string();
+    };
+    let os = info.os.as_deref().unwrap_or("linux");
+    let arch = match (info.architecture.as_deref().unwrap_or("amd64"), info.variant.as_deref()) {
+        (arch, Some(variant)) => format!("{}/{}", arch, variant),
+        (arch, None) => arch.to_string(),
+    };
+    match crate::registry::pull_size(reference, os, &arch, proxy).await {
+        Ok(bytes) => {
+            let on_disk = info.size.unwrap_or(0) as u64;
+            let mut s = format!("Pull size: {} compressed, {} on disk\n", format_bytes(bytes), format_bytes(on_disk));
+            if let Some(rate) = max_kib_per_sec {
+                let secs = bytes / (rate.max(1) * 1024);
+                let time = if secs < 60 { format!("{}s", secs) } else { format!("{}m", secs.div_ceil(60)) };
+                s.push_str(&format!("  ~{} at the {} KiB/s pull cap\n", time, rate));
+            }
+            s
+        }
+        Err(e) => format!("Pull size: unavailable ({:#})\n", e),
+    }
+}
+
+/// The build's revision, source and date, with the commit URL `O` copies.
+fn format_provenance(provenance: &Provenance) -> String {
+    if provenance.is_empty() {
+        return String::new();
+    }
+    let mut s = String::from("\nProvenance:\n");
+    for (label, value) in [("Revision", &provenance.revision), ("Source", &provenance.source), ("Built", &provenance.created)] {
+        if let Some(value) = value {
+            s.push_str(&format!("  {}: {}\n", label, value));
+        }
+    }
+    if let Some(url) = provenance.commit_url() {
+        s.push_str(&format!("  Commit: {}\n", url));
+    }
     s
 }
 
+/// "updated 3s ago" style freshness label for a unix timestamp, 0 meaning never.
+pub fn format_updated(timestamp: i64) -> String {
+    if timestamp == 0 {
+        return "not updated yet".to_string();
+    }
+    let secs = (Utc::now().timestamp() - timestamp).max(0);
+    if secs < 60 { format!("updated {}s ago", secs) }
+    else if secs < 3_600 { format!("updated {}m ago", secs / 60) }
+    else { format!("updated {}h ago", secs / 3_600) }
+}
+
+/// Warning for host ports in `specs` that are taken: published by a running
+/// container, listed twice, or (when the daemon is `local`) bound by any other
+/// process. Empty when there's nothing to warn about or `specs` doesn't parse.
+pub fn port_warning(specs: &str, published: &[PublishedPort], local: bool) -> String {
+    let mut seen = HashSet::new();
+    let mut conflicts = Vec::new();
+    for spec in specs.split_whitespace() {
+        let Ok(ports) = parse_port_specs(spec) else { continue };
+        for (container_port, bindings) in &ports {
+            let protocol = container_port.split_once('/').map_or("tcp", |(_, p)| p);
+            for binding in bindings.iter().flatten() {
+                let Some(port) = binding.host_port.as_deref().and_then(|p| p.parse::<u16>().ok()).filter(|p| *p != 0) else { continue };
+                let label = format!("{}/{}", port, protocol);
+                if !seen.insert(label.clone()) {
+                    conflicts.push(format!("{} is listed twice", label));
+                } else if let Some(owner) = published.iter().find(|p| p.port == port && p.protocol == protocol) {
+                    conflicts.push(format!("{} is used by {}", label, owner.container));
+                } else if local && host_port_bound(binding.host_ip.as_deref(), port, protocol) {
+                    conflicts.push(format!("{} is already bound on this machine", label));
+                }
+            }
+        }
+    }
+    if conflicts.is_empty() {
+        String::new()
+    } else {
+        format!("⚠ {}", conflicts.join("; "))
+    }
+}
+
+/// Tries binding the port; anything but "in use" (e.g. a privileged port) counts as free.
+fn host_port_bound(ip: Option<&str>, port: u16, protocol: &str) -> bool {
+    let ip = ip.filter(|ip| !ip.is_empty()).unwrap_or("0.0.0.0");
+    let in_use = |e: std::io::Error| e.kind() == std::io::ErrorKind::AddrInUse;
+    match protocol {
+        "tcp" => std::net::TcpListener::bind((ip, port)).err().is_some_and(in_use),
+        "udp" => std::net::UdpSocket::bind((ip, port)).err().is_some_and(in_use),
+        _ => false,
+    }
+}
+
+/// A time typed in the logs pane's jump prompt: `HH:MM`, `HH:MM:SS`, or
+/// either after a `YYYY-MM-DD` date, in local time. A bare time means its
+/// latest occurrence that isn't in the future.
+pub fn parse_log_time(value: &str) -> std::result::Result<chrono::DateTime<chrono::Local>, String> {
+    use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+    let value = value.trim();
+    let invalid = || format!("Invalid time '{}'; use HH:MM, HH:MM:SS or YYYY-MM-DD HH:MM", value);
+    let time_of = |text: &str| {
+        NaiveTime::parse_from_str(text, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+            .map_err(|_| invalid())
+    };
+    let now = Local::now();
+    let naive = match value.split_once([' ', 'T']) {
+        Some((date, time)) => {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| invalid())?;
+            NaiveDateTime::new(date, time_of(time)?)
+        }
+        None => {
+            let today = NaiveDateTime::new(now.date_naive(), time_of(value)?);
+            if today > now.naive_local() { today - chrono::Duration::days(1) } else { today }
+        }
+    };
+    Local.from_local_datetime(&naive).earliest().ok_or_else(invalid)
+}
+
+/// `name` inside the container directory `dir`.
+pub fn join_container_path(dir: &str, name: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), name)
+}
+
+/// `[######----] 60% 1.2 GB of ~2.0 GB`, or just the bytes without an estimate.
+fn export_gauge(written: u64, estimate: Option<u64>) -> String {
+    const WIDTH: u64 = 20;
+    match estimate.filter(|e| *e > 0) {
+        Some(total) => {
+            // The estimate is approximate, so the gauge stops short until the end
+            let percent = (written * 100 / total).min(99);
+            let filled = (percent * WIDTH / 100) as usize;
+            format!("[{}{}] {}% {} of ~{}", "#".repeat(filled), "-".repeat(WIDTH as usize - filled), percent, format_bytes(written), format_bytes(total))
+        }
+        None => format!("{} written", format_bytes(written)),
+    }
+}
+
 pub fn format_bytes(bytes: u64) -> String {
     if bytes < 1024 { format!("{} B", bytes) }
     else if bytes < 1024 * 1024 { format!("{:.1} KB", bytes as f64 / 1024.0) }
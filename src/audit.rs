@@ -0,0 +1,161 @@
+//! Exec audit trail: a line in the audit log when a session starts and ends,
+//! and optionally a transcript of the session with secrets redacted.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use regex::Regex;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::docker::export::shell_quote;
+use crate::types::ExecAuditConfig;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Longest run of output without a newline that a transcript holds back;
+/// beyond it the text is redacted and written as if the line had ended.
+const MAX_PENDING_LINE: usize = 64 * 1024;
+
+/// An exec session that has been logged as started.
+pub struct ExecSession {
+    log: PathBuf,
+    /// `user=.. host=.. container=..`, repeated on the end line so each line stands alone.
+    subject: String,
+    transcript: Option<Transcript>,
+}
+
+impl ExecSession {
+    /// Writes the start line. Fails, so the exec is refused, when the session
+    /// can't be audited: an unwritable log or transcript, or a redaction
+    /// pattern that doesn't compile.
+    pub fn start(config: &ExecAuditConfig, host: &str, container: &str, command: &str) -> Result<Self> {
+        let redact = config.redact.iter()
+            .map(|pattern| Regex::new(pattern).with_context(|| format!("Invalid redaction pattern '{}'", pattern)))
+            .collect::<Result<Vec<_>>>()?;
+        let log = match &config.log {
+            Some(path) => path.clone(),
+            None => default_log_path()?,
+        };
+        let started = Utc::now();
+        let transcript = match &config.transcript_dir {
+            Some(dir) => {
+                fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+                Some(Transcript::create(dir.join(transcript_name(container, started)), redact)?)
+            }
+            None => None,
+        };
+
+        let subject = format!("user={} host={} container={}", shell_quote(&current_user()), host, shell_quote(container));
+        let mut line = format!("exec-start {} command={}", subject, shell_quote(command));
+        if let Some(transcript) = &transcript {
+            line.push_str(&format!(" transcript={}", shell_quote(&transcript.path.display().to_string())));
+        }
+        let session = Self { log, subject, transcript };
+        session.append(started, &line)?;
+        Ok(session)
+    }
+
+    /// The session's transcript, for the exec to write its output to.
+    pub fn take_transcript(&mut self) -> Option<Transcript> {
+        self.transcript.take()
+    }
+
+    /// Writes the end line with the session's outcome.
+    pub fn finish(self, outcome: std::result::Result<(), String>) -> Result<()> {
+        let status = match outcome {
+            Ok(()) => "ok".to_string(),
+            Err(e) => shell_quote(&format!("error: {}", e)),
+        };
+        self.append(Utc::now(), &format!("exec-end {} status={}", self.subject, status))
+    }
+
+    fn append(&self, time: DateTime<Utc>, line: &str) -> Result<()> {
+        if let Some(dir) = self.log.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log)
+            .with_context(|| format!("Failed to open audit log {}", self.log.display()))?;
+        writeln!(file, "{} {}", time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true), line)
+            .with_context(|| format!("Failed to write audit log {}", self.log.display()))
+    }
+}
+
+fn default_log_path() -> Result<PathBuf> {
+    let dirs = ProjectDirs::from("com", "dockyard", "dockyard")
+        .context("Failed to determine project directories")?;
+    Ok(dirs.data_dir().join("audit.log"))
+}
+
+/// Login name of whoever runs dockyard.
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// `<container>-<UTC time>.log`, with anything unsafe in a file name replaced.
+fn transcript_name(container: &str, started: DateTime<Utc>) -> String {
+    let name: String = container.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+        .collect();
+    format!("{}-{}.log", name, started.format("%Y%m%dT%H%M%SZ"))
+}
+
+/// Output of an exec session, redacted a line at a time before it is written,
+/// so no secret reaches the disk even if dockyard dies mid-session.
+pub struct Transcript {
+    path: PathBuf,
+    file: fs::File,
+    redact: Vec<Regex>,
+    /// Output since the last newline, held back until the line is complete.
+    pending: Vec<u8>,
+}
+
+impl Transcript {
+    /// Creates the transcript readable by the owner only.
+    fn create(path: PathBuf, redact: Vec<Regex>) -> Result<Self> {
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let file = options.open(&path).with_context(|| format!("Failed to create transcript {}", path.display()))?;
+        Ok(Self { path, file, redact, pending: Vec::new() })
+    }
+
+    /// Writes the complete lines in `bytes` and what was held back before them.
+    pub fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        self.pending.extend_from_slice(bytes);
+        let end = match self.pending.iter().rposition(|b| *b == b'\n') {
+            Some(newline) => newline + 1,
+            None if self.pending.len() > MAX_PENDING_LINE => self.pending.len(),
+            None => return Ok(()),
+        };
+        let lines: Vec<u8> = self.pending.drain(..end).collect();
+        self.write_redacted(&lines)
+    }
+
+    /// Writes the last line, if the session ended without a newline.
+    pub fn finish(mut self) -> std::io::Result<()> {
+        let rest = std::mem::take(&mut self.pending);
+        if rest.is_empty() {
+            return Ok(());
+        }
+        self.write_redacted(&rest)
+    }
+
+    fn write_redacted(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        let mut text = String::from_utf8_lossy(bytes).into_owned();
+        for pattern in &self.redact {
+            text = pattern.replace_all(&text, REDACTED).into_owned();
+        }
+        self.file.write_all(text.as_bytes())
+    }
+}
@@ -0,0 +1,77 @@
+//! Moving a tuned setup between workstations: `dockyard backup` packs
+//! `config.toml` (settings, extra hosts, exec profiles, templates and
+//! workspaces) and the scan cache into one tar archive, and `dockyard restore`
+//! unpacks it on another machine. The exec audit log stays behind; it records
+//! what happened on the machine that wrote it.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::config::{get_config_path, parse_config, write_atomic};
+use crate::scan::cache_path;
+
+const CONFIG_ENTRY: &str = "config.toml";
+const SCANS_ENTRY: &str = "scans.json";
+
+/// Archive entries and where each lives on this machine.
+fn entries() -> Result<Vec<(&'static str, PathBuf)>> {
+    let mut entries = vec![(CONFIG_ENTRY, get_config_path()?)];
+    if let Some(path) = cache_path() {
+        entries.push((SCANS_ENTRY, path));
+    }
+    Ok(entries)
+}
+
+/// Writes the archive to `path` and returns the entries it holds; files that
+/// don't exist yet, like a scan cache before the first scan, are left out.
+pub fn backup(path: &Path) -> Result<Vec<&'static str>> {
+    if !get_config_path()?.exists() {
+        anyhow::bail!("There is no config.toml to back up yet; it is written the first time dockyard runs");
+    }
+    let file = fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut builder = tar::Builder::new(file);
+    let mut written = Vec::new();
+    for (name, source) in entries()? {
+        if source.exists() {
+            builder.append_path_with_name(&source, name)
+                .with_context(|| format!("Failed to add {}", source.display()))?;
+            written.push(name);
+        }
+    }
+    builder.into_inner()?.sync_all()?;
+    Ok(written)
+}
+
+/// Restores the entries of the archive at `path` and returns where each went.
+/// The configuration is checked before anything is written, and the one it
+/// replaces is kept as `config.toml.bak`.
+pub fn restore(path: &Path) -> Result<Vec<PathBuf>> {
+    let file = fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut archive = tar::Archive::new(file);
+    let mut contents = Vec::new();
+    for entry in archive.entries().context("Not a dockyard backup archive")? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        contents.push((name, bytes));
+    }
+
+    let config = contents.iter().find(|(name, _)| name == CONFIG_ENTRY)
+        .context("The archive has no config.toml")?;
+    let text = std::str::from_utf8(&config.1).context("config.toml is not valid UTF-8")?;
+    parse_config(text).context("The archived config.toml doesn't parse")?;
+
+    let mut restored = Vec::new();
+    for (name, target) in entries()? {
+        let Some((_, bytes)) = contents.iter().find(|(entry, _)| entry == name) else { continue };
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        write_atomic(&target, bytes)?;
+        restored.push(target);
+    }
+    Ok(restored)
+}
@@ -0,0 +1,127 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::app::View;
+use crate::types::RefreshRate;
+
+/// Terminal UI for managing Docker containers, images and swarm services.
+#[derive(Parser, Debug)]
+#[command(name = "dockyard", version)]
+pub struct Cli {
+    /// Daemon to connect to, e.g. tcp://host:2376 or ssh://user@host
+    /// (overrides --context, the `host` config key and the current context)
+    #[arg(short = 'H', long, global = true)]
+    pub host: Option<String>,
+
+    /// Docker context to connect to instead of the CLI's current one
+    #[arg(long, global = true)]
+    pub context: Option<String>,
+
+    /// View to open on startup
+    #[arg(long, value_enum)]
+    pub view: Option<StartView>,
+
+    /// Container list search, e.g. name=api (the same as `/` in the list)
+    #[arg(long, value_name = "name=TEXT", value_parser = parse_filter)]
+    pub filter: Option<String>,
+
+    /// Stats refresh rate, e.g. 5s, 1m or manual
+    #[arg(long, value_parser = parse_refresh)]
+    pub refresh: Option<RefreshRate>,
+
+    /// Workspace from config.toml to start in (context, filters, pins and layout)
+    #[arg(long, value_name = "NAME")]
+    pub workspace: Option<String>,
+
+    /// Disable every action that changes containers, images or the swarm
+    #[arg(long)]
+    pub read_only: bool,
+
+    /// Keep small buffers and skip optional caches, for Raspberry Pi-class hosts
+    #[arg(long)]
+    pub low_memory: bool,
+
+    /// Stats polling interval in seconds (1-10)
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..=10))]
+    pub stats_interval: u64,
+
+    /// Append a debug log to this file
+    #[arg(long, global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// Log level for --log-file: error, warn, info, debug or trace
+    #[arg(long, default_value = "info", global = true)]
+    pub log_level: String,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Write a `docker run` script for every running container and exit
+    Export {
+        /// Snapshot directory (default: dockyard-snapshot-<timestamp>)
+        dir: Option<PathBuf>,
+    },
+    /// Pack dockyard's settings and caches into one archive for another machine
+    Backup {
+        /// Archive to write (default: dockyard-backup-<timestamp>.tar)
+        file: Option<PathBuf>,
+    },
+    /// Replace dockyard's settings and caches with those in a backup archive
+    Restore {
+        file: PathBuf,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum StartView {
+    Containers,
+    Top,
+    Images,
+    Services,
+    Stacks,
+    Nodes,
+    System,
+}
+
+impl From<StartView> for View {
+    fn from(view: StartView) -> Self {
+        match view {
+            StartView::Containers => View::Containers,
+            StartView::Top => View::Top,
+            StartView::Images => View::Images,
+            StartView::Services => View::Services,
+            StartView::Stacks => View::Stacks,
+            StartView::Nodes => View::Nodes,
+            StartView::System => View::System,
+        }
+    }
+}
+
+/// `name=TEXT`, in the style of `docker ps --filter`; only names are supported.
+fn parse_filter(value: &str) -> Result<String, String> {
+    match value.split_once('=') {
+        Some(("name", text)) if !text.is_empty() => Ok(text.to_string()),
+        Some(("name", _)) => Err("name filter is empty".to_string()),
+        Some((key, _)) => Err(format!("unsupported filter '{}' (only name=TEXT is supported)", key)),
+        None => Err("expected name=TEXT".to_string()),
+    }
+}
+
+/// `5s`, `1m`, a bare number of seconds, or `manual`.
+fn parse_refresh(value: &str) -> Result<RefreshRate, String> {
+    if value.eq_ignore_ascii_case("manual") {
+        return Ok(RefreshRate::Manual);
+    }
+    let (number, unit) = match value.strip_suffix('m') {
+        Some(minutes) => (minutes, 60),
+        None => (value.strip_suffix('s').unwrap_or(value), 1),
+    };
+    match number.parse::<u64>() {
+        Ok(n) if n > 0 => Ok(RefreshRate::Interval(Duration::from_secs(n * unit))),
+        _ => Err(format!("invalid refresh rate '{}' (use e.g. 5s, 1m or manual)", value)),
+    }
+}
@@ -0,0 +1,11 @@
+use base64::Engine;
+use std::io::Write;
+
+/// Copies `text` to the system clipboard through the terminal (OSC 52), which
+/// also works over SSH. Terminals that don't support it silently ignore it.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
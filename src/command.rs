@@ -0,0 +1,157 @@
+//! Bulk commands typed at the `:` prompt in the Containers view, such as
+//! `stop api-*` or `rm exited>7d`: an action followed by terms that pick the
+//! containers it applies to.
+
+use bollard::models::ContainerInspectResponse;
+use chrono::{DateTime, Utc};
+
+use crate::types::{glob_match, ContainerAction, ContainerInfo};
+
+/// Shown under the prompt while nothing has been typed.
+pub const SYNTAX: &str = "start|stop|restart|rm  NAME-GLOB... STATE[>AGE] image=GLOB label=K[=V] project=P host=H";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    /// A container state, or `stopped` for any of exited, created and dead.
+    /// With an age, only containers that have been in it for longer match.
+    State { state: String, older_than: Option<i64> },
+    Image(String),
+    Label { key: String, value: Option<String> },
+    Project(String),
+    Host(String),
+}
+
+/// A parsed bulk command. Containers must match any of the name globs, when
+/// there are some, and every other term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkCommand {
+    pub action: ContainerAction,
+    names: Vec<String>,
+    terms: Vec<Term>,
+}
+
+const STATES: [&str; 8] = ["running", "paused", "restarting", "exited", "created", "dead", "removing", "stopped"];
+
+pub fn parse(input: &str) -> Result<BulkCommand, String> {
+    let mut words = input.split_whitespace();
+    let action = match words.next() {
+        Some("start") => ContainerAction::Start,
+        Some("stop") => ContainerAction::Stop,
+        Some("restart") => ContainerAction::Restart,
+        Some("rm" | "remove") => ContainerAction::Remove,
+        Some(other) => return Err(format!("Unknown action '{}' (start, stop, restart or rm)", other)),
+        None => return Err("Type an action and a pattern, e.g. stop api-*".to_string()),
+    };
+
+    let mut command = BulkCommand { action, names: Vec::new(), terms: Vec::new() };
+    for word in words {
+        let term = if let Some((key, value)) = word.split_once('=') {
+            if value.is_empty() {
+                return Err(format!("'{}' needs a value", key));
+            }
+            match key {
+                "image" => Term::Image(value.to_string()),
+                "label" => match value.split_once('=') {
+                    Some((k, v)) => Term::Label { key: k.to_string(), value: Some(v.to_string()) },
+                    None => Term::Label { key: value.to_string(), value: None },
+                },
+                "project" => Term::Project(value.to_string()),
+                "host" => Term::Host(value.to_string()),
+                _ => return Err(format!("Unknown filter '{}=' (image, label, project or host)", key)),
+            }
+        } else {
+            let (state, age) = match word.split_once('>') {
+                Some((state, age)) => (state, Some(age)),
+                None => (word, None),
+            };
+            if STATES.contains(&state) {
+                let older_than = age.map(parse_age).transpose()?;
+                Term::State { state: state.to_string(), older_than }
+            } else if age.is_some() {
+                return Err(format!("'{}' is not a container state", state));
+            } else {
+                command.names.push(word.to_string());
+                continue;
+            }
+        };
+        command.terms.push(term);
+    }
+    if command.names.is_empty() && command.terms.is_empty() {
+        return Err("Add a name pattern or filter; use * for every container".to_string());
+    }
+    Ok(command)
+}
+
+/// `30s`, `15m`, `12h`, `7d` or `2w`, in seconds.
+fn parse_age(age: &str) -> Result<i64, String> {
+    let invalid = || format!("Invalid age '{}'; use e.g. 30m, 12h or 7d", age);
+    let unit = age.chars().last().ok_or_else(invalid)?;
+    let count: i64 = age[..age.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    let secs = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86400,
+        'w' => 7 * 86400,
+        _ => return Err(invalid()),
+    };
+    count.checked_mul(secs).ok_or_else(invalid)
+}
+
+fn state_matches(wanted: &str, state: &str) -> bool {
+    match wanted {
+        "stopped" => matches!(state, "exited" | "created" | "dead"),
+        _ => wanted == state,
+    }
+}
+
+impl BulkCommand {
+    /// Whether `container` matches, leaving out state ages, which need an inspect.
+    pub fn matches(&self, container: &ContainerInfo) -> bool {
+        let named = self.names.is_empty() || self.names.iter().any(|pattern| glob_match(pattern, &container.name));
+        named && self.terms.iter().all(|term| match term {
+            Term::State { state, .. } => state_matches(state, &container.state),
+            Term::Image(pattern) => glob_match(pattern, &container.image),
+            Term::Label { key, value } => match (container.labels.get(key), value) {
+                (Some(actual), Some(wanted)) => glob_match(wanted, actual),
+                (found, None) => found.is_some(),
+                (None, Some(_)) => false,
+            },
+            Term::Project(project) => container.compose_project() == Some(project.as_str()),
+            Term::Host(host) => container.host.as_deref().unwrap_or("local") == host,
+        })
+    }
+
+    /// Whether a term asks for containers that aren't running, so they have to be listed.
+    pub fn needs_stopped(&self) -> bool {
+        self.terms.iter().any(|term| matches!(term, Term::State { state, .. } if !matches!(state.as_str(), "running" | "paused" | "restarting")))
+    }
+
+    /// Whether matching needs each candidate inspected for how long it has been in its state.
+    pub fn needs_inspect(&self) -> bool {
+        self.terms.iter().any(|term| matches!(term, Term::State { older_than: Some(_), .. }))
+    }
+
+    /// Checks the state ages against an inspect of the container.
+    pub fn matches_age(&self, info: &ContainerInspectResponse, now: DateTime<Utc>) -> bool {
+        self.terms.iter().all(|term| match term {
+            Term::State { older_than: Some(limit), .. } => state_age(info, now).is_some_and(|age| age > *limit),
+            _ => true,
+        })
+    }
+}
+
+/// Seconds since the container entered its current state: since it started
+/// while it runs, since it finished once it has stopped, and since it was
+/// created if it never ran.
+fn state_age(info: &ContainerInspectResponse, now: DateTime<Utc>) -> Option<i64> {
+    let state = info.state.as_ref()?;
+    let running = state.running.unwrap_or(false) || state.paused.unwrap_or(false) || state.restarting.unwrap_or(false);
+    let since = if running { state.started_at.as_deref() } else { state.finished_at.as_deref() };
+    // Docker reports 0001-01-01 for a container that never ran
+    let since = since
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .filter(|t| t.timestamp() > 0)
+        .or_else(|| info.created.as_deref().and_then(|t| DateTime::parse_from_rfc3339(t).ok()))?;
+    Some((now - since.with_timezone(&Utc)).num_seconds())
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use config::{Config, File};
 use directories::ProjectDirs;
@@ -29,8 +29,25 @@ pub fn load_config() -> Result<AppConfig> {
         return Ok(default_config);
     }
 
+    match read_config(&config_path) {
+        Ok(config) => Ok(config),
+        // A hand-edited file that no longer parses: fall back to the last good save
+        Err(e) => read_config(&backup_path(&config_path)).map_err(|_| e),
+    }
+}
+
+fn read_config(path: &Path) -> Result<AppConfig> {
+    let settings = Config::builder()
+        .add_source(File::from(path).format(config::FileFormat::Toml))
+        .build()?;
+
+    settings.try_deserialize::<AppConfig>().context("Failed to parse configuration")
+}
+
+/// Parses `config.toml` contents the way they are read at startup.
+pub fn parse_config(text: &str) -> Result<AppConfig> {
     let settings = Config::builder()
-        .add_source(File::from(config_path))
+        .add_source(File::from_str(text, config::FileFormat::Toml))
         .build()?;
 
     settings.try_deserialize::<AppConfig>().context("Failed to parse configuration")
@@ -39,9 +56,41 @@ pub fn load_config() -> Result<AppConfig> {
 pub fn save_config(config: &AppConfig) -> Result<()> {
     let config_path = get_config_path()?;
     let toml_string = toml::to_string_pretty(config)?;
-    
-    let mut file = fs::File::create(config_path)?;
-    file.write_all(toml_string.as_bytes())?;
-    
+    write_atomic(&config_path, toml_string.as_bytes())
+}
+
+/// `config.toml` -> `config.toml.bak`
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Replaces `path` without ever leaving it half-written: the contents go to a
+/// synced temp file next to it, the previous version is kept as `<name>.bak`,
+/// and the temp file is renamed over the original.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut tmp_name = path.file_name().context("Path has no file name")?.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = fs::File::create(&tmp_path)
+        .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    if path.exists() {
+        fs::copy(path, backup_path(path))
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+    }
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+
+    // Persist the rename itself; not possible (or needed) on every platform
+    #[cfg(unix)]
+    if let Some(dir) = path.parent().and_then(|d| fs::File::open(d).ok()) {
+        let _ = dir.sync_all();
+    }
     Ok(())
 }
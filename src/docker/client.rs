@@ -1,15 +1,405 @@
-use bollard::Docker;
-use crate::types::{Result, AppError};
+use bollard::{ClientVersion, Docker, API_DEFAULT_VERSION};
+use crate::docker::context::{default_host, env_tls, list_contexts, DEFAULT_CONTEXT};
+use crate::types::{DockerContext, Result, AppError, TimeoutConfig, TlsConfig};
+use http_body_util::{BodyExt, Empty};
+use hyper::body::Bytes;
+use hyper::header::HOST;
+use hyper::{Method, Request};
+use hyper_util::rt::TokioIo;
+use rustls::pki_types::ServerName;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Read/write timeout in seconds, matching bollard's local defaults.
+const CONNECT_TIMEOUT_SECS: u64 = 120;
+
+/// Daemon features that need a newer API version than old engines speak.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiFeature {
+    HealthChecks,
+    Swarm,
+    SystemDf,
+    Prune,
+    BuildCache,
+    /// The `all` filter for volume prune, which older daemons reject as unknown.
+    PruneNamedVolumes,
+}
+
+impl ApiFeature {
+    pub fn label(self) -> &'static str {
+        match self {
+            ApiFeature::HealthChecks => "Health checks",
+            ApiFeature::Swarm => "Swarm mode",
+            ApiFeature::SystemDf => "Disk usage (system df)",
+            ApiFeature::Prune => "Pruning",
+            ApiFeature::BuildCache => "Build cache",
+            ApiFeature::PruneNamedVolumes => "Pruning named volumes",
+        }
+    }
+
+    /// First API version with the feature.
+    pub fn min_version(self) -> (usize, usize) {
+        match self {
+            ApiFeature::HealthChecks | ApiFeature::Swarm => (1, 24),
+            ApiFeature::SystemDf | ApiFeature::Prune => (1, 25),
+            ApiFeature::BuildCache => (1, 31),
+            ApiFeature::PruneNamedVolumes => (1, 42),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct DockerClient {
     pub(crate) inner: Docker,
+    /// The endpoint URL this client talks to, e.g. `unix:///var/run/docker.sock`.
+    pub endpoint: String,
+    pub tls: bool,
+    /// Set by `detect_engine` when the endpoint is Podman's Docker-compatible API.
+    pub podman: bool,
+    /// Limits for calls that can hang on an unresponsive daemon (`[timeouts]` in the config).
+    pub timeouts: TimeoutConfig,
+    /// Kept to reconnect with an older API version for an old daemon.
+    tls_config: Option<TlsConfig>,
 }
 
 impl DockerClient {
+    /// Connects to `DOCKER_HOST`, or the platform's default socket or named pipe
+    /// (falling back to a Podman socket).
     pub fn new() -> Result<Self> {
-        let inner = Docker::connect_with_local_defaults()
-            .map_err(AppError::Docker)?;
-        Ok(Self { inner })
+        Self::connect(&default_host(), env_tls().as_ref())
+    }
+
+    /// Connects to a daemon endpoint: a unix socket, a Windows named pipe
+    /// (`npipe:////./pipe/docker_engine`), `ssh://user@host` (via the system ssh
+    /// client and `docker system dial-stdio` on the remote), or `tcp://` over TLS
+    /// when certificates are given and plain HTTP otherwise. The connection is
+    /// lazy, so callers should ping it.
+    pub fn connect(host: &str, tls: Option<&TlsConfig>) -> Result<Self> {
+        Self::connect_with_version(host, tls, API_DEFAULT_VERSION)
+    }
+
+    fn connect_with_version(host: &str, tls: Option<&TlsConfig>, version: &ClientVersion) -> Result<Self> {
+        let mut with_tls = false;
+        let inner = if host.starts_with("unix://") {
+            connect_unix(host, version)?
+        } else if host.starts_with("npipe://") {
+            connect_named_pipe(host, version)?
+        } else if host.starts_with("ssh://") {
+            connect_ssh(host, version)?
+        } else if let Some(tls) = tls.filter(|_| host.starts_with("tcp://") || host.starts_with("https://")) {
+            for path in [&tls.ca, &tls.cert, &tls.key] {
+                if !path.is_file() {
+                    return Err(AppError::Other(format!("TLS file {} not found", path.display())));
+                }
+            }
+            with_tls = true;
+            Docker::connect_with_ssl(host, &tls.key, &tls.cert, &tls.ca, CONNECT_TIMEOUT_SECS, version)?
+        } else if host.starts_with("tcp://") || host.starts_with("http://") {
+            Docker::connect_with_http(host, CONNECT_TIMEOUT_SECS, version)?
+        } else {
+            return Err(AppError::Other(format!("unsupported endpoint '{}'", host)));
+        };
+        Ok(Self { inner, endpoint: host.to_string(), tls: with_tls, podman: false, timeouts: TimeoutConfig::default(), tls_config: tls.cloned() })
+    }
+
+    pub fn for_context(context: &DockerContext) -> Result<Self> {
+        Self::connect(&context.host, context.tls.as_ref())
+    }
+
+    /// Picks the daemon the way the docker CLI does: an explicit host (`--host` or
+    /// the config file) wins over a named context, which wins over the current
+    /// one. Returns the client and a label for the status bar.
+    pub fn resolve(host: Option<&str>, context: Option<&str>, tls: Option<&TlsConfig>) -> Result<(Self, String)> {
+        if let Some(host) = host {
+            return Ok((Self::connect(host, tls)?, host.to_string()));
+        }
+        let contexts = list_contexts()?;
+        let selected = match context {
+            Some(name) => Some(contexts.into_iter().find(|c| c.name == name)
+                .ok_or_else(|| AppError::Other(format!("unknown Docker context '{}'", name)))?),
+            None => contexts.into_iter().find(|c| c.current),
+        };
+        match selected {
+            Some(context) => Ok((Self::for_context(&context)?, context.name)),
+            None => Ok((Self::new()?, DEFAULT_CONTEXT.to_string())),
+        }
+    }
+
+    /// How requests reach the daemon, for the diagnostics screen.
+    pub fn transport(&self) -> &'static str {
+        match self.endpoint.split("://").next() {
+            Some("unix") => "unix socket",
+            Some("npipe") => "named pipe",
+            Some("ssh") => "ssh",
+            _ if self.tls => "tcp (TLS)",
+            _ => "tcp (plain HTTP)",
+        }
+    }
+
+    /// Whether the daemon runs on this machine, so its published ports are bound here.
+    pub fn is_local(&self) -> bool {
+        match self.endpoint.split_once("://") {
+            Some(("unix" | "npipe", _)) => true,
+            Some((_, rest)) => {
+                let host = rest.rsplit_once(':').map_or(rest, |(host, _)| host);
+                matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+            }
+            None => false,
+        }
+    }
+
+    pub fn with_timeouts(mut self, timeouts: TimeoutConfig) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    pub async fn ping(&self) -> Result<()> {
+        self.inner.ping().await?;
+        Ok(())
+    }
+
+    /// Checks whether the daemon is Podman, whose compat API has no swarm mode
+    /// or BuildKit cache, and drops to the daemon's API version when it is older
+    /// than ours. Leaves things as they are if the daemon can't be reached.
+    pub async fn detect_engine(&mut self) {
+        let mut probe = self.probe_version().await;
+        // Docker refuses every call, /version included, made with a newer API version than its own
+        if let Some(max) = probe.as_ref().err().and_then(|e| max_supported_version(&e.to_string())) {
+            self.downgrade(max);
+            probe = self.probe_version().await;
+        }
+        let Ok(version) = probe else { return };
+        // Podman accepts any version in the path, so compare against what it reports
+        if let Some(server) = version.api_version.as_deref().and_then(parse_api_version) {
+            if server < self.api_version() {
+                self.downgrade(server);
+            }
+        }
+        self.podman = version.platform.map(|p| p.name).into_iter()
+            .chain(version.components.unwrap_or_default().into_iter().map(|c| c.name))
+            .any(|name| name.contains("Podman"));
+    }
+
+    async fn probe_version(&self) -> Result<bollard::models::SystemVersion> {
+        match tokio::time::timeout(std::time::Duration::from_secs(5), self.inner.version()).await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(AppError::Timeout { operation: "version check", secs: 5 }),
+        }
+    }
+
+    fn downgrade(&mut self, (major_version, minor_version): (usize, usize)) {
+        let version = ClientVersion { major_version, minor_version };
+        match Self::connect_with_version(&self.endpoint, self.tls_config.as_ref(), &version) {
+            Ok(client) => {
+                tracing::info!(version = %version, "daemon speaks an older API, downgrading");
+                self.inner = client.inner;
+            }
+            Err(e) => tracing::warn!("failed to reconnect with API {}: {}", version, e),
+        }
+    }
+
+    /// API version requests are made with, after any downgrade for an old daemon.
+    pub fn api_version(&self) -> (usize, usize) {
+        let version = self.inner.client_version();
+        (version.major_version, version.minor_version)
+    }
+
+    pub fn supports(&self, feature: ApiFeature) -> bool {
+        self.api_version() >= feature.min_version()
+    }
+
+    /// Fails with an explanation instead of letting the daemon reject the call.
+    pub fn require(&self, feature: ApiFeature) -> Result<()> {
+        if self.supports(feature) {
+            return Ok(());
+        }
+        let (major, minor) = self.api_version();
+        let (min_major, min_minor) = feature.min_version();
+        Err(AppError::Unsupported {
+            feature: feature.label(),
+            version: format!("{}.{}", major, minor),
+            required: format!("{}.{}", min_major, min_minor),
+        })
     }
 }
+
+/// `1.41` as reported in `ApiVersion`.
+fn parse_api_version(version: &str) -> Option<(usize, usize)> {
+    let (major, minor) = version.trim().split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// The daemon's version from "client version 1.49 is too new. Maximum
+/// supported API version is 1.41".
+fn max_supported_version(error: &str) -> Option<(usize, usize)> {
+    let (_, rest) = error.split_once("Maximum supported API version is ")?;
+    parse_api_version(rest.split(|c: char| !(c.is_ascii_digit() || c == '.')).next()?)
+}
+
+#[cfg(unix)]
+fn connect_unix(host: &str, version: &ClientVersion) -> Result<Docker> {
+    Ok(Docker::connect_with_socket(host, CONNECT_TIMEOUT_SECS, version)?)
+}
+
+#[cfg(not(unix))]
+fn connect_unix(host: &str, _version: &ClientVersion) -> Result<Docker> {
+    Err(AppError::Other(format!("'{}' is a unix socket; on Windows use npipe:////./pipe/docker_engine", host)))
+}
+
+/// Unlike `connect_with_socket`, doesn't check the path first: named pipes
+/// don't reliably show up as existing files, and the ping reports a missing one.
+#[cfg(windows)]
+fn connect_named_pipe(host: &str, version: &ClientVersion) -> Result<Docker> {
+    Ok(Docker::connect_with_named_pipe(host, CONNECT_TIMEOUT_SECS, version)?)
+}
+
+#[cfg(not(windows))]
+fn connect_named_pipe(host: &str, _version: &ClientVersion) -> Result<Docker> {
+    Err(AppError::Other(format!("named pipe endpoint '{}' is only available on Windows", host)))
+}
+
+#[cfg(unix)]
+fn connect_ssh(host: &str, version: &ClientVersion) -> Result<Docker> {
+    Ok(Docker::connect_with_ssh(host, CONNECT_TIMEOUT_SECS, version)?)
+}
+
+#[cfg(not(unix))]
+fn connect_ssh(host: &str, _version: &ClientVersion) -> Result<Docker> {
+    Err(AppError::Other(format!("ssh endpoint '{}' is not supported on this platform", host)))
+}
+
+/// Awaits `call`, giving up with `AppError::Timeout` after `secs`.
+pub async fn with_timeout<T>(operation: &'static str, secs: u64, call: impl Future<Output = Result<T>>) -> Result<T> {
+    match tokio::time::timeout(Duration::from_secs(secs), call).await {
+        Ok(result) => result,
+        Err(_) => {
+            tracing::warn!(operation, secs, "docker call timed out");
+            Err(AppError::Timeout { operation, secs })
+        }
+    }
+}
+
+/// A byte stream to the daemon, whichever transport the endpoint uses.
+trait DaemonIo: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send {}
+
+impl<T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send> DaemonIo for T {}
+
+impl DockerClient {
+    /// Sends one request to the daemon without bollard, for endpoints it has no
+    /// call for. `path` goes after the API version, e.g. `/configs`. Error
+    /// responses come back as `DockerResponseServerError`, like bollard's own.
+    pub(crate) async fn raw_request(&self, method: Method, path: &str) -> Result<Bytes> {
+        // An ssh transport's process is killed once this returns, timed out or not
+        let mut ssh = None;
+        with_timeout("daemon request", self.timeouts.inspect_secs, self.send_raw(method, path, &mut ssh)).await
+    }
+
+    async fn send_raw(&self, method: Method, path: &str, ssh: &mut Option<tokio::process::Child>) -> Result<Bytes> {
+        let (major, minor) = self.api_version();
+        let stream = self.open_stream(ssh).await?;
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(|e| AppError::Other(format!("daemon handshake failed: {}", e)))?;
+        tokio::spawn(connection);
+
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("/v{}.{}{}", major, minor, path))
+            .header(HOST, "docker")
+            .body(Empty::<Bytes>::new())
+            .map_err(|e| AppError::Other(e.to_string()))?;
+        let response = sender.send_request(request).await
+            .map_err(|e| AppError::Other(format!("daemon request failed: {}", e)))?;
+        let status = response.status();
+        let body = response.into_body().collect().await
+            .map_err(|e| AppError::Other(format!("daemon response failed: {}", e)))?
+            .to_bytes();
+        if status.is_success() {
+            return Ok(body);
+        }
+        #[derive(serde::Deserialize)]
+        struct ErrorBody { message: String }
+        let message = serde_json::from_slice::<ErrorBody>(&body)
+            .map(|e| e.message)
+            .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+        Err(bollard::errors::Error::DockerResponseServerError { status_code: status.as_u16(), message }.into())
+    }
+
+    /// Connects to the daemon. For ssh endpoints, the ssh process is put in
+    /// `ssh`, and the stream lasts as long as it is kept.
+    async fn open_stream(&self, ssh: &mut Option<tokio::process::Child>) -> Result<Box<dyn DaemonIo>> {
+        let Some((scheme, rest)) = self.endpoint.split_once("://") else {
+            return Err(AppError::Other(format!("unsupported endpoint '{}'", self.endpoint)));
+        };
+        match scheme {
+            #[cfg(unix)]
+            "unix" => Ok(Box::new(tokio::net::UnixStream::connect(rest).await?)),
+            #[cfg(windows)]
+            "npipe" => {
+                let pipe = rest.replace('/', "\\");
+                Ok(Box::new(tokio::net::windows::named_pipe::ClientOptions::new().open(pipe)?))
+            }
+            // The same route bollard takes, through the system ssh client
+            "ssh" => {
+                // Batch mode, as there is no terminal to answer a password prompt on
+                let child = ssh.insert(tokio::process::Command::new("ssh")
+                    .args(["-o", "BatchMode=yes"])
+                    .arg(&self.endpoint)
+                    .args(["docker", "system", "dial-stdio"])
+                    .stdin(std::process::Stdio::piped())
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn()?);
+                let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+                    return Err(AppError::Other("ssh did not open its pipes".to_string()));
+                };
+                // dial-stdio exits once its stdin closes
+                Ok(Box::new(tokio::io::join(stdout, stdin)))
+            }
+            "tcp" | "http" | "https" => {
+                let address = rest.trim_end_matches('/');
+                let address = if address.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+                    address.to_string()
+                } else {
+                    format!("{}:{}", address, if self.tls { 2376 } else { 2375 })
+                };
+                let tcp = tokio::net::TcpStream::connect(&address).await?;
+                match self.tls_config.as_ref().filter(|_| self.tls) {
+                    Some(tls) => Ok(Box::new(tls_stream(tcp, &address, tls).await?)),
+                    None => Ok(Box::new(tcp)),
+                }
+            }
+            _ => Err(AppError::Other(format!("unsupported endpoint '{}'", self.endpoint))),
+        }
+    }
+}
+
+/// Wraps `tcp` in TLS with the endpoint's CA and client certificate, as
+/// `connect_with_ssl` does.
+async fn tls_stream(tcp: tokio::net::TcpStream, address: &str, tls: &TlsConfig) -> Result<tokio_rustls::client::TlsStream<tokio::net::TcpStream>> {
+    let pem = |path: &std::path::Path| -> Result<std::io::BufReader<std::fs::File>> {
+        Ok(std::io::BufReader::new(std::fs::File::open(path)?))
+    };
+    let tls_error = |e: rustls::Error| AppError::Other(format!("TLS setup failed: {}", e));
+
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in rustls_pemfile::certs(&mut pem(&tls.ca)?) {
+        roots.add(cert?).map_err(tls_error)?;
+    }
+    let chain = rustls_pemfile::certs(&mut pem(&tls.cert)?).collect::<std::io::Result<Vec<_>>>()?;
+    let key = rustls_pemfile::private_key(&mut pem(&tls.key)?)?
+        .ok_or_else(|| AppError::Other(format!("no private key in {}", tls.key.display())))?;
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .with_root_certificates(roots)
+        .with_client_auth_cert(chain, key)
+        .map_err(tls_error)?;
+
+    let host = address.rsplit_once(':').map_or(address, |(host, _)| host);
+    let server_name = ServerName::try_from(host.trim_start_matches('[').trim_end_matches(']').to_string())
+        .map_err(|e| AppError::Other(format!("invalid TLS server name '{}': {}", host, e)))?;
+    Ok(tokio_rustls::TlsConnector::from(Arc::new(config)).connect(server_name, tcp).await?)
+}
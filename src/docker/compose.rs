@@ -0,0 +1,85 @@
+use crate::docker::client::DockerClient;
+use crate::docker::containers::{config_from_inspect, create_container, endpoints_from_inspect, inspect_container, remove_container, start_container, stop_container};
+use crate::types::{Result, AppError, COMPOSE_PROJECT_LABEL, COMPOSE_SERVICE_LABEL};
+use bollard::query_parameters::ListContainersOptions;
+use std::collections::HashMap;
+
+pub const COMPOSE_NUMBER_LABEL: &str = "com.docker.compose.container-number";
+
+/// Returns `(id, replica number)` for every container of a compose service, sorted by number.
+pub async fn service_replicas(client: &DockerClient, project: &str, service: &str) -> Result<Vec<(String, u32)>> {
+    let mut filters = HashMap::new();
+    filters.insert("label".to_string(), vec![
+        format!("{}={}", COMPOSE_PROJECT_LABEL, project),
+        format!("{}={}", COMPOSE_SERVICE_LABEL, service),
+    ]);
+    let options = ListContainersOptions { all: true, filters: Some(filters), ..Default::default() };
+
+    let mut replicas: Vec<(String, u32)> = client.inner.list_containers(Some(options)).await?
+        .into_iter()
+        .filter_map(|c| {
+            let number = c.labels.as_ref()
+                .and_then(|l| l.get(COMPOSE_NUMBER_LABEL))
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+            c.id.map(|id| (id, number))
+        })
+        .collect();
+    replicas.sort_by_key(|(_, n)| *n);
+    Ok(replicas)
+}
+
+/// Adds or removes replicas of a compose service until it has `target` containers.
+/// New replicas copy the lowest-numbered existing one and follow compose's
+/// `<project>-<service>-<n>` naming; surplus replicas are removed highest number first.
+pub async fn scale_service<F>(client: &DockerClient, project: &str, service: &str, target: u32, log: F) -> Result<()>
+where
+    F: Fn(String),
+{
+    let replicas = service_replicas(client, project, service).await?;
+    let current = replicas.len() as u32;
+    log(format!("Scaling {}/{}: {} -> {} replica(s)", project, service, current, target));
+
+    if target == current {
+        log("Nothing to do".to_string());
+        return Ok(());
+    }
+
+    if target < current {
+        for (id, number) in replicas.iter().rev().take((current - target) as usize) {
+            log(format!("Stopping replica #{} ({})", number, &id[..12.min(id.len())]));
+            let _ = stop_container(client, id).await;
+            remove_container(client, id).await?;
+            log(format!("Removed replica #{}", number));
+        }
+        log("Scale complete".to_string());
+        return Ok(());
+    }
+
+    let (template_id, _) = replicas.first()
+        .ok_or_else(|| AppError::Other("Service has no containers to use as a template".to_string()))?;
+    let template = inspect_container(client, template_id).await?;
+    let endpoints = endpoints_from_inspect(&template);
+
+    let used: Vec<u32> = replicas.iter().map(|(_, n)| *n).collect();
+    let mut next_numbers = (1..).filter(|n| !used.contains(n));
+
+    for _ in current..target {
+        let number = next_numbers.next().unwrap_or(0);
+        let name = format!("{}-{}-{}", project, service, number);
+
+        let mut config = config_from_inspect(&template);
+        config.hostname = None;
+        if let Some(labels) = config.labels.as_mut() {
+            labels.insert(COMPOSE_NUMBER_LABEL.to_string(), number.to_string());
+        }
+
+        log(format!("Creating {}", name));
+        let id = create_container(client, &name, config, endpoints.clone()).await?;
+        log(format!("Starting {}", name));
+        start_container(client, &id).await?;
+    }
+
+    log("Scale complete".to_string());
+    Ok(())
+}
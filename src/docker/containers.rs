@@ -1,8 +1,11 @@
-use crate::docker::client::DockerClient;
-use crate::types::{ContainerInfo, Result};
-use bollard::container::{ListContainersOptions, InspectContainerOptions, RemoveContainerOptions};
-use bollard::models::ContainerInspectResponse;
+use crate::docker::client::{with_timeout, DockerClient};
+use crate::types::{ContainerAction, ContainerInfo, FsChange, FsChangeKind, ProcessList, PublishedPort, Result, COMPOSE_PROJECT_LABEL};
+use bollard::models::{ChangeType, ContainerCreateBody, ContainerSummary, ContainerInspectResponse, EndpointSettings, MountPointTypeEnum, NetworkConnectRequest, NetworkingConfig, PortBinding, PortMap};
+use bollard::query_parameters::{AttachContainerOptions, CreateContainerOptions, InspectContainerOptions, KillContainerOptions, ListContainersOptions, RemoveContainerOptions, RenameContainerOptions, RestartContainerOptions, StartContainerOptions, StopContainerOptions, TopOptions};
+use futures::StreamExt;
 use std::collections::HashMap;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
 
 pub async fn list_containers(client: &DockerClient, all: bool) -> Result<Vec<ContainerInfo>> {
     let mut filters = HashMap::new();
@@ -12,72 +15,142 @@ pub async fn list_containers(client: &DockerClient, all: bool) -> Result<Vec<Con
 
     let options = ListContainersOptions {
         all,
-        filters,
+        filters: Some(filters),
         ..Default::default()
     };
 
     let containers = client.inner.list_containers(Some(options)).await?;
 
-    let infos = containers
+    Ok(containers.into_iter().map(container_info).collect())
+}
+
+/// Host ports published by running containers, for spotting conflicts before
+/// a create or start fails on them.
+pub async fn published_ports(client: &DockerClient) -> Result<Vec<PublishedPort>> {
+    let containers = client.inner.list_containers(Some(ListContainersOptions::default())).await?;
+    Ok(containers.into_iter()
+        .flat_map(|c| {
+            let name = c.names.as_ref().and_then(|n| n.first()).map(|n| n.trim_start_matches('/').to_string()).unwrap_or_default();
+            c.ports.unwrap_or_default().into_iter().filter_map(move |p| Some(PublishedPort {
+                port: p.public_port?,
+                protocol: p.typ.map(|t| t.to_string()).unwrap_or_else(|| "tcp".to_string()),
+                container: name.clone(),
+            }))
+        })
+        .collect())
+}
+
+/// Paths the container changed, added or deleted relative to its image, sorted by path.
+pub async fn filesystem_changes(client: &DockerClient, id: &str) -> Result<Vec<FsChange>> {
+    let mut changes: Vec<FsChange> = client.inner.container_changes(id).await?
+        .unwrap_or_default()
         .into_iter()
-        .map(|c| {
-            let state = c.state.as_deref().unwrap_or("unknown");
-            
-            let ports = c.ports.as_ref().map(|p| {
-                 p.iter()
-                    .take(2)
-                    .filter_map(|port| {
-                        if let Some(public) = port.public_port {
-                            Some(format!("{}→{}", public, port.private_port))
-                        } else {
-                            Some(port.private_port.to_string())
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            }).unwrap_or_default();
-            
-            // Replicating logic from main.rs for short_id
-            let short_id = c.id.as_ref()
-                .map(|id| id.chars().take(12).collect())
-                .unwrap_or_default();
-
-            ContainerInfo {
-                id: c.id.unwrap_or_default(),
-                short_id,
-                name: c.names.as_ref().and_then(|n| n.first()).map(|n| n.trim_start_matches('/').to_string()).unwrap_or_default(),
-                status: c.status.unwrap_or_default(),
-                image: c.image.unwrap_or_default(),
-                ports,
-                created: c.created.unwrap_or(0),
-                state: state.to_string(),
-            }
+        .map(|c| FsChange {
+            path: c.path,
+            kind: match c.kind {
+                ChangeType::_0 => FsChangeKind::Changed,
+                ChangeType::_1 => FsChangeKind::Added,
+                ChangeType::_2 => FsChangeKind::Deleted,
+            },
         })
         .collect();
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(changes)
+}
+
+/// Processes running in the container, as PID, user, CPU time and command.
+/// Falls back to `ps -ef` columns when the host's `ps` doesn't take `-o`.
+pub async fn top_processes(client: &DockerClient, id: &str) -> Result<ProcessList> {
+    let options = TopOptions { ps_args: "-eo pid,user,time,args".to_string() };
+    let top = match client.inner.top_processes(id, Some(options)).await {
+        Ok(top) => top,
+        Err(_) => client.inner.top_processes(id, None::<TopOptions>).await?,
+    };
+    Ok(ProcessList {
+        titles: top.titles.unwrap_or_default(),
+        processes: top.processes.unwrap_or_default(),
+        updated: chrono::Utc::now().timestamp(),
+    })
+}
 
-    Ok(infos)
+/// Looks up a single container by ID, as it would appear in `list_containers(.., true)`.
+pub async fn get_container(client: &DockerClient, id: &str) -> Result<Option<ContainerInfo>> {
+    let mut filters = HashMap::new();
+    filters.insert("id".to_string(), vec![id.to_string()]);
+    let options = ListContainersOptions {
+        all: true,
+        filters: Some(filters),
+        ..Default::default()
+    };
+    let containers = client.inner.list_containers(Some(options)).await?;
+    Ok(containers.into_iter().map(container_info).find(|c| c.id == id))
+}
+
+fn container_info(c: ContainerSummary) -> ContainerInfo {
+    let state = c.state.map(|s| s.to_string()).unwrap_or_else(|| "unknown".to_string());
+    
+    let ports = c.ports.as_ref().map(|p| {
+         p.iter()
+            .take(2)
+            .map(|port| {
+                if let Some(public) = port.public_port {
+                    format!("{}→{}", public, port.private_port)
+                } else {
+                    port.private_port.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }).unwrap_or_default();
+    
+    // Replicating logic from main.rs for short_id
+    let short_id = c.id.as_ref()
+        .map(|id| id.chars().take(12).collect())
+        .unwrap_or_default();
+
+    ContainerInfo {
+        id: c.id.unwrap_or_default(),
+        short_id,
+        name: c.names.as_ref().and_then(|n| n.first()).map(|n| n.trim_start_matches('/').to_string()).unwrap_or_default(),
+        status: c.status.unwrap_or_default(),
+        image: c.image.unwrap_or_default(),
+        ports,
+        created: c.created.unwrap_or(0),
+        state,
+        labels: c.labels.unwrap_or_default(),
+        host: None,
+    }
 }
 
 pub async fn inspect_container(client: &DockerClient, id: &str) -> Result<ContainerInspectResponse> {
-    client.inner.inspect_container(id, None::<InspectContainerOptions>).await.map_err(Into::into)
+    let call = async { client.inner.inspect_container(id, None::<InspectContainerOptions>).await.map_err(Into::into) };
+    with_timeout("inspect", client.timeouts.inspect_secs, call).await
 }
 
 pub async fn start_container(client: &DockerClient, id: &str) -> Result<()> {
-    client.inner.start_container::<String>(id, None).await.map_err(Into::into)
+    client.inner.start_container(id, None::<StartContainerOptions>).await.map_err(Into::into)
 }
 
 pub async fn stop_container(client: &DockerClient, id: &str) -> Result<()> {
-    client.inner.stop_container(id, None).await.map_err(Into::into)
+    let call = async { client.inner.stop_container(id, None::<StopContainerOptions>).await.map_err(Into::into) };
+    with_timeout("stop", client.timeouts.stop_secs, call).await
 }
 
 pub async fn restart_container(client: &DockerClient, id: &str) -> Result<()> {
-    client.inner.restart_container(id, None).await.map_err(Into::into)
+    let call = async { client.inner.restart_container(id, None::<RestartContainerOptions>).await.map_err(Into::into) };
+    with_timeout("restart", client.timeouts.stop_secs, call).await
 }
 
 pub async fn pause_container(client: &DockerClient, id: &str) -> Result<()> {
     client.inner.pause_container(id).await.map_err(Into::into)
 }
 
+/// Sends `signal` (name like `SIGHUP` or a number) to the container's main process.
+pub async fn kill_container(client: &DockerClient, id: &str, signal: &str) -> Result<()> {
+    let options = KillContainerOptions { signal: signal.to_string() };
+    client.inner.kill_container(id, Some(options)).await.map_err(Into::into)
+}
+
 pub async fn unpause_container(client: &DockerClient, id: &str) -> Result<()> {
     client.inner.unpause_container(id).await.map_err(Into::into)
 }
@@ -89,3 +162,298 @@ pub async fn remove_container(client: &DockerClient, id: &str) -> Result<()> {
     };
     client.inner.remove_container(id, Some(options)).await.map_err(Into::into)
 }
+
+/// Writes `input` to the stdin of a container started with `-i`, through a
+/// short-lived attach instead of a full `docker attach` session.
+pub async fn send_stdin(client: &DockerClient, id: &str, input: &[u8]) -> Result<()> {
+    let options = AttachContainerOptions { stdin: true, stream: true, ..Default::default() };
+    let mut attached = client.inner.attach_container(id, Some(options)).await?;
+    attached.input.write_all(input).await?;
+    attached.input.flush().await?;
+    Ok(())
+}
+
+/// Size of the container's whole filesystem (image layers included), which is
+/// roughly what `export_filesystem` writes. Computing it can take a while.
+pub async fn root_fs_size(client: &DockerClient, id: &str) -> Result<Option<u64>> {
+    let info = client.inner.inspect_container(id, Some(InspectContainerOptions { size: true })).await?;
+    Ok(info.size_root_fs.and_then(|s| u64::try_from(s).ok()))
+}
+
+/// Streams the container's filesystem to `path` as an uncompressed tar,
+/// reporting the bytes written so far. A partial file is removed on failure.
+pub async fn export_filesystem(client: &DockerClient, id: &str, path: &Path, progress: impl Fn(u64)) -> Result<u64> {
+    let mut file = tokio::fs::File::create(path).await?;
+    let result = async {
+        let mut stream = client.inner.export_container(id);
+        let mut written = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+            progress(written);
+        }
+        file.flush().await?;
+        Ok(written)
+    }.await;
+    if result.is_err() {
+        drop(file);
+        let _ = tokio::fs::remove_file(path).await;
+    }
+    result
+}
+
+pub async fn apply_container_action(client: &DockerClient, id: &str, action: ContainerAction) -> Result<()> {
+    match action {
+        ContainerAction::Start => start_container(client, id).await,
+        ContainerAction::Stop => stop_container(client, id).await,
+        ContainerAction::Restart => restart_container(client, id).await,
+        ContainerAction::Remove => remove_container(client, id).await,
+    }
+}
+
+/// Builds a create config equivalent to an existing container (config, host config
+/// and network aliases). Container-specific values such as the generated hostname
+/// and MAC address are dropped so the new container gets its own.
+pub fn config_from_inspect(info: &ContainerInspectResponse) -> ContainerCreateBody {
+    let c = info.config.clone().unwrap_or_default();
+    let mut config = ContainerCreateBody {
+        hostname: c.hostname,
+        domainname: c.domainname,
+        user: c.user,
+        attach_stdin: c.attach_stdin,
+        attach_stdout: c.attach_stdout,
+        attach_stderr: c.attach_stderr,
+        exposed_ports: c.exposed_ports,
+        tty: c.tty,
+        open_stdin: c.open_stdin,
+        stdin_once: c.stdin_once,
+        env: c.env,
+        cmd: c.cmd,
+        healthcheck: c.healthcheck,
+        args_escaped: c.args_escaped,
+        image: c.image,
+        volumes: c.volumes,
+        working_dir: c.working_dir,
+        entrypoint: c.entrypoint,
+        network_disabled: c.network_disabled,
+        on_build: c.on_build,
+        labels: c.labels,
+        stop_signal: c.stop_signal,
+        stop_timeout: c.stop_timeout,
+        shell: c.shell,
+        ..Default::default()
+    };
+
+    let id = info.id.as_deref().unwrap_or_default();
+    if config.hostname.as_deref().is_some_and(|h| id.starts_with(h)) {
+        config.hostname = None;
+    }
+    config.host_config = info.host_config.clone();
+    config
+}
+
+/// Network endpoints of an existing container, keeping only aliases so the
+/// daemon allocates fresh addresses.
+pub fn endpoints_from_inspect(info: &ContainerInspectResponse) -> Vec<(String, EndpointSettings)> {
+    let mut endpoints: Vec<(String, EndpointSettings)> = info.network_settings.as_ref()
+        .and_then(|n| n.networks.as_ref())
+        .map(|networks| {
+            networks.iter()
+                .map(|(name, ep)| {
+                    let aliases = ep.aliases.as_ref().map(|a| {
+                        a.iter().filter(|alias| !id_prefix_of(info, alias)).cloned().collect()
+                    });
+                    (name.clone(), EndpointSettings { aliases, ..Default::default() })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    endpoints.sort_by(|a, b| a.0.cmp(&b.0));
+    endpoints
+}
+
+fn id_prefix_of(info: &ContainerInspectResponse, alias: &str) -> bool {
+    info.id.as_deref().is_some_and(|id| id.starts_with(alias))
+}
+
+/// Creates a container and attaches it to `endpoints`. The first endpoint is set at
+/// create time (older daemons only accept one), the rest are connected afterwards.
+pub async fn create_container(
+    client: &DockerClient,
+    name: &str,
+    mut config: ContainerCreateBody,
+    endpoints: Vec<(String, EndpointSettings)>,
+) -> Result<String> {
+    let mut endpoints = endpoints.into_iter();
+    let uses_custom_network = config.host_config.as_ref()
+        .and_then(|h| h.network_mode.as_deref())
+        .is_some_and(|mode| !mode.starts_with("container:") && mode != "host" && mode != "none");
+
+    let first = if uses_custom_network { endpoints.next() } else { None };
+    if let Some((network, settings)) = first {
+        let mut endpoints_config = HashMap::new();
+        endpoints_config.insert(network, settings);
+        config.networking_config = Some(NetworkingConfig { endpoints_config: Some(endpoints_config) });
+    }
+
+    let options = CreateContainerOptions { name: Some(name.to_string()), ..Default::default() };
+    let response = client.inner.create_container(Some(options), config).await?;
+
+    if uses_custom_network {
+        for (network, settings) in endpoints {
+            let options = NetworkConnectRequest { container: Some(response.id.clone()), endpoint_config: Some(settings) };
+            client.inner.connect_network(&network, options).await?;
+        }
+    }
+
+    Ok(response.id)
+}
+
+pub async fn rename_container(client: &DockerClient, id: &str, name: &str) -> Result<()> {
+    let options = RenameContainerOptions { name: name.to_string() };
+    client.inner.rename_container(id, options).await.map_err(Into::into)
+}
+
+/// What is lost or goes out of sync when a container is replaced by a copy of
+/// itself, for the confirmation of anything built on `recreate_container`.
+pub fn recreate_warnings(info: &ContainerInspectResponse) -> Vec<String> {
+    let mut warnings = vec!["Files written outside volumes are lost".to_string()];
+    let compose = info.config.as_ref()
+        .and_then(|c| c.labels.as_ref())
+        .is_some_and(|labels| labels.contains_key(COMPOSE_PROJECT_LABEL));
+    if compose {
+        warnings.push("Drifts from the compose file: the next `docker compose up` may undo this".to_string());
+    }
+    warnings
+}
+
+/// Binds (`name:destination[:ro]`) for the anonymous volumes of the container
+/// described by `info`: volume mounts that no bind or mount in its host config
+/// asks for, so the daemon created them from the image's `VOLUME`s.
+fn anonymous_volume_binds(info: &ContainerInspectResponse) -> Vec<String> {
+    let host = info.host_config.as_ref();
+    let bound: Vec<&str> = host.and_then(|h| h.binds.as_ref()).into_iter().flatten()
+        .filter_map(|b| b.split(':').nth(1))
+        .chain(host.and_then(|h| h.mounts.as_ref()).into_iter().flatten().filter_map(|m| m.target.as_deref()))
+        .collect();
+    info.mounts.iter().flatten()
+        .filter(|m| m.typ == Some(MountPointTypeEnum::VOLUME))
+        .filter_map(|m| {
+            let (name, destination) = (m.name.as_deref()?, m.destination.as_deref()?);
+            if bound.contains(&destination) {
+                return None;
+            }
+            let mode = if m.rw == Some(false) { ":ro" } else { "" };
+            Some(format!("{}:{}{}", name, destination, mode))
+        })
+        .collect()
+}
+
+/// Replaces the container described by `info` with one created from `config`,
+/// under the same name and on the same networks. Anonymous volumes are
+/// reattached to the new container by name, as `docker compose` does, instead
+/// of being replaced by empty ones. The old container is stopped and renamed
+/// aside, and only removed once the new one is up; if anything fails it is
+/// renamed back (and restarted if it was running).
+pub async fn recreate_container<F>(client: &DockerClient, info: &ContainerInspectResponse, mut config: ContainerCreateBody, log: F) -> Result<String>
+where
+    F: Fn(String),
+{
+    let anonymous = anonymous_volume_binds(info);
+    if !anonymous.is_empty() {
+        config.host_config.get_or_insert_with(Default::default)
+            .binds.get_or_insert_with(Vec::new)
+            .extend(anonymous);
+    }
+    let id = info.id.clone().unwrap_or_default();
+    let name = info.name.as_deref().unwrap_or_default().trim_start_matches('/').to_string();
+    let running = info.state.as_ref().and_then(|s| s.running).unwrap_or(false);
+    let aside = format!("{}-replaced-{}", name, &id[..12.min(id.len())]);
+
+    if running {
+        log(format!("Stopping {}", name));
+        stop_container(client, &id).await?;
+    }
+    log(format!("Renaming {} to {}", name, aside));
+    rename_container(client, &id, &aside).await?;
+
+    let replaced = async {
+        log(format!("Creating {}", name));
+        let new_id = create_container(client, &name, config, endpoints_from_inspect(info)).await?;
+        if running {
+            log(format!("Starting {}", name));
+            start_container(client, &new_id).await?;
+        }
+        Ok::<_, crate::types::AppError>(new_id)
+    }.await;
+
+    match replaced {
+        Ok(new_id) => {
+            log(format!("Removing {}", aside));
+            remove_container(client, &id).await?;
+            log("Recreate complete".to_string());
+            Ok(new_id)
+        }
+        Err(e) => {
+            log(format!("Error: {}; restoring the original container", e));
+            // The name only refers to a half-created replacement at this point
+            let _ = remove_container(client, &name).await;
+            rename_container(client, &id, &name).await?;
+            if running {
+                start_container(client, &id).await?;
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Parses space-separated `docker run -p` values: `[ip:]host:container[/proto]`.
+pub fn parse_port_specs(specs: &str) -> std::result::Result<PortMap, String> {
+    let mut ports = PortMap::new();
+    for spec in specs.split_whitespace() {
+        let (mapping, proto) = spec.split_once('/').unwrap_or((spec, "tcp"));
+        let parts: Vec<&str> = mapping.split(':').collect();
+        let (ip, host, container) = match parts.as_slice() {
+            [host, container] => (None, *host, *container),
+            [ip, host, container] => (Some(ip.to_string()), *host, *container),
+            _ => return Err(format!("'{}' is not host:container", spec)),
+        };
+        if host.parse::<u16>().is_err() || container.parse::<u16>().is_err() || !matches!(proto, "tcp" | "udp" | "sctp") {
+            return Err(format!("'{}' is not host:container", spec));
+        }
+        let binding = PortBinding { host_ip: ip, host_port: Some(host.to_string()) };
+        ports.entry(format!("{}/{}", container, proto)).or_insert_with(|| Some(Vec::new()))
+            .get_or_insert_with(Vec::new)
+            .push(binding);
+    }
+    Ok(ports)
+}
+
+/// Creates a second instance of a container: the same image and config under
+/// `name`, publishing `ports` instead of the original's. Compose labels and
+/// network aliases are left off so the copy isn't mistaken for (or sent traffic
+/// meant for) the original.
+pub async fn duplicate_container(client: &DockerClient, info: &ContainerInspectResponse, name: &str, ports: PortMap, start: bool) -> Result<String> {
+    let mut config = config_from_inspect(info);
+    config.hostname = None;
+    if let Some(labels) = config.labels.as_mut() {
+        labels.retain(|key, _| !key.starts_with("com.docker.compose."));
+    }
+    let exposed = config.exposed_ports.get_or_insert_with(HashMap::new);
+    for port in ports.keys() {
+        exposed.entry(port.clone()).or_default();
+    }
+    if let Some(host) = config.host_config.as_mut() {
+        host.port_bindings = Some(ports);
+    }
+    let endpoints = endpoints_from_inspect(info).into_iter()
+        .map(|(network, _)| (network, EndpointSettings::default()))
+        .collect();
+
+    let id = create_container(client, name, config, endpoints).await?;
+    if start {
+        start_container(client, &id).await?;
+    }
+    Ok(id)
+}
@@ -0,0 +1,141 @@
+use crate::types::{DockerContext, Result, TlsConfig};
+use directories::BaseDirs;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Name the Docker CLI gives the implicit context built from `DOCKER_HOST`.
+pub const DEFAULT_CONTEXT: &str = "default";
+
+#[cfg(unix)]
+pub const DEFAULT_DOCKER_HOST: &str = "unix:///var/run/docker.sock";
+#[cfg(windows)]
+pub const DEFAULT_DOCKER_HOST: &str = "npipe:////./pipe/docker_engine";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextMeta {
+    name: String,
+    #[serde(default)]
+    metadata: ContextMetadata,
+    #[serde(default)]
+    endpoints: HashMap<String, ContextEndpoint>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase")]
+struct ContextMetadata {
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextEndpoint {
+    #[serde(default)]
+    host: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct CliConfig {
+    #[serde(default)]
+    current_context: Option<String>,
+}
+
+/// The Docker CLI config directory: `$DOCKER_CONFIG` or `~/.docker`.
+fn docker_config_dir() -> Option<PathBuf> {
+    match std::env::var_os("DOCKER_CONFIG") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => BaseDirs::new().map(|dirs| dirs.home_dir().join(".docker")),
+    }
+}
+
+/// Endpoint of the default context: `DOCKER_HOST`, else the Docker socket, or
+/// Podman's socket (rootless first) when only Podman is installed.
+pub fn default_host() -> String {
+    if let Ok(host) = std::env::var("DOCKER_HOST") {
+        return host;
+    }
+    #[cfg(unix)]
+    if !std::path::Path::new(DEFAULT_DOCKER_HOST.trim_start_matches("unix://")).exists() {
+        let rootless = std::env::var_os("XDG_RUNTIME_DIR").map(|dir| PathBuf::from(dir).join("podman/podman.sock"));
+        let rootful = Some(PathBuf::from("/run/podman/podman.sock"));
+        if let Some(socket) = [rootless, rootful].into_iter().flatten().find(|p| p.exists()) {
+            return format!("unix://{}", socket.display());
+        }
+    }
+    DEFAULT_DOCKER_HOST.to_string()
+}
+
+/// TLS settings of the default context: `DOCKER_TLS_VERIFY` with certificates
+/// from `DOCKER_CERT_PATH`, or the config directory.
+pub fn env_tls() -> Option<TlsConfig> {
+    std::env::var_os("DOCKER_TLS_VERIFY").filter(|v| !v.is_empty())?;
+    let dir = std::env::var_os("DOCKER_CERT_PATH").map(PathBuf::from).or_else(docker_config_dir)?;
+    Some(TlsConfig::from_dir(&dir))
+}
+
+/// The context the Docker CLI would use, following its precedence:
+/// `DOCKER_HOST` forces the default context, then `DOCKER_CONTEXT`, then `currentContext` in config.json.
+pub fn current_context_name() -> String {
+    if std::env::var_os("DOCKER_HOST").is_some() {
+        return DEFAULT_CONTEXT.to_string();
+    }
+    if let Ok(name) = std::env::var("DOCKER_CONTEXT") {
+        if !name.is_empty() {
+            return name;
+        }
+    }
+    docker_config_dir()
+        .and_then(|dir| std::fs::read_to_string(dir.join("config.json")).ok())
+        .and_then(|raw| serde_json::from_str::<CliConfig>(&raw).ok())
+        .and_then(|config| config.current_context)
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_CONTEXT.to_string())
+}
+
+/// Lists the default context followed by every context stored by `docker context create`,
+/// sorted by name, with the current one marked.
+pub fn list_contexts() -> Result<Vec<DockerContext>> {
+    let current = current_context_name();
+    let mut contexts = vec![DockerContext {
+        name: DEFAULT_CONTEXT.to_string(),
+        description: "Current DOCKER_HOST based configuration".to_string(),
+        host: default_host(),
+        tls: env_tls(),
+        current: current == DEFAULT_CONTEXT,
+    }];
+
+    let Some(root) = docker_config_dir().map(|dir| dir.join("contexts")) else {
+        return Ok(contexts);
+    };
+    let entries = match std::fs::read_dir(root.join("meta")) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(contexts),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut stored = Vec::new();
+    for entry in entries.flatten() {
+        // Directories are named by the digest of the context name; TLS material uses the same digest
+        let raw = match std::fs::read_to_string(entry.path().join("meta.json")) {
+            Ok(raw) => raw,
+            Err(_) => continue,
+        };
+        let Ok(meta) = serde_json::from_str::<ContextMeta>(&raw) else { continue };
+        let Some(endpoint) = meta.endpoints.get("docker") else { continue };
+        stored.push(DockerContext {
+            current: meta.name == current,
+            tls: Some(root.join("tls").join(entry.file_name()).join("docker"))
+                .filter(|dir| dir.is_dir())
+                .map(|dir| TlsConfig::from_dir(&dir)),
+            name: meta.name,
+            description: meta.metadata.description,
+            host: endpoint.host.clone(),
+        });
+    }
+    stored.sort_by(|a, b| a.name.cmp(&b.name));
+    contexts.extend(stored);
+    Ok(contexts)
+}
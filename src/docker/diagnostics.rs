@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+use crate::docker::client::DockerClient;
+use crate::types::DaemonCheck;
+
+/// Upper bound for each probe, so a dead endpoint doesn't freeze the screen
+/// for the full connection timeout.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Asks the daemon for its version, recording the round trip and which API
+/// versions both sides speak. Failures are part of the result, not an error.
+pub async fn check_daemon(docker: &DockerClient) -> DaemonCheck {
+    let mut check = DaemonCheck {
+        client_api: docker.inner.client_version().to_string(),
+        socket_access: socket_access(&docker.endpoint),
+        ..Default::default()
+    };
+    let started = Instant::now();
+    match tokio::time::timeout(PROBE_TIMEOUT, docker.inner.version()).await {
+        Ok(Ok(version)) => {
+            check.latency_ms = Some(started.elapsed().as_millis());
+            check.engine_name = version.platform.map(|p| p.name);
+            check.engine_version = version.version;
+            check.server_api = version.api_version;
+            check.min_api = version.min_api_version;
+            check.platform = match (version.os, version.arch) {
+                (Some(os), Some(arch)) => Some(format!("{}/{}", os, arch)),
+                _ => None,
+            };
+        }
+        Ok(Err(e)) => check.error = Some(e.to_string()),
+        Err(_) => check.error = Some(format!("no response within {}s", PROBE_TIMEOUT.as_secs())),
+    }
+    check
+}
+
+/// Opens a `unix://` endpoint directly so a permissions problem is reported as
+/// such instead of as a generic connection error. On Windows, checks that the
+/// `npipe://` endpoint exists.
+#[cfg(unix)]
+fn socket_access(endpoint: &str) -> Option<String> {
+    use std::io::ErrorKind;
+    let path = endpoint.strip_prefix("unix://")?;
+    Some(match std::os::unix::net::UnixStream::connect(path) {
+        Ok(_) => "ok".to_string(),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            format!("permission denied on {} (is your user in the docker group?)", path)
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => format!("{} does not exist", path),
+        Err(e) => format!("{}: {}", path, e),
+    })
+}
+
+#[cfg(windows)]
+fn socket_access(endpoint: &str) -> Option<String> {
+    let path = endpoint.strip_prefix("npipe://")?;
+    Some(if std::path::Path::new(path).exists() {
+        "ok".to_string()
+    } else {
+        format!("{} does not exist (is Docker Desktop running?)", path)
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+fn socket_access(_endpoint: &str) -> Option<String> {
+    None
+}
+
+/// Pings an extra host, returning a one-line status.
+pub async fn ping_host(docker: &DockerClient) -> String {
+    let started = Instant::now();
+    match tokio::time::timeout(PROBE_TIMEOUT, docker.ping()).await {
+        Ok(Ok(())) => format!("ok ({} ms)", started.elapsed().as_millis()),
+        Ok(Err(e)) => format!("unreachable ({})", e),
+        Err(_) => format!("no response within {}s", PROBE_TIMEOUT.as_secs()),
+    }
+}
@@ -0,0 +1,66 @@
+use crate::docker::client::DockerClient;
+use crate::types::{LifecycleEvent, Result};
+use bollard::models::EventMessage;
+use bollard::query_parameters::EventsOptions;
+use futures::StreamExt;
+use std::collections::HashMap;
+
+/// Container event actions that make up the lifecycle timeline.
+pub const LIFECYCLE_ACTIONS: [&str; 9] = ["create", "start", "restart", "stop", "kill", "die", "oom", "pause", "unpause"];
+
+fn lifecycle_filters() -> HashMap<String, Vec<String>> {
+    let mut filters = HashMap::new();
+    filters.insert("type".to_string(), vec!["container".to_string()]);
+    filters.insert("event".to_string(), LIFECYCLE_ACTIONS.iter().map(|a| a.to_string()).chain(["health_status".to_string()]).collect());
+    filters
+}
+
+/// Filters for a live subscription to every container's lifecycle events.
+pub fn lifecycle_events_options() -> EventsOptions {
+    EventsOptions {
+        filters: Some(lifecycle_filters()),
+        ..Default::default()
+    }
+}
+
+/// Converts a daemon event into a timeline entry, returning the container ID with it.
+pub fn lifecycle_event(event: EventMessage) -> Option<(String, LifecycleEvent)> {
+    let actor = event.actor?;
+    let id = actor.id?;
+    let attributes = actor.attributes.unwrap_or_default();
+    let action = event.action?;
+    let exit_code = attributes.get("exitCode").and_then(|c| c.parse().ok());
+    // Health events arrive as "health_status: healthy"
+    let (action, detail) = match action.split_once(": ") {
+        Some((action, status)) => (action.to_string(), status.to_string()),
+        None => {
+            let detail = match action.as_str() {
+                "die" => exit_code.map(|c| format!("exit code {}", c)).unwrap_or_default(),
+                "kill" => attributes.get("signal").map(|s| format!("signal {}", s)).unwrap_or_default(),
+                _ => String::new(),
+            };
+            (action, detail)
+        }
+    };
+    Some((id, LifecycleEvent { time: event.time.unwrap_or(0), time_nano: event.time_nano.unwrap_or(0), action, detail, exit_code }))
+}
+
+/// Lifecycle events for one container still held in the daemon's event backlog.
+pub async fn container_history(client: &DockerClient, container_id: &str, since: i64) -> Result<Vec<LifecycleEvent>> {
+    let mut filters = lifecycle_filters();
+    filters.insert("container".to_string(), vec![container_id.to_string()]);
+    let options = EventsOptions {
+        since: Some(since.to_string()),
+        until: Some(chrono::Utc::now().timestamp().to_string()),
+        filters: Some(filters),
+    };
+
+    let mut events = Vec::new();
+    let mut stream = client.inner.events(Some(options));
+    while let Some(event) = stream.next().await {
+        if let Some((_, entry)) = lifecycle_event(event?) {
+            events.push(entry);
+        }
+    }
+    Ok(events)
+}
@@ -1,32 +1,286 @@
 use crate::docker::client::DockerClient;
-use crate::types::{Result, AppError};
-use std::process::Command;
-
-pub async fn exec_interactive_shell(_client: &DockerClient, container_id: &str) -> Result<()> {
-    // We use std::process::Command to leverage the 'docker' CLI which handles PTY/signals correctly
-    // Try /bin/bash first
-    let status = Command::new("docker")
-        .arg("exec")
-        .arg("-it")
-        .arg(container_id)
-        .arg("/bin/bash")
-        .spawn()?
-        .wait()?;
-
-    if !status.success() {
-        // Fallback to /bin/sh
-        let status_sh = Command::new("docker")
-            .arg("exec")
-            .arg("-it")
-            .arg(container_id)
-            .arg("/bin/sh")
-            .spawn()?
-            .wait()?;
-        
-        if !status_sh.success() {
-             return Err(AppError::Other("Failed to start shell (bash or sh) in container".to_string()));
+use crate::audit::Transcript;
+use crate::types::{ExecProfile, Result, AppError};
+use bollard::exec::StartExecResults;
+use bollard::models::ExecConfig;
+use bollard::query_parameters::ResizeExecOptionsBuilder;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Container clock compared to the host, from running `date` inside it.
+#[derive(Debug, Clone)]
+pub struct ClockCheck {
+    /// Container time minus host time, in seconds (positive = container ahead).
+    pub skew_secs: i64,
+    /// UTC offset reported by the container, e.g. `+0000`.
+    pub utc_offset: String,
+}
+
+/// The clock check's command, as written to the audit log.
+pub const CLOCK_COMMAND: &str = "date '+%s %z'";
+
+/// Runs [`CLOCK_COMMAND`] in a running container and compares it against the
+/// host clock, using the midpoint of the exec round trip as host time.
+pub async fn check_container_clock(client: &DockerClient, container_id: &str) -> Result<ClockCheck> {
+    let config = ExecConfig {
+        cmd: Some(vec!["date".to_string(), "+%s %z".to_string()]),
+        attach_stdout: Some(true),
+        attach_stderr: Some(true),
+        ..Default::default()
+    };
+    let before = chrono::Utc::now().timestamp_millis();
+    let exec = client.inner.create_exec(container_id, config).await?;
+    let mut output = String::new();
+    if let StartExecResults::Attached { output: mut stream, .. } = client.inner.start_exec(&exec.id, None).await? {
+        while let Some(chunk) = stream.next().await {
+            output.push_str(&chunk?.to_string());
+        }
+    }
+    let after = chrono::Utc::now().timestamp_millis();
+
+    let mut parts = output.split_whitespace();
+    let container_secs: i64 = parts.next()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| AppError::Other(format!("Unexpected `date` output: {}", output.trim())))?;
+    let host_secs = (before + after) / 2 / 1000;
+    Ok(ClockCheck {
+        skew_secs: container_secs - host_secs,
+        utc_offset: parts.next().unwrap_or("?").to_string(),
+    })
+}
+
+/// The chooser's entry for bash with a fallback to sh, as written to the audit log.
+pub const DEFAULT_COMMAND: &str = "/bin/bash || /bin/sh";
+
+/// Shells offered for every container, after any matching profile's commands.
+const SHELLS: [&str; 3] = [DEFAULT_COMMAND, "/bin/sh", "/bin/ash"];
+
+/// Bash where the image has it, otherwise sh, decided inside the container.
+const DEFAULT_SHELL: &str = "if [ -x /bin/bash ]; then exec /bin/bash; else exec /bin/sh; fi";
+
+/// Commands the exec chooser offers: the shell and commands of each matching
+/// profile in config order, then the common shells, without repeats.
+pub fn exec_choices(profiles: &[&ExecProfile]) -> Vec<String> {
+    let mut choices: Vec<String> = Vec::new();
+    let presets = profiles.iter().flat_map(|p| p.shell.iter().chain(&p.commands)).map(String::as_str);
+    for command in presets.chain(SHELLS) {
+        if !choices.iter().any(|c| c == command) {
+            choices.push(command.to_string());
         }
     }
-    
+    choices
+}
+
+/// Splits a command line into arguments the way a shell would for plain
+/// words, single and double quotes and backslash escapes; nothing is expanded.
+pub fn split_command(command: &str) -> std::result::Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (None, '\\') | (Some('"'), '\\') => {
+                let next = chars.next().ok_or("Trailing backslash")?;
+                current.get_or_insert_with(String::new).push(next);
+            }
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (Some(q), c) if c == q => quote = None,
+            (_, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Exit codes for a command that couldn't be run: not executable, not found.
+const CANNOT_RUN: [i64; 2] = [126, 127];
+
+/// What the terminal sends the session while it runs.
+enum Input {
+    Bytes(Vec<u8>),
+    Resize(u16, u16),
+}
+
+/// Runs `command` interactively in the container over the exec API, as the
+/// profile's user and with its environment, bridging the terminal in raw mode
+/// to the container's TTY, and writes the session's output to `transcript`
+/// when one is given. Expects to be called outside the alternate screen.
+pub async fn exec_interactive_shell(client: &DockerClient, container_id: &str, command: &str, profile: Option<&ExecProfile>, mut transcript: Option<Transcript>) -> Result<()> {
+    let cmd = match command {
+        DEFAULT_COMMAND => vec!["/bin/sh".to_string(), "-c".to_string(), DEFAULT_SHELL.to_string()],
+        command => split_command(command).map_err(AppError::Other)?,
+    };
+    let (width, height) = crossterm::terminal::size()?;
+    let config = ExecConfig {
+        cmd: Some(cmd),
+        user: profile.and_then(|p| p.user.clone()),
+        env: profile.map(|p| p.env.clone()).filter(|env| !env.is_empty()),
+        attach_stdin: Some(true),
+        attach_stdout: Some(true),
+        attach_stderr: Some(true),
+        tty: Some(true),
+        console_size: Some(vec![height as usize, width as usize]),
+        ..Default::default()
+    };
+    let exec = client.inner.create_exec(container_id, config).await?;
+    let StartExecResults::Attached { mut output, mut input } = client.inner.start_exec(&exec.id, None).await? else {
+        return Err(AppError::Other("Exec session did not attach".to_string()));
+    };
+
+    let _raw = RawMode::enable()?;
+    // Older daemons ignore the console size given at create time
+    resize(client, &exec.id, width, height).await;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let reader = tokio::task::spawn_blocking({
+        let stop = stop.clone();
+        move || read_terminal(tx, &stop)
+    });
+
+    let mut stdout = tokio::io::stdout();
+    let session = async {
+        loop {
+            tokio::select! {
+                chunk = output.next() => {
+                    let Some(chunk) = chunk else { break };
+                    let bytes = chunk?.into_bytes();
+                    stdout.write_all(&bytes).await?;
+                    stdout.flush().await?;
+                    if let Some(transcript) = transcript.as_mut() {
+                        transcript.write(&bytes)?;
+                    }
+                }
+                Some(event) = rx.recv() => match event {
+                    Input::Bytes(bytes) => {
+                        // The shell may already be gone; its output stream ending says so
+                        if input.write_all(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                    Input::Resize(width, height) => resize(client, &exec.id, width, height).await,
+                },
+            }
+        }
+        Ok::<_, AppError>(())
+    };
+    let result = session.await;
+    stop.store(true, Ordering::Relaxed);
+    let _ = reader.await;
+    if let Some(transcript) = transcript {
+        transcript.finish()?;
+    }
+    result?;
+
+    let exit_code = client.inner.inspect_exec(&exec.id).await?.exit_code.unwrap_or_default();
+    if CANNOT_RUN.contains(&exit_code) {
+        return Err(AppError::Other(format!("Failed to run '{}' in container (exit code {})", command, exit_code)));
+    }
     Ok(())
 }
+
+async fn resize(client: &DockerClient, exec_id: &str, width: u16, height: u16) {
+    let options = ResizeExecOptionsBuilder::default().w(width as i32).h(height as i32).build();
+    if let Err(e) = client.inner.resize_exec(exec_id, options).await {
+        tracing::debug!("exec resize failed: {}", e);
+    }
+}
+
+/// Forwards keys, pastes and resizes until `stop` is set, polling so the
+/// thread notices in time and leaves no read pending for the UI's event loop.
+fn read_terminal(tx: tokio::sync::mpsc::UnboundedSender<Input>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match event::poll(Duration::from_millis(50)) {
+            Ok(false) => continue,
+            Ok(true) => {}
+            Err(_) => break,
+        }
+        let input = match event::read() {
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => Input::Bytes(key_bytes(key)),
+            Ok(Event::Paste(text)) => Input::Bytes(text.into_bytes()),
+            Ok(Event::Resize(width, height)) => Input::Resize(width, height),
+            Ok(_) => continue,
+            Err(_) => break,
+        };
+        if tx.send(input).is_err() {
+            break;
+        }
+    }
+}
+
+/// The bytes a terminal sends for `key`, as a VT100/xterm would.
+fn key_bytes(key: KeyEvent) -> Vec<u8> {
+    let mut bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c.to_ascii_lowercase() {
+            c @ 'a'..='z' => vec![c as u8 - b'a' + 1],
+            '@' | ' ' | '2' => vec![0],
+            '[' | '3' => vec![0x1b],
+            '\\' | '4' => vec![0x1c],
+            ']' | '5' => vec![0x1d],
+            '^' | '6' => vec![0x1e],
+            '_' | '7' | '/' => vec![0x1f],
+            c => c.to_string().into_bytes(),
+        },
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n @ 1..=4) => vec![0x1b, b'O', b'P' + n - 1],
+        KeyCode::F(n) => match n {
+            5 => b"\x1b[15~".to_vec(),
+            6 => b"\x1b[17~".to_vec(),
+            7 => b"\x1b[18~".to_vec(),
+            8 => b"\x1b[19~".to_vec(),
+            9 => b"\x1b[20~".to_vec(),
+            10 => b"\x1b[21~".to_vec(),
+            11 => b"\x1b[23~".to_vec(),
+            12 => b"\x1b[24~".to_vec(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    if key.modifiers.contains(KeyModifiers::ALT) && !bytes.is_empty() {
+        bytes.insert(0, 0x1b);
+    }
+    bytes
+}
+
+/// Raw mode for the length of a session, restored however it ends.
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> Result<Self> {
+        crossterm::terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
@@ -0,0 +1,363 @@
+use crate::docker::client::DockerClient;
+use crate::docker::containers::{inspect_container, list_containers};
+use crate::docker::images::inspect_image;
+use crate::types::Result;
+use bollard::models::{ContainerInspectResponse, HostConfig, ImageInspect, MountTypeEnum, RestartPolicyNameEnum};
+use std::path::{Path, PathBuf};
+
+/// Quotes a value for a POSIX shell, leaving simple words untouched.
+pub fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Published ports as `docker run -p` values (`[ip:]host:container[/proto]`).
+pub fn port_specs(host: &HostConfig) -> Vec<String> {
+    let mut ports: Vec<String> = host.port_bindings.clone().unwrap_or_default().into_iter()
+        .flat_map(|(container_port, bindings)| {
+            let container_port = container_port.trim_end_matches("/tcp").to_string();
+            bindings.unwrap_or_default().into_iter().map(move |b| {
+                let host_port = b.host_port.unwrap_or_default();
+                match b.host_ip.as_deref() {
+                    Some(ip) if !ip.is_empty() && ip != "0.0.0.0" && ip != "::" => format!("{}:{}:{}", ip, host_port, container_port),
+                    _ => format!("{}:{}", host_port, container_port),
+                }
+            })
+        })
+        .collect();
+    ports.sort();
+    ports.dedup();
+    ports
+}
+
+/// Builds a `docker run` command equivalent to an existing container. Settings
+/// inherited from the image (env, labels, cmd, ...) are left out when `image`
+/// is known so the command stays readable.
+pub fn run_command(info: &ContainerInspectResponse, image: Option<&ImageInspect>) -> String {
+    let config = info.config.clone().unwrap_or_default();
+    let host = info.host_config.clone().unwrap_or_default();
+    let image_config = image.and_then(|i| i.config.clone()).unwrap_or_default();
+    let mut args: Vec<String> = vec!["docker".into(), "run".into(), "-d".into()];
+
+    if let Some(name) = info.name.as_deref() {
+        args.push(format!("--name {}", shell_quote(name.trim_start_matches('/'))));
+    }
+
+    let id = info.id.as_deref().unwrap_or_default();
+    if let Some(hostname) = config.hostname.as_deref().filter(|h| !id.starts_with(*h)) {
+        args.push(format!("--hostname {}", shell_quote(hostname)));
+    }
+    if let Some(user) = config.user.as_deref().filter(|u| !u.is_empty() && image_config.user.as_deref() != Some(*u)) {
+        args.push(format!("--user {}", shell_quote(user)));
+    }
+    if let Some(dir) = config.working_dir.as_deref().filter(|d| !d.is_empty() && image_config.working_dir.as_deref() != Some(*d)) {
+        args.push(format!("--workdir {}", shell_quote(dir)));
+    }
+
+    let image_env = image_config.env.unwrap_or_default();
+    for env in config.env.unwrap_or_default().iter().filter(|e| !image_env.contains(e)) {
+        args.push(format!("-e {}", shell_quote(env)));
+    }
+
+    let image_labels = image_config.labels.unwrap_or_default();
+    let mut labels: Vec<_> = config.labels.unwrap_or_default().into_iter()
+        .filter(|(k, v)| image_labels.get(k) != Some(v))
+        .collect();
+    labels.sort();
+    for (key, value) in labels {
+        args.push(format!("--label {}", shell_quote(&format!("{}={}", key, value))));
+    }
+
+    for port in port_specs(&host) {
+        args.push(format!("-p {}", shell_quote(&port)));
+    }
+
+    for bind in host.binds.unwrap_or_default() {
+        args.push(format!("-v {}", shell_quote(&bind)));
+    }
+    for mount in host.mounts.unwrap_or_default() {
+        let kind = match mount.typ {
+            Some(MountTypeEnum::BIND) => "bind",
+            Some(MountTypeEnum::TMPFS) => "tmpfs",
+            _ => "volume",
+        };
+        let mut spec = format!("type={}", kind);
+        if let Some(source) = mount.source.filter(|s| !s.is_empty()) {
+            spec.push_str(&format!(",source={}", source));
+        }
+        spec.push_str(&format!(",target={}", mount.target.unwrap_or_default()));
+        if mount.read_only == Some(true) {
+            spec.push_str(",readonly");
+        }
+        args.push(format!("--mount {}", shell_quote(&spec)));
+    }
+
+    if let Some(mode) = host.network_mode.as_deref().filter(|m| !m.is_empty() && *m != "default" && *m != "bridge") {
+        args.push(format!("--network {}", shell_quote(mode)));
+    }
+
+    if let Some(policy) = host.restart_policy {
+        match policy.name {
+            Some(RestartPolicyNameEnum::ON_FAILURE) => match policy.maximum_retry_count {
+                Some(n) if n > 0 => args.push(format!("--restart on-failure:{}", n)),
+                _ => args.push("--restart on-failure".to_string()),
+            },
+            Some(RestartPolicyNameEnum::ALWAYS) => args.push("--restart always".to_string()),
+            Some(RestartPolicyNameEnum::UNLESS_STOPPED) => args.push("--restart unless-stopped".to_string()),
+            _ => {}
+        }
+    }
+
+    if host.privileged == Some(true) {
+        args.push("--privileged".to_string());
+    }
+    for cap in host.cap_add.unwrap_or_default() {
+        args.push(format!("--cap-add {}", shell_quote(&cap)));
+    }
+    for cap in host.cap_drop.unwrap_or_default() {
+        args.push(format!("--cap-drop {}", shell_quote(&cap)));
+    }
+    if let Some(memory) = host.memory.filter(|m| *m > 0) {
+        args.push(format!("--memory {}", memory));
+    }
+    if let Some(nano_cpus) = host.nano_cpus.filter(|n| *n > 0) {
+        args.push(format!("--cpus {}", nano_cpus as f64 / 1e9));
+    }
+
+    if let Some(entrypoint) = config.entrypoint.as_ref().filter(|e| image_config.entrypoint.as_ref() != Some(*e)) {
+        // --entrypoint only takes the executable; extra entrypoint args go before cmd
+        if let Some((first, _)) = entrypoint.split_first() {
+            args.push(format!("--entrypoint {}", shell_quote(first)));
+        }
+    }
+
+    args.push(shell_quote(config.image.as_deref().unwrap_or_default()));
+
+    let entrypoint_changed = config.entrypoint.is_some() && config.entrypoint != image_config.entrypoint;
+    if entrypoint_changed {
+        args.extend(config.entrypoint.iter().flatten().skip(1).map(|a| shell_quote(a)));
+    }
+    if entrypoint_changed || config.cmd != image_config.cmd {
+        args.extend(config.cmd.iter().flatten().map(|a| shell_quote(a)));
+    }
+
+    args.join(" \\\n  ")
+}
+
+/// Files in a snapshot besides the per-container run scripts.
+pub const SNAPSHOT_SHARED_FILES: usize = 2;
+
+/// Quotes a YAML scalar unless it is a plain word YAML wouldn't read as
+/// something else (a number, bool or null).
+fn yaml_str(value: &str) -> String {
+    let plain = value.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '/')
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_./@".contains(c))
+        && !matches!(value.to_lowercase().as_str(), "true" | "false" | "yes" | "no" | "on" | "off" | "null");
+    if plain {
+        value.to_string()
+    } else {
+        serde_json::to_string(value).unwrap_or_default()
+    }
+}
+
+fn yaml_list(values: &[String]) -> String {
+    format!("[{}]", values.iter().map(|v| yaml_str(v)).collect::<Vec<_>>().join(", "))
+}
+
+/// Builds a `docker-compose.yml` with a service per container, equivalent to
+/// `run_command` for each. Networks and named volumes the containers use are
+/// declared `external`, since they already exist. Settings inherited from the
+/// image are left out when its inspect data is given.
+pub fn compose_file(containers: &[(ContainerInspectResponse, Option<ImageInspect>)]) -> String {
+    let mut services = String::from("services:\n");
+    let mut networks: Vec<String> = Vec::new();
+    let mut volumes: Vec<String> = Vec::new();
+
+    for (info, image) in containers {
+        let config = info.config.clone().unwrap_or_default();
+        let host = info.host_config.clone().unwrap_or_default();
+        let image_config = image.as_ref().and_then(|i| i.config.clone()).unwrap_or_default();
+        let name = info.name.as_deref().unwrap_or_default().trim_start_matches('/');
+        let mut lines: Vec<String> = Vec::new();
+        let mut push = |line: String| lines.push(format!("    {}", line));
+
+        push(format!("image: {}", yaml_str(config.image.as_deref().unwrap_or_default())));
+        push(format!("container_name: {}", yaml_str(name)));
+        let id = info.id.as_deref().unwrap_or_default();
+        if let Some(hostname) = config.hostname.as_deref().filter(|h| !id.starts_with(*h)) {
+            push(format!("hostname: {}", yaml_str(hostname)));
+        }
+        if let Some(user) = config.user.as_deref().filter(|u| !u.is_empty() && image_config.user.as_deref() != Some(*u)) {
+            push(format!("user: {}", yaml_str(user)));
+        }
+        if let Some(dir) = config.working_dir.as_deref().filter(|d| !d.is_empty() && image_config.working_dir.as_deref() != Some(*d)) {
+            push(format!("working_dir: {}", yaml_str(dir)));
+        }
+        let entrypoint_changed = config.entrypoint.is_some() && config.entrypoint != image_config.entrypoint;
+        if let Some(entrypoint) = config.entrypoint.as_ref().filter(|_| entrypoint_changed) {
+            push(format!("entrypoint: {}", yaml_list(entrypoint)));
+        }
+        if entrypoint_changed || config.cmd != image_config.cmd {
+            if let Some(cmd) = config.cmd.as_ref() {
+                push(format!("command: {}", yaml_list(cmd)));
+            }
+        }
+
+        let image_env = image_config.env.unwrap_or_default();
+        let env: Vec<String> = config.env.unwrap_or_default().into_iter().filter(|e| !image_env.contains(e)).collect();
+        if !env.is_empty() {
+            push("environment:".to_string());
+            for e in env {
+                push(format!("  - {}", yaml_str(&e)));
+            }
+        }
+
+        // Labels compose sets itself would tie the service to the old project
+        let image_labels = image_config.labels.unwrap_or_default();
+        let mut labels: Vec<_> = config.labels.unwrap_or_default().into_iter()
+            .filter(|(k, v)| image_labels.get(k) != Some(v) && !k.starts_with("com.docker.compose."))
+            .collect();
+        labels.sort();
+        if !labels.is_empty() {
+            push("labels:".to_string());
+            for (key, value) in labels {
+                push(format!("  {}: {}", yaml_str(&key), yaml_str(&value)));
+            }
+        }
+
+        let ports = port_specs(&host);
+        if !ports.is_empty() {
+            push("ports:".to_string());
+            for port in ports {
+                // Always quoted: unquoted `80:80` style values are read as base-60 numbers
+                push(format!("  - {}", serde_json::to_string(&port).unwrap_or_default()));
+            }
+        }
+
+        let binds = host.binds.unwrap_or_default();
+        let mounts = host.mounts.unwrap_or_default();
+        if !binds.is_empty() || !mounts.is_empty() {
+            push("volumes:".to_string());
+            for bind in binds {
+                if let Some((source, _)) = bind.split_once(':').filter(|(s, _)| !s.starts_with('/')) {
+                    volumes.push(source.to_string());
+                }
+                push(format!("  - {}", yaml_str(&bind)));
+            }
+            for mount in mounts {
+                let kind = match mount.typ {
+                    Some(MountTypeEnum::BIND) => "bind",
+                    Some(MountTypeEnum::TMPFS) => "tmpfs",
+                    _ => "volume",
+                };
+                push(format!("  - type: {}", kind));
+                if let Some(source) = mount.source.filter(|s| !s.is_empty()) {
+                    if kind == "volume" {
+                        volumes.push(source.clone());
+                    }
+                    push(format!("    source: {}", yaml_str(&source)));
+                }
+                push(format!("    target: {}", yaml_str(&mount.target.unwrap_or_default())));
+                if mount.read_only == Some(true) {
+                    push("    read_only: true".to_string());
+                }
+            }
+        }
+
+        match host.network_mode.as_deref().filter(|m| !m.is_empty() && *m != "default" && *m != "bridge") {
+            Some(mode) if mode == "host" || mode == "none" || mode.starts_with("container:") => {
+                push(format!("network_mode: {}", yaml_str(mode)));
+            }
+            Some(network) => {
+                push(format!("networks: {}", yaml_list(&[network.to_string()])));
+                networks.push(network.to_string());
+            }
+            None => {}
+        }
+
+        if let Some(policy) = host.restart_policy {
+            let restart = match policy.name {
+                Some(RestartPolicyNameEnum::ON_FAILURE) => match policy.maximum_retry_count {
+                    Some(n) if n > 0 => Some(format!("on-failure:{}", n)),
+                    _ => Some("on-failure".to_string()),
+                },
+                Some(RestartPolicyNameEnum::ALWAYS) => Some("always".to_string()),
+                Some(RestartPolicyNameEnum::UNLESS_STOPPED) => Some("unless-stopped".to_string()),
+                _ => None,
+            };
+            if let Some(restart) = restart {
+                push(format!("restart: {}", yaml_str(&restart)));
+            }
+        }
+
+        if host.privileged == Some(true) {
+            push("privileged: true".to_string());
+        }
+        if let Some(caps) = host.cap_add.filter(|c| !c.is_empty()) {
+            push(format!("cap_add: {}", yaml_list(&caps)));
+        }
+        if let Some(caps) = host.cap_drop.filter(|c| !c.is_empty()) {
+            push(format!("cap_drop: {}", yaml_list(&caps)));
+        }
+        if let Some(memory) = host.memory.filter(|m| *m > 0) {
+            push(format!("mem_limit: {}", memory));
+        }
+        if let Some(nano_cpus) = host.nano_cpus.filter(|n| *n > 0) {
+            push(format!("cpus: {}", nano_cpus as f64 / 1e9));
+        }
+
+        let service: String = name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || "-_.".contains(c) { c } else { '_' })
+            .collect();
+        services.push_str(&format!("  {}:\n{}\n", yaml_str(&service), lines.join("\n")));
+    }
+
+    let mut file = services;
+    for (section, mut names) in [("networks", networks), ("volumes", volumes)] {
+        names.sort();
+        names.dedup();
+        if !names.is_empty() {
+            file.push_str(&format!("\n{}:\n", section));
+            for name in names {
+                file.push_str(&format!("  {}:\n    external: true\n", yaml_str(&name)));
+            }
+        }
+    }
+    file
+}
+
+/// Writes a `<name>.sh` run script per running container plus a `run-all.sh`
+/// and a `compose.yml` with all of them into `dir`, as a snapshot of what was
+/// running on this host.
+pub async fn export_running_containers(client: &DockerClient, dir: &Path) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut written = Vec::new();
+    let mut all = String::from("#!/bin/sh\n# Containers running when the snapshot was taken\nset -e\n\n");
+    let mut inspected = Vec::new();
+    for container in list_containers(client, false).await? {
+        let info = inspect_container(client, &container.id).await?;
+        let image_id = info.image.clone().unwrap_or_default();
+        let image = inspect_image(client, &image_id).await.ok();
+        let command = run_command(&info, image.as_ref());
+
+        let path = dir.join(format!("{}.sh", container.name));
+        std::fs::write(&path, format!("#!/bin/sh\n# Image: {}\n{}\n", container.image, command))?;
+        all.push_str(&format!("# {}\n{}\n\n", container.name, command));
+        written.push(path);
+        inspected.push((info, image));
+    }
+
+    let path = dir.join("run-all.sh");
+    std::fs::write(&path, all)?;
+    written.push(path);
+    let path = dir.join("compose.yml");
+    std::fs::write(&path, compose_file(&inspected))?;
+    written.push(path);
+    Ok(written)
+}
@@ -0,0 +1,355 @@
+//! Container file browsing. Directories are listed with `ls` through exec,
+//! falling back to reading the headers of a `docker cp`-style archive when the
+//! container is stopped or has no `ls`. Downloads and uploads always use the
+//! archive API.
+
+use crate::audit::Transcript;
+use crate::docker::client::DockerClient;
+use crate::docker::export::shell_quote;
+use crate::types::{AppError, FileEntry, Result};
+use bollard::container::LogOutput;
+use bollard::exec::StartExecResults;
+use bollard::models::ExecConfig;
+use bollard::query_parameters::{DownloadFromContainerOptions, UploadToContainerOptions};
+use futures::StreamExt;
+use hyper::body::Bytes;
+use std::io::BufWriter;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+/// Exit codes for a command that couldn't be run (`sh` convention).
+const COMMAND_NOT_RUN: [i64; 2] = [126, 127];
+
+/// The `ls` a listing runs, as it goes in the exec audit log.
+pub fn list_command(path: &str) -> String {
+    format!("ls -lA {}", shell_quote(path))
+}
+
+/// Entries of the directory at `path`, directories first, then by name. The
+/// `ls` output goes to `transcript`.
+pub async fn list_directory(client: &DockerClient, id: &str, path: &str, transcript: Option<Transcript>) -> Result<Vec<FileEntry>> {
+    let mut entries = match list_with_ls(client, id, path, transcript).await? {
+        Some(entries) => entries,
+        None => list_with_archive(client, id, path).await?,
+    };
+    entries.sort_by(|a, b| b.is_dir().cmp(&a.is_dir()).then_with(|| a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// None when `ls` can't be run at all, so the archive has to be used instead.
+async fn list_with_ls(client: &DockerClient, id: &str, path: &str, transcript: Option<Transcript>) -> Result<Option<Vec<FileEntry>>> {
+    let config = ExecConfig {
+        cmd: Some(vec!["ls".to_string(), "-lA".to_string(), path.to_string()]),
+        env: Some(vec!["LC_ALL=C".to_string()]),
+        attach_stdout: Some(true),
+        attach_stderr: Some(true),
+        ..Default::default()
+    };
+    // Fails for stopped containers
+    let Ok(exec) = client.inner.create_exec(id, config).await else { return Ok(None) };
+    let (mut stdout, mut stderr) = (String::new(), String::new());
+    match client.inner.start_exec(&exec.id, None).await {
+        Ok(StartExecResults::Attached { output: mut stream, .. }) => {
+            while let Some(chunk) = stream.next().await {
+                match chunk? {
+                    LogOutput::StdErr { message } => stderr.push_str(&String::from_utf8_lossy(&message)),
+                    other => stdout.push_str(&other.to_string()),
+                }
+            }
+        }
+        _ => return Ok(None),
+    }
+    if let Some(mut transcript) = transcript {
+        transcript.write(stdout.as_bytes())?;
+        transcript.write(stderr.as_bytes())?;
+        transcript.finish()?;
+    }
+    let exit_code = client.inner.inspect_exec(&exec.id).await?.exit_code.unwrap_or(0);
+    if COMMAND_NOT_RUN.contains(&exit_code) || stderr.contains("executable file not found") {
+        return Ok(None);
+    }
+    if exit_code != 0 && stdout.trim().is_empty() {
+        return Err(AppError::Other(stderr.trim().to_string()));
+    }
+    Ok(Some(stdout.lines().filter_map(parse_ls_line).collect()))
+}
+
+/// Parses a line of `ls -l` (GNU or busybox); `total` lines give None.
+fn parse_ls_line(line: &str) -> Option<FileEntry> {
+    // mode, links, owner, group, size, month, day, time/year, then the name
+    let mut rest = line.trim_start();
+    let mut fields = Vec::with_capacity(8);
+    while fields.len() < 8 {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+        // Device files show `major, minor` where the size would be
+        if fields.len() == 5 && fields[4].ends_with(',') {
+            fields.pop();
+            let end = rest.find(char::is_whitespace)?;
+            rest = rest[end..].trim_start();
+            fields.push("0");
+        }
+    }
+    let mode = fields[0];
+    if mode.len() < 10 || rest.is_empty() {
+        return None;
+    }
+    let (name, link_target) = match rest.split_once(" -> ") {
+        Some((name, target)) if mode.starts_with('l') => (name, Some(target.to_string())),
+        _ => (rest, None),
+    };
+    Some(FileEntry {
+        name: name.to_string(),
+        mode: mode.chars().take(10).collect(),
+        size: fields[4].parse().unwrap_or(0),
+        modified: fields[5..8].join(" "),
+        link_target,
+    })
+}
+
+/// Lists a directory from the tar the archive API returns for it. This
+/// transfers the whole tree below `path`, so it is only the fallback.
+async fn list_with_archive(client: &DockerClient, id: &str, path: &str) -> Result<Vec<FileEntry>> {
+    let options = DownloadFromContainerOptions { path: path.to_string() };
+    let mut stream = client.inner.download_from_container(id, Some(options));
+    let mut reader = TarHeaders::default();
+    let mut entries = Vec::new();
+    let mut root: Option<String> = None;
+    while let Some(chunk) = stream.next().await {
+        for header in reader.feed(&chunk?) {
+            // The first entry is the directory itself; its children follow
+            let Some(root) = &root else {
+                root = Some(header.path.trim_end_matches('/').to_string() + "/");
+                continue;
+            };
+            let Some(name) = header.path.strip_prefix(root.as_str()) else { continue };
+            let name = name.trim_end_matches('/');
+            if !name.is_empty() && !name.contains('/') {
+                entries.push(FileEntry {
+                    name: name.to_string(),
+                    mode: mode_string(header.kind, header.mode),
+                    size: header.size,
+                    modified: chrono::DateTime::from_timestamp(header.mtime, 0)
+                        .map(|t| t.format("%b %e %H:%M").to_string())
+                        .unwrap_or_default(),
+                    link_target: header.link_target,
+                });
+            }
+        }
+    }
+    Ok(entries)
+}
+
+struct TarHeader {
+    path: String,
+    kind: u8,
+    mode: u32,
+    size: u64,
+    mtime: i64,
+    link_target: Option<String>,
+}
+
+/// Incremental tar reader that yields headers and skips file contents.
+#[derive(Default)]
+struct TarHeaders {
+    buffer: Vec<u8>,
+    /// Content bytes (with padding) still to skip before the next header.
+    skip: u64,
+    /// Path from a preceding PAX or GNU long-name entry.
+    long_name: Option<String>,
+    /// Collecting the content of a long-name entry: (kind, bytes left).
+    collecting: Option<(u8, u64)>,
+    content: Vec<u8>,
+}
+
+impl TarHeaders {
+    fn feed(&mut self, mut data: &[u8]) -> Vec<TarHeader> {
+        let mut headers = Vec::new();
+        loop {
+            if let Some((kind, left)) = self.collecting {
+                let take = (left as usize).min(data.len());
+                self.content.extend_from_slice(&data[..take]);
+                data = &data[take..];
+                if take as u64 == left {
+                    self.collecting = None;
+                    self.long_name = long_name(kind, &self.content);
+                    self.content.clear();
+                } else {
+                    self.collecting = Some((kind, left - take as u64));
+                    return headers;
+                }
+            }
+            if self.skip > 0 {
+                let take = (self.skip as usize).min(data.len());
+                data = &data[take..];
+                self.skip -= take as u64;
+                if self.skip > 0 {
+                    return headers;
+                }
+            }
+            let need = 512 - self.buffer.len();
+            let take = need.min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() < 512 {
+                return headers;
+            }
+            let block = std::mem::take(&mut self.buffer);
+            if block.iter().all(|&b| b == 0) {
+                continue;
+            }
+            let size = octal(&block[124..136]);
+            let padded = size.div_ceil(512) * 512;
+            let kind = block[156];
+            if matches!(kind, b'x' | b'L') {
+                // Content is the next entry's path; the padding is skipped after
+                self.collecting = Some((kind, size));
+                self.skip = padded - size;
+                continue;
+            }
+            self.skip = padded;
+            if matches!(kind, b'g' | b'K') {
+                continue;
+            }
+            let mut path = text(&block[0..100]);
+            let prefix = text(&block[345..500]);
+            if !prefix.is_empty() {
+                path = format!("{}/{}", prefix, path);
+            }
+            let link = text(&block[157..257]);
+            headers.push(TarHeader {
+                path: self.long_name.take().unwrap_or(path),
+                kind,
+                mode: octal(&block[100..108]) as u32,
+                size,
+                mtime: octal(&block[136..148]) as i64,
+                link_target: (!link.is_empty()).then_some(link),
+            });
+        }
+    }
+}
+
+fn text(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+fn octal(field: &[u8]) -> u64 {
+    u64::from_str_radix(text(field).trim(), 8).unwrap_or(0)
+}
+
+/// Path from a GNU long-name entry, or the `path` record of a PAX header.
+fn long_name(kind: u8, content: &[u8]) -> Option<String> {
+    let content = String::from_utf8_lossy(content);
+    if kind == b'L' {
+        return Some(content.trim_end_matches('\0').to_string());
+    }
+    // Records are "<length> <key>=<value>\n"
+    content.lines()
+        .filter_map(|record| record.split_once(' ')?.1.split_once('='))
+        .find(|(key, _)| *key == "path")
+        .map(|(_, value)| value.to_string())
+}
+
+/// `ls`-style mode string from a tar type flag and permission bits.
+fn mode_string(kind: u8, mode: u32) -> String {
+    let mut s = String::with_capacity(10);
+    s.push(match kind {
+        b'5' => 'd',
+        b'2' => 'l',
+        b'3' => 'c',
+        b'4' => 'b',
+        b'6' => 'p',
+        _ => '-',
+    });
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 7;
+        s.push(if bits & 4 != 0 { 'r' } else { '-' });
+        s.push(if bits & 2 != 0 { 'w' } else { '-' });
+        s.push(if bits & 1 != 0 { 'x' } else { '-' });
+    }
+    s
+}
+
+/// Downloads `path` (a file or a whole directory) from the container to
+/// `dest` as a tar archive. A partial file is removed on failure.
+pub async fn download_path(client: &DockerClient, id: &str, path: &str, dest: &Path) -> Result<u64> {
+    let mut file = tokio::fs::File::create(dest).await?;
+    let result = async {
+        let options = DownloadFromContainerOptions { path: path.to_string() };
+        let mut stream = client.inner.download_from_container(id, Some(options));
+        let mut written = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(written)
+    }.await;
+    if result.is_err() {
+        drop(file);
+        let _ = tokio::fs::remove_file(dest).await;
+    }
+    result
+}
+
+/// Uploads `source` (a local file or directory) into the container directory
+/// `dest`, replacing entries of the same name. Returns the archive size.
+pub async fn upload_path(client: &DockerClient, id: &str, source: &Path, dest: &str) -> Result<u64> {
+    let name = source.file_name()
+        .ok_or_else(|| AppError::Other(format!("{} has no file name", source.display())))?
+        .to_owned();
+    let source = source.to_path_buf();
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Bytes>(8);
+    // The tar builder does blocking file IO, so it feeds the upload from its own thread
+    let archive = tokio::task::spawn_blocking(move || -> std::io::Result<u64> {
+        let writer = ChannelWriter { tx, written: 0 };
+        let mut builder = tar::Builder::new(BufWriter::with_capacity(UPLOAD_CHUNK, writer));
+        builder.follow_symlinks(false);
+        if source.is_dir() {
+            builder.append_dir_all(&name, &source)?;
+        } else {
+            builder.append_path_with_name(&source, &name)?;
+        }
+        let writer = builder.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+        Ok(writer.written)
+    });
+
+    let stream = futures::stream::poll_fn(move |cx| rx.poll_recv(cx));
+    let options = UploadToContainerOptions {
+        path: dest.to_string(),
+        no_overwrite_dir_non_dir: Some("true".to_string()),
+        ..Default::default()
+    };
+    let uploaded = client.inner.upload_to_container(id, Some(options), bollard::body_stream(stream)).await;
+    let archived = archive.await.map_err(|e| AppError::Other(e.to_string()))?;
+    match (archived, uploaded) {
+        // A broken pipe only means the daemon gave up first, and its error says why
+        (Err(e), _) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        (_, Err(e)) => Err(e.into()),
+        (archived, Ok(())) => Ok(archived?),
+    }
+}
+
+const UPLOAD_CHUNK: usize = 64 * 1024;
+
+/// Sends whatever the tar builder writes to the upload request.
+struct ChannelWriter {
+    tx: tokio::sync::mpsc::Sender<Bytes>,
+    written: u64,
+}
+
+impl std::io::Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.tx.blocking_send(Bytes::copy_from_slice(buf))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "The daemon stopped reading the upload"))?;
+        self.written += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
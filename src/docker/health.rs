@@ -48,7 +48,7 @@ fn parse_health_info(inspect: ContainerInspectResponse) -> Result<ContainerHealt
                     if let Ok(ts) = DateTime::parse_from_rfc3339(start) {
                         check_history.push_front(HealthCheckResult {
                             timestamp: ts.with_timezone(&Utc),
-                            exit_code: exit.clone(),
+                            exit_code: exit,
                             output: out.chars().take(200).collect(),
                         });
                     }
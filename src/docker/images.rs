@@ -1,11 +1,14 @@
 use crate::docker::client::DockerClient;
-use crate::types::{ImageInfo, Result, AppError};
-use bollard::image::{ListImagesOptions, CreateImageOptions, RemoveImageOptions, PruneImagesOptions};
-use bollard::models::ImageInspect;
+use crate::docker::containers::list_containers;
+use crate::types::{ImageInfo, ImageUsage, ProxyConfig, RegistryInfo, Result, AppError};
+use bollard::query_parameters::{EventsOptions, ListImagesOptions, CreateImageOptions, RemoveImageOptions, PruneImagesOptions, CommitContainerOptions};
+use bollard::models::{ContainerConfig, ImageInspect};
 use futures::stream::BoxStream;
 use futures::StreamExt;
 use futures::TryStreamExt;
-use std::collections::HashMap;
+use hyper::body::Bytes;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 pub async fn list_images(client: &DockerClient, show_dangling: bool) -> Result<Vec<ImageInfo>> {
     let mut filters = HashMap::new();
@@ -14,7 +17,7 @@ pub async fn list_images(client: &DockerClient, show_dangling: bool) -> Result<V
     }
     
     let options = ListImagesOptions {
-        filters,
+        filters: Some(filters),
         ..Default::default()
     };
 
@@ -39,6 +42,96 @@ pub async fn list_images(client: &DockerClient, show_dangling: bool) -> Result<V
     Ok(infos)
 }
 
+/// Normalizes an image reference as found in container events so it matches
+/// `ImageInfo::id` or one of its repo tags.
+fn usage_key(image: &str) -> String {
+    if let Some(digest) = image.strip_prefix("sha256:") {
+        return digest.chars().take(12).collect();
+    }
+    let name = image.rsplit('/').next().unwrap_or(image);
+    if name.contains(':') || name.contains('@') {
+        image.to_string()
+    } else {
+        format!("{}:latest", image)
+    }
+}
+
+/// Counts containers created from each image over the last `days` days and
+/// records when one was last started. Containers that still exist are counted
+/// too, since the daemon only keeps a limited event backlog.
+pub async fn image_usage(client: &DockerClient, days: i64) -> Result<HashMap<String, ImageUsage>> {
+    let now = chrono::Utc::now().timestamp();
+    let mut filters = HashMap::new();
+    filters.insert("type".to_string(), vec!["container".to_string()]);
+    filters.insert("event".to_string(), vec!["create".to_string(), "start".to_string()]);
+    let options = EventsOptions {
+        since: Some((now - days * 86_400).to_string()),
+        until: Some(now.to_string()),
+        filters: Some(filters),
+    };
+
+    let mut created: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut last_run: HashMap<String, i64> = HashMap::new();
+    let mut events = client.inner.events(Some(options));
+    while let Some(event) = events.next().await {
+        let event = event?;
+        let Some(actor) = event.actor else { continue };
+        let Some(image) = actor.attributes.as_ref().and_then(|a| a.get("image")) else { continue };
+        let key = usage_key(image);
+        match event.action.as_deref() {
+            Some("create") => {
+                created.entry(key).or_default().insert(actor.id.unwrap_or_default());
+            }
+            Some("start") => {
+                let time = event.time.unwrap_or(now);
+                let entry = last_run.entry(key).or_insert(time);
+                *entry = (*entry).max(time);
+            }
+            _ => {}
+        }
+    }
+
+    for container in list_containers(client, true).await? {
+        let key = usage_key(&container.image);
+        created.entry(key.clone()).or_default().insert(container.id);
+        if container.state == "running" {
+            last_run.insert(key, now);
+        }
+    }
+
+    let mut usage: HashMap<String, ImageUsage> = HashMap::new();
+    for (key, ids) in created {
+        usage.entry(key).or_default().containers = ids.len();
+    }
+    for (key, time) in last_run {
+        usage.entry(key).or_default().last_run = Some(time);
+    }
+    Ok(usage)
+}
+
+/// Reads registry mirrors, index configs and proxies from daemon info.
+pub async fn registry_info(client: &DockerClient) -> Result<RegistryInfo> {
+    let info = client.inner.info().await?;
+    let strip = |url: &str| url.trim_start_matches("https://").trim_start_matches("http://").trim_end_matches('/').to_string();
+    let config = info.registry_config.unwrap_or_default();
+
+    let mirrors: Vec<String> = config.mirrors.unwrap_or_default().iter().map(|m| strip(m)).collect();
+    let mut registries: Vec<String> = config.index_configs.unwrap_or_default()
+        .into_iter()
+        .filter(|(_, index)| index.official != Some(true))
+        .map(|(name, _)| strip(&name))
+        .collect();
+    registries.sort();
+
+    let non_empty = |v: Option<String>| v.filter(|p| !p.is_empty());
+    Ok(RegistryInfo {
+        mirrors,
+        registries,
+        http_proxy: non_empty(info.http_proxy),
+        https_proxy: non_empty(info.https_proxy),
+    })
+}
+
 pub async fn inspect_image(client: &DockerClient, id: &str) -> Result<ImageInspect> {
     client.inner.inspect_image(id).await.map_err(Into::into)
 }
@@ -56,16 +149,177 @@ pub async fn remove_image(client: &DockerClient, id: &str, force: bool) -> Resul
 
 pub fn pull_image(client: &DockerClient, image: String) -> BoxStream<'static, Result<bollard::models::CreateImageInfo>> {
     let options = CreateImageOptions {
-        from_image: image,
+        from_image: Some(image),
         ..Default::default()
     };
     
     // create_image returns impl Stream<Item = Result<CreateImageInfo, Error>>
-    let stream = client.inner.create_image(Some(options), None, None);
-    
-    stream
-        .map_err(AppError::Docker)
-        .boxed()
+    let stream = client.inner.create_image(Some(options), None, None).map_err(AppError::Docker).boxed();
+
+    // A stalled pull ends with a timeout error instead of waiting forever
+    let secs = client.timeouts.pull_chunk_secs;
+    futures::stream::unfold(Some(stream), move |stream| async move {
+        let mut stream = stream?;
+        match tokio::time::timeout(Duration::from_secs(secs), stream.next()).await {
+            Ok(Some(item)) => Some((item, Some(stream))),
+            Ok(None) => None,
+            Err(_) => Some((Err(AppError::Timeout { operation: "image pull", secs }), None)),
+        }
+    })
+    .boxed()
+}
+
+/// Pulls `image` at no more than `kib_per_sec`. The daemon can't be asked to
+/// download more slowly, so dockyard fetches the manifest and layers from the
+/// registry itself, through a token bucket, and streams them to the daemon as
+/// a `docker load` archive. Only works for registries that allow anonymous pulls.
+pub async fn pull_image_throttled(client: &DockerClient, image: &str, kib_per_sec: u64, proxy: &ProxyConfig, progress: impl Fn(String)) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use bollard::query_parameters::ImportImageOptions;
+    use crate::app::format_bytes;
+    use crate::registry::{NeedsDaemonPull, Reference, Session, Throttle};
+    use http_body_util::BodyExt;
+
+    let version = client.inner.version().await.map_err(AppError::Docker)?;
+    let os = version.os.unwrap_or_else(|| "linux".to_string());
+    let arch = version.arch.unwrap_or_else(|| "amd64".to_string());
+
+    let mut session = Session::new(Reference::parse(image)?, proxy)?;
+    let manifest = session.manifest(&os, &arch).await?;
+    if let Some(layer) = manifest.layers.iter().find(|l| !l.urls.is_empty()) {
+        return Err(NeedsDaemonPull(format!("{} has a foreign layer ({}), which needs the daemon's own pull", image, layer.digest)).into());
+    }
+    let config = session.blob_bytes(&manifest.config.digest).await?;
+    let total: u64 = manifest.layers.iter().map(|l| l.size).sum();
+    progress(format!("Pulling {} ({} in {} layers) at up to {} KiB/s", image, format_bytes(total), manifest.layers.len(), kib_per_sec));
+
+    // docker save layout: config and manifest.json, then one layer.tar per
+    // layer. Layers stay compressed; the daemon decompresses them on load.
+    let hex = |digest: &str| digest.rsplit(':').next().unwrap_or(digest).to_string();
+    let config_name = format!("{}.json", hex(&manifest.config.digest));
+    let layer_names: Vec<String> = manifest.layers.iter().map(|l| format!("{}/layer.tar", hex(&l.digest))).collect();
+    let archive_manifest = serde_json::json!([{
+        "Config": config_name,
+        "RepoTags": session.reference().tag.iter().collect::<Vec<_>>(),
+        "Layers": layer_names,
+    }]);
+
+    let (mut tx, rx) = futures::channel::mpsc::channel::<Bytes>(8);
+    let secs = client.timeouts.pull_chunk_secs;
+    let producer = async {
+        use futures::SinkExt;
+        let closed = || anyhow::anyhow!("The daemon stopped reading the image");
+        tx.send(tar_entry(&config_name, &config)?).await.map_err(|_| closed())?;
+        tx.send(tar_entry("manifest.json", &serde_json::to_vec(&archive_manifest)?)?).await.map_err(|_| closed())?;
+
+        let mut throttle = Throttle::new(kib_per_sec);
+        let mut written = HashSet::new();
+        for (i, (layer, name)) in manifest.layers.iter().zip(&layer_names).enumerate() {
+            // A layer listed twice is stored once
+            if !written.insert(name.clone()) {
+                continue;
+            }
+            tx.send(tar_header(name, layer.size)?).await.map_err(|_| closed())?;
+            let mut body = session.blob(&layer.digest).await?;
+            let mut received = 0u64;
+            let mut reported = 0;
+            while let Some(frame) = tokio::time::timeout(Duration::from_secs(secs), body.frame()).await
+                .map_err(|_| AppError::Timeout { operation: "image pull", secs })?
+            {
+                let Ok(data) = frame.context("Layer download failed")?.into_data() else { continue };
+                throttle.take(data.len()).await;
+                received += data.len() as u64;
+                tx.send(data).await.map_err(|_| closed())?;
+                let percent = received * 100 / layer.size.max(1);
+                if percent / 10 > reported {
+                    reported = percent / 10;
+                    progress(format!("Layer {}/{} {}: {}% of {}", i + 1, manifest.layers.len(), &hex(&layer.digest)[..12], percent, format_bytes(layer.size)));
+                }
+            }
+            if received != layer.size {
+                anyhow::bail!("Layer {} ended after {} of {} bytes", layer.digest, received, layer.size);
+            }
+            tx.send(tar_padding(layer.size)).await.map_err(|_| closed())?;
+        }
+        // End-of-archive marker
+        tx.send(Bytes::from(vec![0; 1024])).await.map_err(|_| closed())?;
+        tx.close_channel();
+        anyhow::Ok(())
+    };
+
+    let consumer = async {
+        let mut load = client.inner.import_image_stream(ImportImageOptions::default(), rx, None);
+        while let Some(info) = load.next().await {
+            let info = info.map_err(AppError::Docker)?;
+            if let Some(error) = info.error_detail.and_then(|e| e.message) {
+                anyhow::bail!("Load failed: {}", error);
+            }
+            if let Some(stream) = info.stream.filter(|s| !s.trim().is_empty()) {
+                progress(stream.trim().to_string());
+            }
+        }
+        anyhow::Ok(())
+    };
+
+    let (produced, consumed) = futures::join!(producer, consumer);
+    // When the download fails the archive is cut short, and the daemon's
+    // complaint about that says less than the download error
+    produced?;
+    consumed
+}
+
+/// One file in a tar archive, header and padding included.
+fn tar_entry(name: &str, data: &[u8]) -> anyhow::Result<Bytes> {
+    let mut entry = tar_header(name, data.len() as u64)?.to_vec();
+    entry.extend_from_slice(data);
+    entry.extend_from_slice(&tar_padding(data.len() as u64));
+    Ok(Bytes::from(entry))
+}
+
+/// ustar header for a regular file of `size` bytes.
+fn tar_header(name: &str, size: u64) -> anyhow::Result<Bytes> {
+    if name.len() > 100 || size >= 1 << 33 {
+        anyhow::bail!("{} is too large for the image archive", name);
+    }
+    let mut header = [0u8; 512];
+    let mut field = |offset: usize, value: &[u8]| header[offset..offset + value.len()].copy_from_slice(value);
+    field(0, name.as_bytes());
+    field(100, b"0000644\0");
+    field(108, b"0000000\0");
+    field(116, b"0000000\0");
+    field(124, format!("{:011o}\0", size).as_bytes());
+    field(136, format!("{:011o}\0", chrono::Utc::now().timestamp()).as_bytes());
+    field(148, b"        ");
+    field(156, b"0");
+    field(257, b"ustar\0");
+    field(263, b"00");
+    let checksum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+    header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+    Ok(Bytes::copy_from_slice(&header))
+}
+
+fn tar_padding(size: u64) -> Bytes {
+    Bytes::from(vec![0; ((512 - size % 512) % 512) as usize])
+}
+
+/// Snapshots a container's filesystem as `reference` (`repo[:tag]`), pausing
+/// it meanwhile as `docker commit` does. Returns the new image ID.
+pub async fn commit_container(client: &DockerClient, id: &str, reference: &str, comment: Option<String>, author: Option<String>) -> Result<String> {
+    let (repo, tag) = match reference.rsplit_once(':') {
+        Some((repo, tag)) if !tag.contains('/') => (repo, tag),
+        _ => (reference, "latest"),
+    };
+    let options = CommitContainerOptions {
+        container: Some(id.to_string()),
+        repo: Some(repo.to_string()),
+        tag: Some(tag.to_string()),
+        comment,
+        author,
+        pause: true,
+        ..Default::default()
+    };
+    let response = client.inner.commit_container(options, ContainerConfig::default()).await?;
+    Ok(response.id)
 }
 
 pub async fn prune_images(client: &DockerClient) -> Result<()> {
@@ -73,7 +327,7 @@ pub async fn prune_images(client: &DockerClient) -> Result<()> {
      filters.insert("dangling".to_string(), vec!["true".to_string()]);
      
      let options = PruneImagesOptions {
-         filters,
+         filters: Some(filters),
      };
      client.inner.prune_images(Some(options)).await?;
      Ok(())
@@ -1,14 +1,26 @@
 use crate::docker::client::DockerClient;
-use bollard::container::LogsOptions;
+use bollard::query_parameters::LogsOptions;
 use futures::Stream;
 use bollard::container::LogOutput;
+use futures::StreamExt;
+use chrono::DateTime;
+
+/// Nanosecond time of a line from a stream with `timestamps: true`, also
+/// behind the `service | ` prefix of project logs.
+pub fn line_time(line: &str) -> Option<i64> {
+    let parse = |text: &str| {
+        let (stamp, _) = text.split_once(' ')?;
+        DateTime::parse_from_rfc3339(stamp).ok()?.timestamp_nanos_opt()
+    };
+    parse(line).or_else(|| parse(line.split_once(" | ")?.1))
+}
 
 pub fn stream_logs(
     client: &DockerClient,
     container_id: &str,
     tail: &str,
 ) -> impl Stream<Item = Result<LogOutput, bollard::errors::Error>> {
-    let options = LogsOptions::<String> {
+    let options = LogsOptions {
         stdout: true,
         stderr: true,
         follow: true,
@@ -19,3 +31,21 @@ pub fn stream_logs(
     
     client.inner.logs(container_id, Some(options))
 }
+
+/// One-shot fetch of the last `tail` log lines (no follow).
+pub async fn fetch_recent_logs(client: &DockerClient, container_id: &str, tail: usize) -> crate::types::Result<Vec<String>> {
+    let options = LogsOptions {
+        stdout: true,
+        stderr: true,
+        follow: false,
+        tail: tail.to_string(),
+        ..Default::default()
+    };
+
+    let mut stream = client.inner.logs(container_id, Some(options));
+    let mut lines = Vec::new();
+    while let Some(log) = stream.next().await {
+        lines.push(log?.to_string().trim_end().to_string());
+    }
+    Ok(lines)
+}
@@ -0,0 +1,206 @@
+//! Moving a container to another host: its image is committed or reused,
+//! copied across (or pulled by the target), and the container is recreated
+//! there with the same config. Steps already taken are undone if a later one
+//! fails, so a failed migration leaves both hosts as they were.
+
+use crate::docker::client::DockerClient;
+use crate::docker::containers::{config_from_inspect, create_container, endpoints_from_inspect, remove_container, start_container, stop_container};
+use crate::docker::images::{commit_container, pull_image, remove_image};
+use crate::app::format_bytes;
+use crate::types::AppError;
+use anyhow::{Context, Result};
+use bollard::models::{ContainerInspectResponse, MountPointTypeEnum};
+use bollard::query_parameters::{ImportImageOptions, InspectContainerOptions, InspectNetworkOptions};
+use futures::{SinkExt, StreamExt};
+use hyper::body::Bytes;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MigrateOptions {
+    /// Commit the container first, so changes outside volumes move too.
+    pub commit: bool,
+    /// Let the target pull the image from its registry instead of copying it.
+    pub pull: bool,
+    /// Stop the original once the copy is up.
+    pub stop_source: bool,
+}
+
+/// What has been done so far, to be undone in reverse on failure.
+#[derive(Default)]
+struct Rollback {
+    source_image: Option<String>,
+    target_image: Option<String>,
+    target_container: Option<String>,
+}
+
+/// Recreates the container described by `info` on `target` under the same
+/// name, started if the original was running. Returns the new container's ID.
+pub async fn migrate_container<F>(source: &DockerClient, target: &DockerClient, target_name: &str, info: &ContainerInspectResponse, options: MigrateOptions, log: F) -> Result<String>
+where
+    F: Fn(String),
+{
+    let name = info.name.as_deref().unwrap_or_default().trim_start_matches('/').to_string();
+    let mut rollback = Rollback::default();
+    let result = run_steps(source, target, target_name, info, &name, options, &mut rollback, &log).await;
+    if let Err(e) = &result {
+        log(format!("Error: {}; rolling back", e));
+        if let Some(id) = rollback.target_container {
+            log(format!("Removing {} from {}", name, target_name));
+            let _ = remove_container(target, &id).await;
+        }
+        if let Some(image) = rollback.target_image {
+            log(format!("Removing {} from {}", image, target_name));
+            let _ = remove_image(target, &image, false).await;
+        }
+        if let Some(image) = rollback.source_image {
+            log(format!("Removing {}", image));
+            let _ = remove_image(source, &image, false).await;
+        }
+        log("Rollback complete; the original is untouched".to_string());
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_steps<F>(
+    source: &DockerClient,
+    target: &DockerClient,
+    target_name: &str,
+    info: &ContainerInspectResponse,
+    name: &str,
+    options: MigrateOptions,
+    rollback: &mut Rollback,
+    log: &F,
+) -> Result<String>
+where
+    F: Fn(String),
+{
+    let id = info.id.clone().unwrap_or_default();
+    let running = info.state.as_ref().and_then(|s| s.running).unwrap_or(false);
+    let total_steps = if options.stop_source && running { 4 } else { 3 };
+    let step = |n: usize, text: String| log(format!("[{}/{}] {}", n, total_steps, text));
+
+    if target.inner.inspect_container(name, None::<InspectContainerOptions>).await.is_ok() {
+        anyhow::bail!("{} already has a container named {}", target_name, name);
+    }
+    for (network, _) in endpoints_from_inspect(info) {
+        if target.inner.inspect_network(&network, None::<InspectNetworkOptions>).await.is_err() {
+            anyhow::bail!("Network {} doesn't exist on {}; create it there first", network, target_name);
+        }
+    }
+    for mount in info.mounts.iter().flatten() {
+        let source_path = mount.source.as_deref().unwrap_or_default();
+        match mount.typ {
+            Some(MountPointTypeEnum::BIND) => log(format!("Warning: bind mount {} has to exist on {}", source_path, target_name)),
+            Some(MountPointTypeEnum::VOLUME) => {
+                log(format!("Warning: volume {} starts empty on {}", mount.name.as_deref().unwrap_or(source_path), target_name))
+            }
+            _ => {}
+        }
+    }
+
+    let image = if options.commit {
+        let repo: String = name.to_lowercase().chars()
+            .map(|c| if c.is_ascii_alphanumeric() || "._-".contains(c) { c } else { '-' })
+            .collect();
+        let reference = format!("dockyard-migrate/{}:{}", repo, chrono::Utc::now().format("%Y%m%d%H%M%S"));
+        step(1, format!("Committing {} as {}", name, reference));
+        commit_container(source, &id, &reference, Some("Migrated by dockyard".to_string()), None).await?;
+        rollback.source_image = Some(reference.clone());
+        reference
+    } else {
+        let image = info.config.as_ref().and_then(|c| c.image.clone())
+            .or_else(|| info.image.clone())
+            .context("The container has no image")?;
+        step(1, format!("Using image {}", image));
+        image
+    };
+
+    if !options.commit && target.inner.inspect_image(&image).await.is_ok() {
+        step(2, format!("{} is already on {}", image, target_name));
+    } else if options.pull && !options.commit {
+        step(2, format!("Pulling {} on {}", image, target_name));
+        let mut pull = pull_image(target, image.clone());
+        let mut reported = HashSet::new();
+        while let Some(progress) = pull.next().await {
+            let progress = progress?;
+            if let Some(status) = progress.status.filter(|s| !matches!(s.as_str(), "Downloading" | "Extracting" | "Waiting")) {
+                let line = match progress.id {
+                    Some(layer) => format!("{}: {}", layer, status),
+                    None => status,
+                };
+                if reported.insert(line.clone()) {
+                    log(line);
+                }
+            }
+        }
+        rollback.target_image = Some(image.clone());
+    } else {
+        step(2, format!("Copying {} to {}", image, target_name));
+        copy_image(source, target, &image, log).await?;
+        rollback.target_image = Some(image.clone());
+    }
+
+    step(3, format!("Creating {} on {}", name, target_name));
+    let mut config = config_from_inspect(info);
+    config.image = Some(image);
+    let new_id = create_container(target, name, config, endpoints_from_inspect(info)).await?;
+    rollback.target_container = Some(new_id.clone());
+    if running {
+        log(format!("Starting {} on {}", name, target_name));
+        start_container(target, &new_id).await?;
+    }
+
+    if options.stop_source && running {
+        step(4, format!("Stopping the original {}", name));
+        stop_container(source, &id).await?;
+    }
+    log("Migration complete".to_string());
+    Ok(new_id)
+}
+
+/// Streams `docker save` from `source` straight into `docker load` on `target`.
+async fn copy_image<F>(source: &DockerClient, target: &DockerClient, image: &str, log: &F) -> Result<()>
+where
+    F: Fn(String),
+{
+    let size = source.inner.inspect_image(image).await.map_err(AppError::Docker)?.size.unwrap_or_default().max(1) as u64;
+    let (mut tx, rx) = futures::channel::mpsc::channel::<Bytes>(8);
+
+    let producer = async {
+        let mut save = source.inner.export_image(image);
+        let mut sent = 0u64;
+        let mut reported = 0;
+        while let Some(chunk) = save.next().await {
+            let chunk = chunk.map_err(AppError::Docker)?;
+            sent += chunk.len() as u64;
+            tx.send(chunk).await.map_err(|_| anyhow::anyhow!("The target stopped reading the image"))?;
+            // The archive is about as large as the unpacked image
+            let percent = (sent * 100 / size).min(100);
+            if percent / 10 > reported {
+                reported = percent / 10;
+                log(format!("Copied {} (~{}%)", format_bytes(sent), percent));
+            }
+        }
+        tx.close_channel();
+        anyhow::Ok(())
+    };
+
+    let consumer = async {
+        let mut load = target.inner.import_image_stream(ImportImageOptions::default(), rx, None);
+        while let Some(info) = load.next().await {
+            let info = info.map_err(AppError::Docker)?;
+            if let Some(error) = info.error_detail.and_then(|e| e.message) {
+                anyhow::bail!("Load failed: {}", error);
+            }
+            if let Some(stream) = info.stream.filter(|s| !s.trim().is_empty()) {
+                log(stream.trim().to_string());
+            }
+        }
+        anyhow::Ok(())
+    };
+
+    let (produced, consumed) = futures::join!(producer, consumer);
+    produced?;
+    consumed
+}
@@ -5,3 +5,14 @@ pub mod logs;
 pub mod exec;
 pub mod images;
 pub mod health;
+pub mod compose;
+pub mod swarm;
+pub mod export;
+pub mod system;
+pub mod events;
+pub mod context;
+pub mod diagnostics;
+pub mod netem;
+pub mod files;
+pub mod templates;
+pub mod migrate;
@@ -0,0 +1,75 @@
+//! Traffic shaping for latency and bandwidth testing. `tc` has to run in the
+//! container's network namespace with `NET_ADMIN`, which most application
+//! images lack, so a short-lived helper container joins the namespace instead.
+
+use crate::docker::client::DockerClient;
+use crate::docker::logs::fetch_recent_logs;
+use crate::types::{AppError, NetemRule, Result};
+use bollard::models::{ContainerCreateBody, HostConfig};
+use bollard::query_parameters::{CreateContainerOptions, CreateImageOptions, RemoveContainerOptions, StartContainerOptions, WaitContainerOptions};
+use futures::StreamExt;
+
+/// Shapes all traffic leaving every interface of the container.
+pub async fn apply_netem(client: &DockerClient, container_id: &str, image: &str, rule: &NetemRule) -> Result<()> {
+    let script = format!(
+        "set -e; for dev in $(ls /sys/class/net); do [ \"$dev\" = lo ] || tc qdisc replace dev \"$dev\" root netem {}; done",
+        rule.tc_args()
+    );
+    run_helper(client, container_id, image, &script).await
+}
+
+/// Removes whatever root qdisc `apply_netem` installed, restoring the default.
+pub async fn clear_netem(client: &DockerClient, container_id: &str, image: &str) -> Result<()> {
+    let script = "for dev in $(ls /sys/class/net); do [ \"$dev\" = lo ] || tc qdisc del dev \"$dev\" root 2>/dev/null || true; done";
+    run_helper(client, container_id, image, script).await
+}
+
+async fn run_helper(client: &DockerClient, container_id: &str, image: &str, script: &str) -> Result<()> {
+    ensure_image(client, image).await?;
+    let config = ContainerCreateBody {
+        image: Some(image.to_string()),
+        entrypoint: Some(vec!["sh".to_string(), "-c".to_string()]),
+        cmd: Some(vec![script.to_string()]),
+        host_config: Some(HostConfig {
+            network_mode: Some(format!("container:{}", container_id)),
+            cap_add: Some(vec!["NET_ADMIN".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let helper = client.inner.create_container(None::<CreateContainerOptions>, config).await?.id;
+
+    let result = async {
+        client.inner.start_container(&helper, None::<StartContainerOptions>).await?;
+        let mut wait = client.inner.wait_container(&helper, None::<WaitContainerOptions>);
+        // A non-zero exit arrives as an error carrying the status code
+        let status = match wait.next().await {
+            Some(Ok(response)) => response.status_code,
+            Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => code,
+            Some(Err(e)) => return Err(e.into()),
+            None => 0,
+        };
+        if status != 0 {
+            let output = fetch_recent_logs(client, &helper, 5).await.unwrap_or_default().join("; ");
+            return Err(AppError::Other(format!("tc exited with {}: {}", status, output)));
+        }
+        Ok(())
+    }.await;
+
+    let options = RemoveContainerOptions { force: true, ..Default::default() };
+    let _ = client.inner.remove_container(&helper, Some(options)).await;
+    result
+}
+
+/// Pulls the helper image the first time it is needed.
+async fn ensure_image(client: &DockerClient, image: &str) -> Result<()> {
+    if client.inner.inspect_image(image).await.is_ok() {
+        return Ok(());
+    }
+    let options = CreateImageOptions { from_image: Some(image.to_string()), ..Default::default() };
+    let mut pull = client.inner.create_image(Some(options), None, None);
+    while let Some(progress) = pull.next().await {
+        progress?;
+    }
+    Ok(())
+}
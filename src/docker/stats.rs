@@ -1,6 +1,6 @@
 use crate::docker::client::DockerClient;
 use crate::types::Result;
-use bollard::container::StatsOptions;
+use bollard::query_parameters::StatsOptions;
 use futures::StreamExt;
 
 pub async fn fetch_container_stats(
@@ -16,22 +16,25 @@ pub async fn fetch_container_stats(
     );
 
     if let Some(Ok(stats)) = stats_stream.next().await {
-        let cpu_delta = stats.cpu_stats.cpu_usage.total_usage
-            .saturating_sub(stats.precpu_stats.cpu_usage.total_usage);
-        let system_delta = stats
-            .cpu_stats
+        let cpu_stats = stats.cpu_stats.unwrap_or_default();
+        let precpu_stats = stats.precpu_stats.unwrap_or_default();
+        let cpu_usage = cpu_stats.cpu_usage.clone().unwrap_or_default();
+        let precpu_usage = precpu_stats.cpu_usage.unwrap_or_default();
+        let memory_stats = stats.memory_stats.unwrap_or_default();
+
+        let cpu_delta = cpu_usage.total_usage.unwrap_or(0)
+            .saturating_sub(precpu_usage.total_usage.unwrap_or(0));
+        let system_delta = cpu_stats
             .system_cpu_usage
             .unwrap_or(0)
-            .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or(0));
+            .saturating_sub(precpu_stats.system_cpu_usage.unwrap_or(0));
 
         let cpu_percent = if system_delta > 0 && cpu_delta > 0 {
-            let num_cpus = stats
-                .cpu_stats
+            let num_cpus = cpu_stats
                 .online_cpus
+                .map(u64::from)
                 .unwrap_or_else(|| {
-                    stats
-                        .cpu_stats
-                        .cpu_usage
+                    cpu_usage
                         .percpu_usage
                         .as_ref()
                         .map(|p| p.len() as u64)
@@ -43,19 +46,17 @@ pub async fn fetch_container_stats(
         };
 
         // Calculate user and system CPU percentages
-        let user_cpu_delta = stats.cpu_stats.cpu_usage.usage_in_usermode
-            .saturating_sub(stats.precpu_stats.cpu_usage.usage_in_usermode);
-        let system_cpu_delta = stats.cpu_stats.cpu_usage.usage_in_kernelmode
-            .saturating_sub(stats.precpu_stats.cpu_usage.usage_in_kernelmode);
+        let user_cpu_delta = cpu_usage.usage_in_usermode.unwrap_or(0)
+            .saturating_sub(precpu_usage.usage_in_usermode.unwrap_or(0));
+        let system_cpu_delta = cpu_usage.usage_in_kernelmode.unwrap_or(0)
+            .saturating_sub(precpu_usage.usage_in_kernelmode.unwrap_or(0));
 
         let user_cpu_percent = if system_delta > 0 && user_cpu_delta > 0 {
-            let num_cpus = stats
-                .cpu_stats
+            let num_cpus = cpu_stats
                 .online_cpus
+                .map(u64::from)
                 .unwrap_or_else(|| {
-                    stats
-                        .cpu_stats
-                        .cpu_usage
+                    cpu_usage
                         .percpu_usage
                         .as_ref()
                         .map(|p| p.len() as u64)
@@ -67,13 +68,11 @@ pub async fn fetch_container_stats(
         };
 
         let system_cpu_percent = if system_delta > 0 && system_cpu_delta > 0 {
-            let num_cpus = stats
-                .cpu_stats
+            let num_cpus = cpu_stats
                 .online_cpus
+                .map(u64::from)
                 .unwrap_or_else(|| {
-                    stats
-                        .cpu_stats
-                        .cpu_usage
+                    cpu_usage
                         .percpu_usage
                         .as_ref()
                         .map(|p| p.len() as u64)
@@ -84,16 +83,12 @@ pub async fn fetch_container_stats(
             0.0
         };
 
-        let memory_usage = stats.memory_stats.usage.unwrap_or(0);
-        let cached_memory = if let Some(stats_map) = &stats.memory_stats.stats {
-             match stats_map {
-                 bollard::container::MemoryStatsStats::V1(v1) => v1.cache,
-                 bollard::container::MemoryStatsStats::V2(_v2) => 0, // V2 doesn't have cache field
-             }
-        } else {
-             0
-        };
-        let memory_limit = stats.memory_stats.limit.unwrap_or(0);
+        let memory_usage = memory_stats.usage.unwrap_or(0);
+        // cgroup v1 reports "cache"; v2 has no direct equivalent
+        let cached_memory = memory_stats.stats.as_ref()
+            .and_then(|m| m.get("cache").copied())
+            .unwrap_or(0);
+        let memory_limit = memory_stats.limit.unwrap_or(0);
 
         Ok(Some((cpu_percent, user_cpu_percent, system_cpu_percent, memory_usage, cached_memory, memory_limit)))
     } else {
@@ -0,0 +1,291 @@
+use crate::docker::client::{ApiFeature, DockerClient};
+use crate::types::{AppError, NodeAvailability, NodeInfo, Result, ServiceInfo, StackInfo, STACK_NAMESPACE_LABEL};
+use bollard::models::{Config, NodeSpecAvailabilityEnum, Service, ServiceUpdateStatusStateEnum, TaskState};
+use bollard::query_parameters::{InspectServiceOptions, ListNetworksOptions, ListNodesOptions, ListSecretsOptions, ListServicesOptions, ListTasksOptions, UpdateNodeOptions, UpdateServiceOptions};
+use hyper::Method;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Lists swarm nodes. Fails when the daemon is not a swarm manager.
+pub async fn list_nodes(client: &DockerClient) -> Result<Vec<NodeInfo>> {
+    client.require(ApiFeature::Swarm)?;
+    let nodes = client.inner.list_nodes(None::<ListNodesOptions>).await?;
+
+    let mut infos: Vec<NodeInfo> = nodes
+        .into_iter()
+        .map(|n| {
+            let spec = n.spec.unwrap_or_default();
+            let description = n.description.unwrap_or_default();
+            let resources = description.resources.unwrap_or_default();
+
+            let availability = match spec.availability {
+                Some(NodeSpecAvailabilityEnum::DRAIN) => NodeAvailability::Drain,
+                Some(NodeSpecAvailabilityEnum::PAUSE) => NodeAvailability::Pause,
+                _ => NodeAvailability::Active,
+            };
+
+            NodeInfo {
+                id: n.id.unwrap_or_default(),
+                hostname: description.hostname.unwrap_or_default(),
+                role: spec.role.map(|r| r.to_string()).unwrap_or_default(),
+                availability,
+                state: n.status.and_then(|s| s.state).map(|s| s.to_string()).unwrap_or_else(|| "unknown".to_string()),
+                engine_version: description.engine.and_then(|e| e.engine_version).unwrap_or_default(),
+                nano_cpus: resources.nano_cpus.unwrap_or(0),
+                memory_bytes: resources.memory_bytes.unwrap_or(0),
+                is_leader: n.manager_status.and_then(|m| m.leader).unwrap_or(false),
+            }
+        })
+        .collect();
+
+    infos.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+    Ok(infos)
+}
+
+/// Changes a node's availability. The update must carry the node's current
+/// spec and version, so the node is inspected first.
+pub async fn set_node_availability(client: &DockerClient, id: &str, availability: NodeAvailability) -> Result<()> {
+    let node = client.inner.inspect_node(id).await?;
+    let version = node.version.and_then(|v| v.index)
+        .ok_or_else(|| AppError::Other(format!("Node {} has no version", id)))?;
+
+    let mut spec = node.spec.unwrap_or_default();
+    spec.availability = Some(match availability {
+        NodeAvailability::Active => NodeSpecAvailabilityEnum::ACTIVE,
+        NodeAvailability::Pause => NodeSpecAvailabilityEnum::PAUSE,
+        NodeAvailability::Drain => NodeSpecAvailabilityEnum::DRAIN,
+    });
+
+    let options = UpdateNodeOptions { version: version as i64 };
+    client.inner.update_node(id, spec, options).await.map_err(Into::into)
+}
+
+/// Lists swarm services with their running/desired task counts.
+pub async fn list_services(client: &DockerClient) -> Result<Vec<ServiceInfo>> {
+    client.require(ApiFeature::Swarm)?;
+    let options = ListServicesOptions { status: Some(true), ..Default::default() };
+    let services = client.inner.list_services(Some(options)).await?;
+
+    let mut infos: Vec<ServiceInfo> = services
+        .into_iter()
+        .map(|s| {
+            let spec = s.spec.unwrap_or_default();
+            let status = s.service_status.unwrap_or_default();
+            let mode = match spec.mode.as_ref() {
+                Some(m) if m.global.is_some() => "global",
+                Some(m) if m.replicated_job.is_some() || m.global_job.is_some() => "job",
+                _ => "replicated",
+            };
+
+            ServiceInfo {
+                id: s.id.unwrap_or_default(),
+                name: spec.name.unwrap_or_default(),
+                image: spec.task_template
+                    .and_then(|t| t.container_spec)
+                    .and_then(|c| c.image)
+                    .unwrap_or_default(),
+                mode: mode.to_string(),
+                running_tasks: status.running_tasks.unwrap_or(0),
+                desired_tasks: status.desired_tasks.unwrap_or(0),
+                labels: spec.labels.unwrap_or_default(),
+                update_state: s.update_status.and_then(|u| u.state).map(|st| st.to_string()),
+            }
+        })
+        .collect();
+
+    infos.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(infos)
+}
+
+async fn inspect_service(client: &DockerClient, id: &str) -> Result<(Service, i32)> {
+    let service = client.inner.inspect_service(id, None::<InspectServiceOptions>).await?;
+    let version = service.version.as_ref().and_then(|v| v.index)
+        .ok_or_else(|| AppError::Other(format!("Service {} has no version", id)))?;
+    Ok((service, version as i32))
+}
+
+/// Force-updates a service, optionally switching it to a new image. Tasks are
+/// recreated even when the image is unchanged.
+pub async fn update_service_image(client: &DockerClient, id: &str, image: Option<&str>) -> Result<()> {
+    let (service, version) = inspect_service(client, id).await?;
+    let mut spec = service.spec.unwrap_or_default();
+
+    let task_template = spec.task_template.get_or_insert_with(Default::default);
+    task_template.force_update = Some(task_template.force_update.unwrap_or(0) + 1);
+    if let Some(image) = image {
+        task_template.container_spec.get_or_insert_with(Default::default).image = Some(image.to_string());
+    }
+
+    let options = UpdateServiceOptions { version, ..Default::default() };
+    client.inner.update_service(id, spec, options, None).await?;
+    Ok(())
+}
+
+/// Rolls a service back to its previous spec (server side).
+pub async fn rollback_service(client: &DockerClient, id: &str) -> Result<()> {
+    let (service, version) = inspect_service(client, id).await?;
+    let options = UpdateServiceOptions {
+        version,
+        rollback: Some("previous".to_string()),
+        ..Default::default()
+    };
+    client.inner.update_service(id, service.spec.unwrap_or_default(), options, None).await?;
+    Ok(())
+}
+
+/// Polls a service after an update, reporting each task state change through `log`
+/// until the update (or rollback) finishes, pauses or `timeout` passes.
+pub async fn watch_service_update<F>(client: &DockerClient, id: &str, timeout: Duration, log: F) -> Result<()>
+where
+    F: Fn(String),
+{
+    let mut filters = HashMap::new();
+    filters.insert("service".to_string(), vec![id.to_string()]);
+    let mut seen: HashMap<String, String> = HashMap::new();
+    let started = std::time::Instant::now();
+
+    while started.elapsed() < timeout {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let options = ListTasksOptions { filters: Some(filters.clone()) };
+        let mut tasks = client.inner.list_tasks(Some(options)).await?;
+        tasks.sort_by_key(|t| t.slot.unwrap_or(0));
+        for task in tasks {
+            let Some(task_id) = task.id else { continue };
+            let status = task.status.unwrap_or_default();
+            let state = status.state.map(|s| s.to_string()).unwrap_or_else(|| "unknown".to_string());
+            if seen.get(&task_id) == Some(&state) {
+                continue;
+            }
+            let short_id: String = task_id.chars().take(12).collect();
+            let detail = status.err.or(status.message).unwrap_or_default();
+            log(format!("slot {} {}: {} {}", task.slot.unwrap_or(0), short_id, state, detail).trim_end().to_string());
+            seen.insert(task_id, state);
+        }
+
+        let (service, _) = inspect_service(client, id).await?;
+        let update = service.update_status.unwrap_or_default();
+        match update.state {
+            Some(ServiceUpdateStatusStateEnum::COMPLETED) | Some(ServiceUpdateStatusStateEnum::ROLLBACK_COMPLETED) => {
+                log(update.message.unwrap_or_else(|| "Update completed".to_string()));
+                return Ok(());
+            }
+            Some(ServiceUpdateStatusStateEnum::PAUSED) | Some(ServiceUpdateStatusStateEnum::ROLLBACK_PAUSED) => {
+                let message = update.message.unwrap_or_else(|| "Update paused".to_string());
+                return Err(AppError::Other(message));
+            }
+            _ => {}
+        }
+    }
+
+    log("Stopped watching: update still in progress".to_string());
+    Ok(())
+}
+
+/// Groups services by their stack namespace label and aggregates the state of the
+/// tasks that should currently be running.
+pub async fn list_stacks(client: &DockerClient) -> Result<Vec<StackInfo>> {
+    client.require(ApiFeature::Swarm)?;
+    let services = list_services(client).await?;
+
+    let mut filters = HashMap::new();
+    filters.insert("desired-state".to_string(), vec!["running".to_string()]);
+    let tasks = client.inner.list_tasks(Some(ListTasksOptions { filters: Some(filters) })).await?;
+
+    let mut stacks: BTreeMap<String, StackInfo> = BTreeMap::new();
+    let mut stack_of_service: HashMap<String, String> = HashMap::new();
+    for service in &services {
+        let Some(name) = service.stack() else { continue };
+        let stack = stacks.entry(name.to_string()).or_insert_with(|| StackInfo { name: name.to_string(), ..Default::default() });
+        stack.services += 1;
+        stack.desired_tasks += service.desired_tasks;
+        stack_of_service.insert(service.id.clone(), name.to_string());
+    }
+
+    for task in tasks {
+        let Some(stack) = task.service_id.as_ref()
+            .and_then(|id| stack_of_service.get(id))
+            .and_then(|name| stacks.get_mut(name)) else { continue };
+        match task.status.and_then(|s| s.state) {
+            Some(TaskState::RUNNING) => stack.running_tasks += 1,
+            Some(TaskState::FAILED) | Some(TaskState::REJECTED) => stack.failed_tasks += 1,
+            _ => stack.starting_tasks += 1,
+        }
+    }
+
+    Ok(stacks.into_values().collect())
+}
+
+/// Lists swarm configs matching `filters`. bollard has no configs API, so
+/// this goes to the daemon directly.
+pub async fn list_configs(client: &DockerClient, filters: &HashMap<String, Vec<String>>) -> Result<Vec<Config>> {
+    let filters = serde_json::to_string(filters).map_err(|e| AppError::Other(e.to_string()))?;
+    let body = client.raw_request(Method::GET, &format!("/configs?filters={}", query_escape(&filters))).await?;
+    serde_json::from_slice(&body).map_err(|e| AppError::Other(format!("unexpected configs response: {}", e)))
+}
+
+pub async fn delete_config(client: &DockerClient, id: &str) -> Result<()> {
+    client.raw_request(Method::DELETE, &format!("/configs/{}", query_escape(id))).await?;
+    Ok(())
+}
+
+/// Percent-encodes everything but unreserved characters.
+fn query_escape(value: &str) -> String {
+    value.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Removes a stack the way `docker stack rm` does: its services first, then the
+/// networks, secrets and configs carrying the stack namespace label.
+pub async fn remove_stack<F>(client: &DockerClient, name: &str, log: F) -> Result<()>
+where
+    F: Fn(String),
+{
+    let mut filters = HashMap::new();
+    filters.insert("label".to_string(), vec![format!("{}={}", STACK_NAMESPACE_LABEL, name)]);
+
+    let services = client.inner.list_services(Some(ListServicesOptions { filters: Some(filters.clone()), ..Default::default() })).await?;
+    for service in services {
+        let Some(id) = service.id else { continue };
+        let service_name = service.spec.and_then(|s| s.name).unwrap_or_else(|| id.clone());
+        log(format!("Removing service {}", service_name));
+        client.inner.delete_service(&id).await?;
+    }
+
+    let networks = client.inner.list_networks(Some(ListNetworksOptions { filters: Some(filters.clone()) })).await?;
+    for network in networks {
+        let Some(id) = network.id else { continue };
+        log(format!("Removing network {}", network.name.unwrap_or_else(|| id.clone())));
+        // Tasks shut down asynchronously, so the network may still have endpoints for a moment
+        let mut attempts = 0;
+        while let Err(e) = client.inner.remove_network(&id).await {
+            attempts += 1;
+            if attempts >= 10 {
+                return Err(e.into());
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+
+    let secrets = client.inner.list_secrets(Some(ListSecretsOptions { filters: Some(filters.clone()) })).await?;
+    for secret in secrets {
+        let Some(id) = secret.id else { continue };
+        let secret_name = secret.spec.and_then(|s| s.name).unwrap_or_else(|| id.clone());
+        log(format!("Removing secret {}", secret_name));
+        client.inner.delete_secret(&id).await?;
+    }
+
+    for config in list_configs(client, &filters).await? {
+        let Some(id) = config.id else { continue };
+        let config_name = config.spec.and_then(|s| s.name).unwrap_or_else(|| id.clone());
+        log(format!("Removing config {}", config_name));
+        delete_config(client, &id).await?;
+    }
+
+    Ok(())
+}
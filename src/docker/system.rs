@@ -0,0 +1,253 @@
+use crate::docker::client::{ApiFeature, DockerClient};
+use crate::docker::containers::inspect_container;
+use crate::types::{BuildCacheEntry, CleanupItem, DaemonInfo, CleanupKind, CleanupResult, DiskUsage, Result, SystemReport};
+use bollard::models::ContainerSummaryStateEnum;
+use bollard::query_parameters::{DataUsageOptions, PruneBuildOptions, PruneContainersOptions, PruneImagesOptions, PruneNetworksOptions, PruneVolumesOptions};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Container log files larger than this are flagged by the cleanup advisor.
+pub const OVERSIZED_LOG_BYTES: u64 = 100 * 1024 * 1024;
+
+fn is_dangling(tags: &[String]) -> bool {
+    tags.is_empty() || tags.iter().all(|t| t == "<none>:<none>")
+}
+
+/// Collects disk usage and builds the cleanup checklist for the host.
+pub async fn analyze_host(client: &DockerClient) -> Result<SystemReport> {
+    client.require(ApiFeature::SystemDf)?;
+    let df = client.inner.df(None::<DataUsageOptions>).await?;
+    let images = df.images.unwrap_or_default();
+    let containers = df.containers.unwrap_or_default();
+    let volumes = df.volumes.unwrap_or_default();
+    let build_cache = df.build_cache.unwrap_or_default();
+
+    let exited: Vec<_> = containers.iter()
+        .filter(|c| matches!(c.state, Some(ContainerSummaryStateEnum::EXITED) | Some(ContainerSummaryStateEnum::DEAD) | Some(ContainerSummaryStateEnum::CREATED)))
+        .collect();
+    let dangling: Vec<_> = images.iter().filter(|i| is_dangling(&i.repo_tags) && i.containers <= 0).collect();
+    let unused_volumes: Vec<_> = volumes.iter()
+        .filter(|v| v.usage_data.as_ref().is_some_and(|u| u.ref_count == 0))
+        .collect();
+    let idle_cache: Vec<_> = build_cache.iter().filter(|b| b.in_use != Some(true) && b.shared != Some(true)).collect();
+
+    let volume_size = |v: &&bollard::models::Volume| v.usage_data.as_ref().map(|u| u.size.max(0) as u64).unwrap_or(0);
+    let usage = vec![
+        DiskUsage {
+            kind: "Images",
+            total: images.len(),
+            active: images.iter().filter(|i| i.containers > 0).count(),
+            size: df.layers_size.unwrap_or(0).max(0) as u64,
+            reclaimable: images.iter().filter(|i| i.containers <= 0).map(|i| (i.size - i.shared_size.max(0)).max(0) as u64).sum(),
+        },
+        DiskUsage {
+            kind: "Containers",
+            total: containers.len(),
+            active: containers.iter().filter(|c| c.state == Some(ContainerSummaryStateEnum::RUNNING)).count(),
+            size: containers.iter().map(|c| c.size_rw.unwrap_or(0).max(0) as u64).sum(),
+            reclaimable: exited.iter().map(|c| c.size_rw.unwrap_or(0).max(0) as u64).sum(),
+        },
+        DiskUsage {
+            kind: "Local Volumes",
+            total: volumes.len(),
+            active: volumes.len() - unused_volumes.len(),
+            size: volumes.iter().map(|v| volume_size(&v)).sum(),
+            reclaimable: unused_volumes.iter().map(volume_size).sum(),
+        },
+        DiskUsage {
+            kind: "Build Cache",
+            total: build_cache.len(),
+            active: build_cache.iter().filter(|b| b.in_use == Some(true)).count(),
+            size: build_cache.iter().map(|b| b.size.unwrap_or(0).max(0) as u64).sum(),
+            reclaimable: idle_cache.iter().map(|b| b.size.unwrap_or(0).max(0) as u64).sum(),
+        },
+    ];
+
+    let oldest_days = exited.iter()
+        .filter_map(|c| c.created)
+        .min()
+        .map(|created| (Utc::now().timestamp() - created) / 86_400);
+    let mut items = vec![
+        CleanupItem {
+            kind: CleanupKind::ExitedContainers,
+            count: exited.len(),
+            reclaimable: usage[1].reclaimable,
+            detail: match oldest_days {
+                Some(days) => format!("oldest created {}d ago", days),
+                None => String::new(),
+            },
+        },
+        CleanupItem {
+            kind: CleanupKind::DanglingImages,
+            count: dangling.len(),
+            reclaimable: dangling.iter().map(|i| i.size.max(0) as u64).sum(),
+            detail: String::new(),
+        },
+        CleanupItem {
+            kind: CleanupKind::UnusedVolumes,
+            count: unused_volumes.len(),
+            reclaimable: usage[2].reclaimable,
+            detail: unused_volumes.iter().map(|v| v.name.clone()).collect::<Vec<_>>().join(", "),
+        },
+    ];
+
+    let oversized = oversized_logs(client, &containers.iter().filter_map(|c| c.id.clone()).collect::<Vec<_>>()).await;
+    items.push(CleanupItem {
+        kind: CleanupKind::OversizedLogs,
+        count: oversized.len(),
+        reclaimable: oversized.values().map(|(_, size)| size).sum(),
+        detail: oversized.values().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(", "),
+    });
+    items.push(CleanupItem {
+        kind: CleanupKind::BuildCache,
+        count: idle_cache.len(),
+        reclaimable: usage[3].reclaimable,
+        detail: String::new(),
+    });
+
+    let mut cache_entries: Vec<BuildCacheEntry> = build_cache.into_iter()
+        .map(|b| BuildCacheEntry {
+            id: b.id.unwrap_or_default().chars().take(12).collect(),
+            kind: b.typ.map(|t| t.to_string()).unwrap_or_default(),
+            description: b.description.unwrap_or_default(),
+            size: b.size.unwrap_or(0).max(0) as u64,
+            last_used: b.last_used_at
+                .and_then(|d| DateTime::parse_from_rfc3339(&d).ok())
+                .map(|d| d.timestamp()),
+            in_use: b.in_use == Some(true),
+            shared: b.shared == Some(true),
+        })
+        .collect();
+    cache_entries.sort_by_key(|b| std::cmp::Reverse(b.size));
+
+    let daemon = daemon_info(client).await?;
+    Ok(SystemReport { usage, items, build_cache: cache_entries, daemon })
+}
+
+/// Collects `docker info` and the API version.
+pub async fn daemon_info(client: &DockerClient) -> Result<DaemonInfo> {
+    let info = client.inner.info().await?;
+    let version = client.inner.version().await?;
+    let text = |v: Option<String>| v.filter(|s| !s.is_empty()).unwrap_or_else(|| "-".to_string());
+    let swarm = info.swarm.unwrap_or_default();
+    let swarm_state = match swarm.local_node_state.map(|s| s.to_string()) {
+        Some(state) if state == "active" => match swarm.control_available {
+            Some(true) => format!("active (manager, {} nodes)", swarm.nodes.unwrap_or(0)),
+            _ => "active (worker)".to_string(),
+        },
+        Some(state) if !state.is_empty() => state,
+        _ => "inactive".to_string(),
+    };
+
+    Ok(DaemonInfo {
+        name: text(info.name),
+        engine_version: text(info.server_version),
+        api_version: text(version.api_version),
+        os: text(info.operating_system),
+        kernel: text(info.kernel_version),
+        storage_driver: text(info.driver),
+        cgroup_driver: text(info.cgroup_driver.map(|d| d.to_string())),
+        cgroup_version: text(info.cgroup_version.map(|v| v.to_string())),
+        cpus: info.ncpu.unwrap_or(0),
+        memory: info.mem_total.unwrap_or(0).max(0) as u64,
+        logging_driver: text(info.logging_driver),
+        default_runtime: text(info.default_runtime),
+        swarm_state,
+        warnings: info.warnings.unwrap_or_default(),
+    })
+}
+
+/// Log files over `OVERSIZED_LOG_BYTES`, keyed by path with `(container name, size)`.
+/// Only works when the daemon's log files are readable from here (local host).
+async fn oversized_logs(client: &DockerClient, ids: &[String]) -> HashMap<String, (String, u64)> {
+    let mut logs = HashMap::new();
+    for id in ids {
+        let Ok(info) = inspect_container(client, id).await else { continue };
+        let Some(path) = info.log_path.filter(|p| !p.is_empty()) else { continue };
+        let Ok(meta) = std::fs::metadata(&path) else { continue };
+        if meta.len() > OVERSIZED_LOG_BYTES {
+            let name = info.name.unwrap_or_default().trim_start_matches('/').to_string();
+            logs.insert(path, (name, meta.len()));
+        }
+    }
+    logs
+}
+
+fn reclaimed(space: Option<i64>) -> u64 {
+    space.unwrap_or(0).max(0) as u64
+}
+
+/// Executes one cleanup class and reports what was removed.
+pub async fn run_cleanup(client: &DockerClient, kind: CleanupKind) -> Result<CleanupResult> {
+    client.require(ApiFeature::Prune)?;
+    match kind {
+        CleanupKind::ExitedContainers => {
+            let res = client.inner.prune_containers(None::<PruneContainersOptions>).await?;
+            let deleted = res.containers_deleted.unwrap_or_default()
+                .into_iter()
+                .map(|id| id.chars().take(12).collect())
+                .collect();
+            Ok(CleanupResult { deleted, reclaimed: reclaimed(res.space_reclaimed) })
+        }
+        CleanupKind::DanglingImages => {
+            let mut filters = HashMap::new();
+            filters.insert("dangling".to_string(), vec!["true".to_string()]);
+            let res = client.inner.prune_images(Some(PruneImagesOptions { filters: Some(filters) })).await?;
+            let deleted = res.images_deleted.unwrap_or_default()
+                .into_iter()
+                .filter_map(|i| i.deleted.or(i.untagged))
+                .map(|id| id.trim_start_matches("sha256:").chars().take(12).collect())
+                .collect();
+            Ok(CleanupResult { deleted, reclaimed: reclaimed(res.space_reclaimed) })
+        }
+        CleanupKind::UnusedNetworks => {
+            let res = client.inner.prune_networks(None::<PruneNetworksOptions>).await?;
+            Ok(CleanupResult { deleted: res.networks_deleted.unwrap_or_default(), reclaimed: 0 })
+        }
+        CleanupKind::UnusedVolumes => {
+            // Without `all`, API 1.42+ only prunes anonymous volumes; older daemons reject it
+            let mut filters = HashMap::new();
+            if client.supports(ApiFeature::PruneNamedVolumes) {
+                filters.insert("all".to_string(), vec!["true".to_string()]);
+            }
+            let res = client.inner.prune_volumes(Some(PruneVolumesOptions { filters: Some(filters) })).await?;
+            Ok(CleanupResult { deleted: res.volumes_deleted.unwrap_or_default(), reclaimed: reclaimed(res.space_reclaimed) })
+        }
+        CleanupKind::OversizedLogs => {
+            let ids: Vec<String> = crate::docker::containers::list_containers(client, true).await?
+                .into_iter()
+                .map(|c| c.id)
+                .collect();
+            let mut result = CleanupResult::default();
+            for (path, (name, size)) in oversized_logs(client, &ids).await {
+                std::fs::OpenOptions::new().write(true).open(&path)?.set_len(0)?;
+                result.deleted.push(format!("{} log", name));
+                result.reclaimed += size;
+            }
+            Ok(result)
+        }
+        CleanupKind::BuildCache => {
+            client.require(ApiFeature::BuildCache)?;
+            let res = client.inner.prune_build(None::<PruneBuildOptions>).await?;
+            Ok(CleanupResult { deleted: res.caches_deleted.unwrap_or_default(), reclaimed: reclaimed(res.space_reclaimed) })
+        }
+    }
+}
+
+/// Prunes build cache, keeping up to `keep_bytes` of the most recently used
+/// cache and only removing records unused for at least `older_than_hours`.
+pub async fn prune_build_cache(client: &DockerClient, keep_bytes: Option<u64>, older_than_hours: Option<u64>) -> Result<CleanupResult> {
+    let filters = older_than_hours.map(|hours| {
+        let mut filters = HashMap::new();
+        filters.insert("until".to_string(), vec![format!("{}h", hours)]);
+        filters
+    });
+    let options = PruneBuildOptions {
+        keep_storage: keep_bytes.map(|b| b.min(i64::MAX as u64) as i64),
+        filters,
+        ..Default::default()
+    };
+    client.require(ApiFeature::BuildCache)?;
+    let res = client.inner.prune_build(Some(options)).await?;
+    Ok(CleanupResult { deleted: res.caches_deleted.unwrap_or_default(), reclaimed: reclaimed(res.space_reclaimed) })
+}
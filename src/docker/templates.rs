@@ -0,0 +1,46 @@
+//! Volumes and networks created from the templates in `config.toml`.
+
+use crate::docker::client::DockerClient;
+use crate::types::{NetworkTemplate, Result, VolumeTemplate};
+use bollard::models::{Ipam, IpamConfig, NetworkCreateRequest, VolumeCreateOptions};
+
+/// Creates the volume. Like `docker volume create`, this succeeds without
+/// changes when a volume of that name already exists with the same driver.
+pub async fn create_volume(client: &DockerClient, template: &VolumeTemplate) -> Result<String> {
+    let options = VolumeCreateOptions {
+        name: Some(template.name.clone()),
+        driver: template.driver.clone(),
+        driver_opts: Some(template.options.clone()),
+        labels: Some(template.labels.clone()),
+        ..Default::default()
+    };
+    let volume = client.inner.create_volume(options).await?;
+    Ok(volume.name)
+}
+
+/// Creates the network and returns its ID; fails if the name is taken.
+pub async fn create_network(client: &DockerClient, template: &NetworkTemplate) -> Result<String> {
+    let ipam = (!template.subnets.is_empty()).then(|| Ipam {
+        config: Some(template.subnets.iter()
+            .map(|s| IpamConfig {
+                subnet: Some(s.subnet.clone()),
+                gateway: s.gateway.clone(),
+                ip_range: s.ip_range.clone(),
+                ..Default::default()
+            })
+            .collect()),
+        ..Default::default()
+    });
+    let request = NetworkCreateRequest {
+        name: template.name.clone(),
+        driver: template.driver.clone(),
+        internal: Some(template.internal),
+        attachable: Some(template.attachable),
+        ipam,
+        options: Some(template.options.clone()),
+        labels: Some(template.labels.clone()),
+        ..Default::default()
+    };
+    let response = client.inner.create_network(request).await?;
+    Ok(response.id)
+}
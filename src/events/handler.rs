@@ -3,9 +3,11 @@ use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScree
 use crossterm::execute;
 use ratatui::{Terminal, backend::Backend};
 use std::time::{Duration, Instant};
-use crate::app::App;
+use crate::app::{App, Modal};
+use crate::audit::ExecSession;
 use crate::ui::draw;
 use crate::events::key_bindings::handle_key_events;
+use crate::events::mouse::handle_mouse_event;
 use crate::types::Result;
 
 pub async fn run_event_loop<B: Backend + std::io::Write>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
@@ -30,6 +32,7 @@ pub async fn run_event_loop<B: Backend + std::io::Write>(terminal: &mut Terminal
                     // However, if we want quick details, we can do it here.
                     // For now, details are triggered by Enter key as per requirements.
                 }
+                crate::app::View::Top | crate::app::View::Services | crate::app::View::Stacks | crate::app::View::Nodes | crate::app::View::System => {}
             }
             needs_fetch = false;
         }
@@ -42,37 +45,92 @@ pub async fn run_event_loop<B: Backend + std::io::Write>(terminal: &mut Terminal
             }
         }
 
+        app.check_idle();
+        app.run_due_actions().await;
+        if app.take_notifications() {
+            terminal.backend_mut().write_all(b"\x07")?;
+            std::io::Write::flush(terminal.backend_mut())?;
+        }
+
         // Draw UI
         terminal.draw(|f| {
             draw(f, app);
         })?;
 
+        // Manual refresh runs after a frame with the spinner has been drawn
+        if app.pending_refresh {
+            app.refresh_current_view().await;
+            needs_fetch = true;
+            continue;
+        }
+
+        if app.resume_after_reconnect().await {
+            needs_fetch = true;
+        }
+
         // Poll for events
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let ev = event::read()?;
+            if let Event::Mouse(mouse) = ev {
+                // Moving the mouse is enough to uncover, but doesn't act on what's beneath
+                if !app.note_input() {
+                    handle_mouse_event(mouse, app).await;
+                }
+            }
+            if let Event::Key(key) = ev {
                 if key.kind == KeyEventKind::Press {
-                    if handle_key_events(key.code, app, &mut last_selection_change, &mut needs_fetch).await {
-                        break;
+                    if app.note_input() {
+                        continue;
+                    }
+                    if handle_key_events(key, app, &mut last_selection_change, &mut needs_fetch).await {
+                        // Queued removals and prunes would silently never run
+                        if app.pending_actions.is_empty() {
+                            break;
+                        }
+                        app.push_modal(Modal::QuitConfirm(app.pending_actions.len()));
                     }
 
                     // Check for exec request
-                    if let Some(container_id) = app.should_exec.take() {
+                    if let Some((container_id, command)) = app.should_exec.take() {
+                        let profile = app.exec_profile_for(&container_id);
+                        let docker = app.docker_for(&container_id);
+                        // Nothing runs unless the session made it into the audit log
+                        let audit = app.config.read().unwrap().exec_audit.clone();
+                        let mut session = match ExecSession::start(&audit, &docker.endpoint, &app.container_label(&container_id), &command) {
+                            Ok(session) => session,
+                            Err(e) => {
+                                App::log_error(&app.error_log, "exec audit", format!("{:#}", e));
+                                app.show_toast(format!("Exec refused: {:#}", e), true);
+                                continue;
+                            }
+                        };
                         // Restore terminal
                         disable_raw_mode()?;
                         execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
                         terminal.show_cursor()?;
                         
                         // Run exec
-                        if let Err(e) = crate::docker::exec::exec_interactive_shell(&app.docker, &container_id).await {
+                        let result = crate::docker::exec::exec_interactive_shell(&docker, &container_id, &command, profile.as_ref(), session.take_transcript()).await;
+                        if let Err(e) = &result {
+                            // Outside the alternate screen here, so printing is safe and visible
                             eprintln!("Exec error: {}", e);
+                            App::log_error(&app.error_log, "exec", e.to_string());
                             tokio::time::sleep(Duration::from_secs(2)).await;
                         }
+                        if let Err(e) = session.finish(result.map_err(|e| e.to_string())) {
+                            App::log_error(&app.error_log, "exec audit", format!("{:#}", e));
+                        }
                         
                         // Setup terminal again
                         enable_raw_mode()?;
                         execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
                         terminal.hide_cursor()?;
                         terminal.clear()?;
+                        // Drop input meant for the shell that crossterm still has queued
+                        // (notably key releases on Windows consoles)
+                        while event::poll(Duration::ZERO)? {
+                            event::read()?;
+                        }
                         
                         // Force refresh
                         app.refresh_containers().await?;
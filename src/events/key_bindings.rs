@@ -1,78 +1,32 @@
-use crossterm::event::KeyCode;
-use crate::app::{App, View, Focus};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::docker::client::ApiFeature;
+use crate::docker::migrate::MigrateOptions;
+use crate::app::{join_container_path, parse_log_time, port_warning, App, ContainerEdit, DeferredAction, GroupBy, Modal, StateFilter, PruneStep, View, Focus};
+use crate::types::{ChaosAction, CleanupKind, ContainerAction, FsChangeKind, NetemRule, NodeAvailability};
+use crate::ui::widgets::form::FormOutcome;
+use crate::ui::widgets::text_input::parse_label_assignments;
 use std::time::Instant;
 
-pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_change: &mut Instant, needs_fetch: &mut bool) -> bool {
-    // 0. Handle Health Log Dialog
-    if app.show_health_log_dialog {
-        match key {
-            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('E') => {
-                app.show_health_log_dialog = false;
-            }
-            _ => {}
-        }
-        return false;
-    }
-
-    // 1. Handle Pull Dialog (Input)
-    if app.show_pull_dialog {
-        match key {
-            KeyCode::Esc => app.show_pull_dialog = false,
-            KeyCode::Enter => {
-                if !app.pull_input.is_empty() {
-                    let image = app.pull_input.clone();
-                    app.start_pull_image(image);
-                }
-            }
-            KeyCode::Backspace => {
-                app.pull_input.pop();
-            }
-            KeyCode::Char(c) => {
-                app.pull_input.push(c);
-            }
-            _ => {}
-        }
-        return false;
-    }
+pub async fn handle_key_events(event: KeyEvent, app: &mut App, last_selection_change: &mut Instant, needs_fetch: &mut bool) -> bool {
+    let key = event.code;
 
-    // 2. Handle Delete Confirmation
-    if app.show_delete_confirm {
-        match key {
-            KeyCode::Char('y') | KeyCode::Enter => {
-                let force = app.pending_delete_force;
-                let _ = app.remove_current_image(force).await;
-                app.show_delete_confirm = false;
-            }
-            KeyCode::Esc | KeyCode::Char('n') => {
-                app.show_delete_confirm = false;
-            }
-            _ => {}
-        }
-        return false;
-    }
+    // Any key press dismisses the quick-info popup; 'o' re-opens it below
+    let had_preview = app.preview.take().is_some();
 
-    // 3. Handle Help
-    if app.show_help {
-        match key {
-            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
-                app.show_help = false;
-                app.current_help_tab = crate::types::HelpTab::Keybindings;
-                app.help_scroll = 0; // Reset scroll
-            }
-            KeyCode::Tab => {
-                app.current_help_tab = match app.current_help_tab {
-                    crate::types::HelpTab::Keybindings => crate::types::HelpTab::Wiki,
-                    crate::types::HelpTab::Wiki => crate::types::HelpTab::Keybindings,
-                };
-                app.help_scroll = 0; // Reset scroll on tab switch
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                app.help_scroll = app.help_scroll.saturating_add(1);
-            }
-            KeyCode::Up | KeyCode::Char('k') => {
-                app.help_scroll = app.help_scroll.saturating_sub(1);
+    // 0. Modals: the top of the stack gets every key, Esc always closes it
+    if let Some(modal) = app.top_modal().cloned() {
+        if key == KeyCode::Esc {
+            // Cancelling a search also drops its filter
+            if matches!(modal, Modal::ContainerSearch) {
+                app.clear_container_filter();
+                *needs_fetch = true;
             }
-            _ => {}
+            app.pop_modal();
+        } else if matches!(modal, Modal::QuitConfirm(_)) && key == KeyCode::Char('y') {
+            app.pending_actions.clear();
+            return true;
+        } else {
+            handle_modal_key(modal, event, app, needs_fetch).await;
         }
         return false;
     }
@@ -80,22 +34,39 @@ pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_chang
     // 4. Global Keys
     match key {
         KeyCode::Char('?') => {
-            app.show_help = true;
+            app.push_modal(Modal::Help);
             return false;
         }
         KeyCode::Char('q') => return true,
-        KeyCode::BackTab | KeyCode::Char('v') => {
-            if app.current_view == View::Containers {
-                app.current_view = View::Images;
-                // Trigger details fetch for initial selection if switching to images
-                app.trigger_image_details();
-            } else {
-                app.current_view = View::Containers;
-            }
+        KeyCode::Char('z') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.undo_last();
+            return false;
+        }
+        KeyCode::Char('!') => {
+            app.toggle_error_console();
+            return false;
+        }
+        KeyCode::Char('C') => {
+            app.open_context_picker();
+            return false;
+        }
+        KeyCode::Char('G') => {
+            app.open_workspace_picker();
+            return false;
+        }
+        KeyCode::Char('I') => {
+            app.open_diagnostics().await;
+            return false;
+        }
+        // Shift+Tab cycles panes in the containers view, so it only switches views elsewhere
+        k if k == KeyCode::Char('v') || (k == KeyCode::BackTab && app.current_view != View::Containers) => {
+            let view = if k == KeyCode::BackTab { app.current_view.prev() } else { app.current_view.next() };
+            app.enter_view(view).await;
             *needs_fetch = true;
             return false;
         }
-        KeyCode::Char('t') | KeyCode::Char('T') => {
+        // With the logs pane focused, T jumps to a time instead
+        KeyCode::Char('t') | KeyCode::Char('T') if !(key == KeyCode::Char('T') && app.current_view == View::Containers && app.focus.is(Focus::Logs)) => {
             {
                 let mut config = app.config.write().unwrap();
                 config.turbo_mode = !config.turbo_mode;
@@ -133,11 +104,7 @@ pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_chang
             return false;
         }
         KeyCode::Char('R') => {
-            let _ = app.refresh_containers().await;
-            if app.current_view == View::Images {
-                let _ = app.refresh_images().await;
-            }
-            *needs_fetch = true;
+            app.request_refresh();
             return false;
         }
         KeyCode::Char('P') => {
@@ -190,61 +157,84 @@ pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_chang
         _ => {}
     }
 
+    if is_mutating(app.current_view, key) && app.deny_read_only("this action") {
+        return false;
+    }
+
     // 5. View Specific Keys
     match app.current_view {
         View::Containers => {
             match key {
-                KeyCode::Esc => return true, 
-                KeyCode::Tab => {
-                    app.focus = match app.focus {
-                        Focus::ContainerList => Focus::Logs,
-                        Focus::Logs => Focus::ContainerList,
-                    };
+                KeyCode::Esc if !had_preview && !app.marked.is_empty() => app.marked.clear(),
+                KeyCode::Char(' ') => app.toggle_mark(),
+                KeyCode::Char('*') => app.toggle_pin(),
+                KeyCode::Char('-') => app.toggle_hidden(),
+                KeyCode::Char('.') => {
+                    app.toggle_show_hidden();
+                    *needs_fetch = true;
+                }
+                KeyCode::Char('A') => app.toggle_mark_all(),
+                KeyCode::Char('r') if !app.marked.is_empty() => app.run_marked_action(ContainerAction::Restart),
+                KeyCode::Char('s') if !app.marked.is_empty() => app.run_marked_action(ContainerAction::Stop),
+                KeyCode::Char('S') if !app.marked.is_empty() => app.run_marked_action(ContainerAction::Start),
+                KeyCode::Char('d') if !app.marked.is_empty() => app.push_modal(Modal::RemoveMarkedConfirm(app.marked.len())),
+                KeyCode::Esc if !had_preview && !app.container_filter.is_empty() => {
+                    app.clear_container_filter();
+                    *needs_fetch = true;
+                }
+                KeyCode::Esc => return !had_preview,
+                KeyCode::Char('/') if app.focus.is(Focus::ContainerList) => app.push_modal(Modal::ContainerSearch),
+                KeyCode::Char('n') if !app.container_filter.is_empty() => {
+                    app.select_search_match(1);
+                    *needs_fetch = true;
+                }
+                KeyCode::Char('N') if !app.container_filter.is_empty() => {
+                    app.select_search_match(-1);
+                    *needs_fetch = true;
+                }
+                KeyCode::Char('T') if app.focus.is(Focus::Logs) => app.open_jump_to_time_dialog(),
+                KeyCode::Char(':') => {
+                    app.bulk_command.clear();
+                    app.push_modal(Modal::BulkCommand);
                 }
+                KeyCode::Tab => app.cycle_focus(true),
+                KeyCode::BackTab => app.cycle_focus(false),
                 KeyCode::Down | KeyCode::Char('j') => {
-                    match app.focus {
+                    match app.focus.current() {
                         Focus::ContainerList => {
                             app.next();
                             *last_selection_change = Instant::now();
                             *needs_fetch = true;
                         }
-                        Focus::Logs => {
-                            app.auto_scroll = false;
-                            let logs_len = app.selected_container_logs.read().unwrap().len();
-                            if logs_len > 0 {
-                                let i = match app.logs_state.selected() {
-                                    Some(i) => {
-                                        if i >= logs_len - 1 { logs_len - 1 } else { i + 1 }
-                                    },
-                                    None => 0,
-                                };
-                                app.logs_state.select(Some(i));
-                            }
-                        }
+                        Focus::Logs => scroll_logs(app, true),
+                        Focus::Details => app.details_scroll = app.details_scroll.saturating_add(1),
+                        Focus::Graphs => {}
                     }
                 },
                 KeyCode::Up | KeyCode::Char('k') => {
-                    match app.focus {
+                    match app.focus.current() {
                         Focus::ContainerList => {
                             app.previous();
                             *last_selection_change = Instant::now();
                             *needs_fetch = true;
                         }
-                        Focus::Logs => {
-                            app.auto_scroll = false;
-                            let logs_len = app.selected_container_logs.read().unwrap().len();
-                            if logs_len > 0 {
-                                let i = match app.logs_state.selected() {
-                                    Some(i) => {
-                                        if i == 0 { 0 } else { i - 1 }
-                                    },
-                                    None => 0,
-                                };
-                                app.logs_state.select(Some(i));
-                            }
-                        }
+                        Focus::Logs => scroll_logs(app, false),
+                        Focus::Details => app.details_scroll = app.details_scroll.saturating_sub(1),
+                        Focus::Graphs => {}
                     }
                 },
+                KeyCode::Char('g') if app.focus.is(Focus::Details) => app.details_scroll = 0,
+                KeyCode::Char('c') if app.focus.is(Focus::Details) => app.check_clock(),
+                KeyCode::Char('g') if app.focus.is(Focus::ContainerList) => app.open_group_by_dialog(),
+                KeyCode::Enter if app.focus.is(Focus::ContainerList) && app.group_by.is_some() => {
+                    app.toggle_selected_group();
+                    *needs_fetch = true;
+                }
+                KeyCode::Char('o') if !had_preview => {
+                    if let Some(row) = app.table_state.selected() {
+                        app.show_preview(row);
+                    }
+                }
                 KeyCode::Char('r') => {
                     let _ = app.restart_container().await;
                     let _ = app.refresh_containers().await;
@@ -264,11 +254,7 @@ pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_chang
                     let _ = app.unpause_container().await;
                 }
                 KeyCode::Char('e') => {
-                    if let Some(container) = app.selected_container() {
-                        if container.state.to_lowercase() == "running" {
-                            app.should_exec = Some(container.id);
-                        }
-                    }
+                    app.open_exec_chooser();
                 }
                 KeyCode::Char('h') => {
                     app.toggle_health_filter();
@@ -280,20 +266,26 @@ pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_chang
                 }
                 KeyCode::Char('E') => {
                     if let Some(c) = app.selected_container() {
+                        if let Err(e) = app.docker_for(&c.id).require(ApiFeature::HealthChecks) {
+                            app.show_toast(e.to_string(), true);
+                            return false;
+                        }
                         let health = app.container_health.read().unwrap();
                         if let Some(h) = health.get(&c.id) {
-                            if let Some(output) = &h.last_check_output {
-                                app.health_log_content = output.clone();
-                                app.show_health_log_dialog = true;
-                            } else {
-                                app.health_log_content = "No output available.".to_string();
-                                app.show_health_log_dialog = true;
-                            }
+                            let content = h.last_check_output.clone()
+                                .unwrap_or_else(|| "No output available.".to_string());
+                            drop(health);
+                            app.push_modal(Modal::HealthLog(content));
                         }
                     }
                 }
-                KeyCode::Char('d') => {
-                    let _ = app.remove_container().await;
+                KeyCode::Char('d') => app.remove_selected_container(),
+                KeyCode::Char('c') => {
+                    if app.selected_project().is_some() {
+                        app.push_modal(Modal::ProjectMenu);
+                    } else {
+                        app.show_toast("Selected container is not part of a compose project".to_string(), true);
+                    }
                 }
                 KeyCode::Char('f') => {
                     app.toggle_filter();
@@ -303,60 +295,81 @@ pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_chang
                 KeyCode::Char('a') => {
                     app.auto_scroll = !app.auto_scroll;
                 }
-                KeyCode::Char('J') => {
-                    app.auto_scroll = false;
-                    let logs_len = app.selected_container_logs.read().unwrap().len();
-                    if logs_len > 0 {
-                        let i = match app.logs_state.selected() {
-                            Some(i) => {
-                                if i >= logs_len - 1 { logs_len - 1 } else { i + 1 }
-                            },
-                            None => 0,
-                        };
-                        app.logs_state.select(Some(i));
+                KeyCode::Char('l') => app.open_timeline(),
+                KeyCode::Char('i') => app.open_signal_dialog().await,
+                KeyCode::Char('w') => app.open_stdin_dialog().await,
+                KeyCode::Char('y') => app.open_run_command().await,
+                KeyCode::Char('Y') => app.open_compose_snippet().await,
+                KeyCode::Char('b') => app.open_label_editor().await,
+                KeyCode::Char('D') => app.open_duplicate_dialog().await,
+                KeyCode::F(2) => app.open_rename_dialog(),
+                KeyCode::F(4) => app.open_edit_dialog().await,
+                KeyCode::F(n @ 5..=8) => app.toggle_state_filter(StateFilter::ALL[usize::from(n - 5)]).await,
+                KeyCode::Char('W') => app.open_commit_dialog(),
+                KeyCode::Char('Z') => app.open_netem_dialog(),
+                KeyCode::Char('Q') => app.open_migrate_dialog(),
+                KeyCode::Char('F') => app.open_export_filesystem_dialog(),
+                KeyCode::Char('z') => app.open_incident_dialog(),
+                KeyCode::Char('B') => app.open_file_browser().await,
+                KeyCode::Char('O') => app.copy_commit_url().await,
+                KeyCode::Char('V') => app.open_fs_diff().await,
+                KeyCode::Char('U') => app.open_processes(),
+                KeyCode::Char('x') => app.open_export_stats_dialog(),
+                KeyCode::Char('X') => app.export_snapshot(),
+                KeyCode::Char('L') => {
+                    app.toggle_project_logs();
+                    *needs_fetch = true;
+                }
+                KeyCode::Char('J') => scroll_logs(app, true),
+                KeyCode::Char('K') => scroll_logs(app, false),
+                _ => {}
+            }
+        },
+        View::Top => {
+            match key {
+                KeyCode::Esc => return true,
+                KeyCode::Down | KeyCode::Char('j') => app.move_top_selection(1),
+                KeyCode::Up | KeyCode::Char('k') => app.move_top_selection(-1),
+                KeyCode::PageDown => app.move_top_selection(10),
+                KeyCode::PageUp => app.move_top_selection(-10),
+                KeyCode::Tab => app.toggle_top_sort(),
+                KeyCode::Enter => app.open_top_selection().await,
+                KeyCode::Char('s') => {
+                    if let Some(c) = app.top_selected_container() {
+                        app.run_bulk_action(c.name, vec![c.id], ContainerAction::Stop);
                     }
                 }
                 KeyCode::Char('K') => {
-                    app.auto_scroll = false;
-                    let logs_len = app.selected_container_logs.read().unwrap().len();
-                    if logs_len > 0 {
-                        let i = match app.logs_state.selected() {
-                            Some(i) => {
-                                if i == 0 { 0 } else { i - 1 }
-                            },
-                            None => 0,
-                        };
-                        app.logs_state.select(Some(i));
+                    if let Some(c) = app.top_selected_container() {
+                        app.send_signal(&c.id, &c.name, "SIGKILL").await;
                     }
                 }
                 _ => {}
             }
-        },
+        }
         View::Images => {
             match key {
                 KeyCode::Esc => return true,
                 KeyCode::Down | KeyCode::Char('j') => {
                     app.next_image();
-                    app.trigger_image_details();
+                    app.trigger_image_details(false);
                 },
                 KeyCode::Up | KeyCode::Char('k') => {
                     app.previous_image();
-                    app.trigger_image_details();
+                    app.trigger_image_details(false);
                 },
                 KeyCode::Char('p') => {
-                    app.show_pull_dialog = true;
                     app.pull_input.clear();
+                    app.push_modal(Modal::PullImage);
                 },
                 KeyCode::Char('d') => {
-                     app.show_delete_confirm = true;
-                     app.pending_delete_force = false;
+                     app.push_modal(Modal::DeleteImageConfirm { force: false });
                 },
                 KeyCode::Char('D') => {
-                     app.show_delete_confirm = true;
-                     app.pending_delete_force = true;
+                     app.push_modal(Modal::DeleteImageConfirm { force: true });
                 },
                 KeyCode::Enter | KeyCode::Char(' ') => {
-                    app.trigger_image_details();
+                    app.trigger_image_details(true);
                 },
                 KeyCode::Char('f') => {
                     let current = app.show_dangling.load(std::sync::atomic::Ordering::Relaxed);
@@ -367,9 +380,874 @@ pub async fn handle_key_events(key: KeyCode, app: &mut App, last_selection_chang
                      app.cycle_sort();
                      let _ = app.refresh_images().await;
                 },
+                KeyCode::Char('O') => app.copy_commit_url().await,
+                _ => {}
+            }
+        }
+        View::Services => {
+            match key {
+                KeyCode::Esc => return true,
+                KeyCode::Down | KeyCode::Char('j') => app.next_service(),
+                KeyCode::Up | KeyCode::Char('k') => app.previous_service(),
+                KeyCode::Char('u') => app.open_service_update_dialog(),
+                KeyCode::Char('b') => {
+                    if let Some(service) = app.selected_service().cloned() {
+                        app.start_service_update(service.id, service.name, None);
+                    }
+                }
+                _ => {}
+            }
+        }
+        View::Stacks => {
+            match key {
+                KeyCode::Esc => return true,
+                KeyCode::Down | KeyCode::Char('j') => app.next_stack(),
+                KeyCode::Up | KeyCode::Char('k') => app.previous_stack(),
+                KeyCode::Char('d') => {
+                    if let Some(stack) = app.selected_stack() {
+                        let name = stack.name.clone();
+                        app.push_modal(Modal::RemoveStackConfirm(name));
+                    }
+                }
+                _ => {}
+            }
+        }
+        View::System => {
+            match key {
+                KeyCode::Esc => return true,
+                KeyCode::Down | KeyCode::Char('j') => app.next_cleanup_item(),
+                KeyCode::Up | KeyCode::Char('k') => app.previous_cleanup_item(),
+                KeyCode::Enter => app.run_selected_cleanup(),
+                KeyCode::Char('p') => app.open_prune_wizard(),
+                KeyCode::Char('c') => app.open_build_cache_prune_dialog(),
+                KeyCode::Char('n') => app.open_template_dialog(),
+                _ => {}
+            }
+        }
+        View::Nodes => {
+            match key {
+                KeyCode::Esc => return true,
+                KeyCode::Down | KeyCode::Char('j') => app.next_node(),
+                KeyCode::Up | KeyCode::Char('k') => app.previous_node(),
+                KeyCode::Char('d') => app.confirm_drain_selected_node(),
+                KeyCode::Char('a') => app.set_selected_node_availability(NodeAvailability::Active).await,
                 _ => {}
             }
         }
     }
     false
 }
+
+fn scroll_logs(app: &mut App, down: bool) {
+    app.auto_scroll = false;
+    let logs_len = app.selected_container_logs.read().unwrap().len();
+    if logs_len > 0 {
+        let i = match app.logs_state.selected() {
+            Some(i) if down => (i + 1).min(logs_len - 1),
+            Some(i) => i.saturating_sub(1),
+            None => 0,
+        };
+        app.logs_state.select(Some(i));
+    }
+}
+
+async fn handle_modal_key(modal: Modal, event: KeyEvent, app: &mut App, needs_fetch: &mut bool) {
+    let key = event.code;
+
+    // Help can be layered over any modal that doesn't take text input
+    if key == KeyCode::Char('?') && !matches!(modal, Modal::Help | Modal::PullImage | Modal::ContainerSearch | Modal::BulkCommand) {
+        app.push_modal(Modal::Help);
+        return;
+    }
+
+    match modal {
+        Modal::HealthLog(_) => {
+            if matches!(key, KeyCode::Char('q') | KeyCode::Char('E')) {
+                app.pop_modal();
+            }
+        }
+        Modal::ProjectMenu => {
+            if matches!(key, KeyCode::Char('S' | 's' | 'r' | 'd' | 'n' | 'x')) && app.deny_read_only("changing the project") {
+                app.pop_modal();
+                return;
+            }
+            let action = match key {
+                KeyCode::Char('S') => Some(ContainerAction::Start),
+                KeyCode::Char('s') => Some(ContainerAction::Stop),
+                KeyCode::Char('r') => Some(ContainerAction::Restart),
+                KeyCode::Char('d') => Some(ContainerAction::Remove),
+                _ => None,
+            };
+            app.pop_modal();
+            if let Some(action) = action {
+                app.run_project_action(action);
+                *needs_fetch = true;
+            } else if key == KeyCode::Char('f') {
+                app.open_compose_file();
+            } else if key == KeyCode::Char('n') {
+                app.open_scale_dialog();
+            } else if key == KeyCode::Char('x') {
+                app.open_chaos_dialog();
+            }
+        }
+        Modal::ScaleService { project, service, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::ScaleService { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let target = match app.top_modal() {
+                    Some(Modal::ScaleService { form, .. }) => form.text_value("replicas").trim().parse().unwrap_or(0),
+                    _ => return,
+                };
+                app.pop_modal();
+                app.start_scale_service(project, service, target);
+            }
+        }
+        Modal::UpdateService { service_id, service_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::UpdateService { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let image = match app.top_modal() {
+                    Some(Modal::UpdateService { form, .. }) => form.text_value("image").trim().to_string(),
+                    _ => return,
+                };
+                app.pop_modal();
+                app.start_service_update(service_id, service_name, Some(image));
+            }
+        }
+        Modal::ExportStats { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::ExportStats { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (minutes, to_file) = match app.top_modal() {
+                    Some(Modal::ExportStats { form, .. }) => (
+                        form.text_value("minutes").trim().parse().unwrap_or(5),
+                        form.select_value("destination").as_deref() == Some("File"),
+                    ),
+                    _ => return,
+                };
+                app.pop_modal();
+                app.export_stats(&container_id, &container_name, minutes, to_file);
+            }
+        }
+        Modal::IncidentReport { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::IncidentReport { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let to_file = match app.top_modal() {
+                    Some(Modal::IncidentReport { form, .. }) => form.select_value("destination").as_deref() == Some("File"),
+                    _ => return,
+                };
+                app.pop_modal();
+                app.export_incident(&container_id, &container_name, to_file).await;
+            }
+        }
+        Modal::PruneBuildCache { .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::PruneBuildCache { form }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (keep_mb, hours) = match app.top_modal() {
+                    Some(Modal::PruneBuildCache { form }) => (
+                        form.text_value("keep").trim().parse().ok(),
+                        form.text_value("age").trim().parse().ok(),
+                    ),
+                    _ => return,
+                };
+                app.pop_modal();
+                app.defer("Prune build cache".to_string(), DeferredAction::PruneBuildCache { keep_mb, older_than_hours: hours });
+            }
+        }
+        Modal::CreateFromTemplate { .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::CreateFromTemplate { form }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let choice = match app.top_modal() {
+                    Some(Modal::CreateFromTemplate { form }) => form.select_value("template").unwrap_or_default(),
+                    _ => return,
+                };
+                app.pop_modal();
+                app.start_create_from_template(choice);
+            }
+        }
+        Modal::TextViewer { content, .. } => {
+            let max = content.lines().count().saturating_sub(1) as u16;
+            match key {
+                KeyCode::Char('q') => {
+                    app.pop_modal();
+                }
+                KeyCode::Char('c') => match crate::clipboard::copy_to_clipboard(&content) {
+                    Ok(()) => app.show_toast("Copied to clipboard".to_string(), false),
+                    Err(e) => app.show_toast(format!("Copy failed: {}", e), true),
+                },
+                KeyCode::Down | KeyCode::Char('j') => app.viewer_scroll = (app.viewer_scroll + 1).min(max),
+                KeyCode::Up | KeyCode::Char('k') => app.viewer_scroll = app.viewer_scroll.saturating_sub(1),
+                KeyCode::PageDown => app.viewer_scroll = (app.viewer_scroll + 20).min(max),
+                KeyCode::PageUp => app.viewer_scroll = app.viewer_scroll.saturating_sub(20),
+                KeyCode::Home | KeyCode::Char('g') => app.viewer_scroll = 0,
+                KeyCode::End | KeyCode::Char('G') => app.viewer_scroll = max,
+                _ => {}
+            }
+        }
+        Modal::SendSignal { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::SendSignal { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let signal = match app.top_modal() {
+                    Some(Modal::SendSignal { form, .. }) => {
+                        let custom = form.text_value("custom").trim().to_uppercase();
+                        if custom.is_empty() { form.select_value("signal").unwrap_or_default() } else { custom }
+                    }
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("sending signals") {
+                    app.send_signal(&container_id, &container_name, &signal).await;
+                }
+            }
+        }
+        Modal::ExecCustom { container_id, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::ExecCustom { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let command = match app.top_modal() {
+                    Some(Modal::ExecCustom { form, .. }) => form.text_value("command").trim().to_string(),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("exec") {
+                    app.should_exec = Some((container_id, command));
+                }
+            }
+        }
+        Modal::SendStdin { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::SendStdin { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (line, file) = match app.top_modal() {
+                    Some(Modal::SendStdin { form, .. }) => (form.text_value("line"), form.text_value("file").trim().to_string()),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("writing to stdin") {
+                    app.send_to_stdin(&container_id, &container_name, &line, &file).await;
+                }
+            }
+        }
+        Modal::EditLabels { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::EditLabels { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (set, remove) = match app.top_modal() {
+                    Some(Modal::EditLabels { form, .. }) => (
+                        parse_label_assignments(&form.text_value("set")),
+                        form.text_value("remove").split_whitespace().map(str::to_string).collect(),
+                    ),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("changing labels") {
+                    app.start_label_edit(container_id, container_name, set, remove);
+                }
+            }
+        }
+        Modal::EditContainer { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::EditContainer { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let edit = match app.top_modal() {
+                    Some(Modal::EditContainer { form, .. }) => ContainerEdit {
+                        image: form.text_value("image").trim().to_string(),
+                        env_set: parse_label_assignments(&form.text_value("env_set")),
+                        env_remove: form.text_value("env_remove").split_whitespace().map(str::to_string).collect(),
+                        ports: form.text_value("ports"),
+                    },
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("recreating containers") {
+                    app.start_container_edit(container_id, container_name, edit);
+                }
+            }
+        }
+        Modal::Duplicate { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::Duplicate { form, published, local, .. }) => {
+                    let outcome = form.handle_key(event);
+                    form.set_note("port_warning", port_warning(&form.text_value("ports"), published, *local));
+                    outcome
+                }
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (name, ports, start) = match app.top_modal() {
+                    Some(Modal::Duplicate { form, .. }) => (form.text_value("name").trim().to_string(), form.text_value("ports"), form.checkbox_value("start")),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("creating containers") {
+                    app.start_duplicate(container_id, container_name, name, ports, start);
+                }
+            }
+        }
+        Modal::Rename { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::Rename { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let name = match app.top_modal() {
+                    Some(Modal::Rename { form, .. }) => form.text_value("name").trim().to_string(),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("renaming containers") {
+                    app.start_rename(container_id, container_name, name);
+                }
+            }
+        }
+        Modal::JumpToTime { .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::JumpToTime { form }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let time = match app.top_modal() {
+                    Some(Modal::JumpToTime { form }) => parse_log_time(&form.text_value("time")),
+                    _ => return,
+                };
+                match time {
+                    Ok(time) => {
+                        app.pop_modal();
+                        app.jump_logs_to_time(time);
+                    }
+                    // The dialog stays open to correct the time
+                    Err(e) => app.show_toast(e, true),
+                }
+            }
+        }
+        Modal::GroupBy { .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::GroupBy { form }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let group_by = match app.top_modal() {
+                    Some(Modal::GroupBy { form }) => GroupBy::parse(&form.text_value("key")),
+                    _ => return,
+                };
+                app.pop_modal();
+                app.set_group_by(group_by);
+            }
+        }
+        Modal::Migrate { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::Migrate { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (target, options) = match app.top_modal() {
+                    Some(Modal::Migrate { form, .. }) => (form.select_value("target").unwrap_or_default(), MigrateOptions {
+                        commit: form.checkbox_value("commit"),
+                        pull: form.checkbox_value("pull"),
+                        stop_source: form.checkbox_value("stop"),
+                    }),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("migrating containers") {
+                    app.start_migrate(container_id, container_name, target, options);
+                }
+            }
+        }
+        Modal::Commit { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::Commit { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (image, comment, author) = match app.top_modal() {
+                    Some(Modal::Commit { form, .. }) => (form.text_value("image").trim().to_string(), form.text_value("comment"), form.text_value("author")),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("committing containers") {
+                    app.start_commit(container_id, container_name, image, comment, author);
+                }
+            }
+        }
+        Modal::Netem { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::Netem { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (rule, revert_after) = match app.top_modal() {
+                    Some(Modal::Netem { form, .. }) => {
+                        let number = |key| form.text_value(key).trim().parse::<u64>().ok();
+                        let rule = (form.select_value("action").as_deref() == Some("Apply")).then(|| NetemRule {
+                            delay_ms: number("delay").unwrap_or(0),
+                            jitter_ms: number("jitter").unwrap_or(0),
+                            loss_percent: form.text_value("loss").trim().parse().unwrap_or(0.0),
+                            rate_kbit: number("rate").filter(|r| *r > 0),
+                        });
+                        (rule, number("revert"))
+                    }
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("shaping network traffic") {
+                    app.start_netem(container_id, container_name, rule, revert_after);
+                }
+            }
+        }
+        Modal::Chaos { project, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::Chaos { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (action, target, confirmed) = match app.top_modal() {
+                    Some(Modal::Chaos { form, .. }) => {
+                        let secs = form.text_value("secs").trim().parse().unwrap_or(10);
+                        let action = form.select_value("action").and_then(|name| ChaosAction::from_name(&name, secs));
+                        let target = form.select_value("target").filter(|t| t != "Random container");
+                        (action, target, form.text_value("confirm").trim() == project)
+                    }
+                    _ => return,
+                };
+                if !confirmed {
+                    app.show_toast(format!("Type '{}' to confirm", project), true);
+                    return;
+                }
+                app.pop_modal();
+                if let Some(action) = action {
+                    if !app.deny_read_only("chaos testing") {
+                        app.start_chaos(project, action, target);
+                    }
+                }
+            }
+        }
+        Modal::ExportFilesystem { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::ExportFilesystem { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let path = match app.top_modal() {
+                    Some(Modal::ExportFilesystem { form, .. }) => form.text_value("path").trim().to_string(),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !path.is_empty() {
+                    app.start_export_filesystem(container_id, container_name, path);
+                }
+            }
+        }
+        Modal::Files { path, entries, selected, .. } => match key {
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(Modal::Files { selected, .. }) = app.top_modal_mut() {
+                    *selected = (*selected + 1).min(entries.len().saturating_sub(1));
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(Modal::Files { selected, .. }) = app.top_modal_mut() {
+                    *selected = selected.saturating_sub(1);
+                }
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => {
+                // Symlinks are tried as directories; a link to a file just fails to list
+                if let Some(entry) = entries.get(selected).filter(|e| e.is_dir() || e.link_target.is_some()) {
+                    app.browse_to(join_container_path(&path, &entry.name)).await;
+                }
+            }
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') if path != "/" => {
+                let parent = path.trim_end_matches('/').rsplit_once('/').map(|(parent, _)| parent).unwrap_or("");
+                app.browse_to(if parent.is_empty() { "/".to_string() } else { parent.to_string() }).await;
+            }
+            KeyCode::Char('r') => app.browse_to(path).await,
+            KeyCode::Char('d') => app.download_selected_file(),
+            KeyCode::Char('u') => app.open_upload_dialog(),
+            KeyCode::Char('q') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::Processes { list, .. } => {
+            let count = list.read().unwrap().as_ref().and_then(|r| r.as_ref().ok()).map_or(0, |l| l.processes.len());
+            match key {
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if let Some(Modal::Processes { selected, .. }) = app.top_modal_mut() {
+                        *selected = (*selected + 1).min(count.saturating_sub(1));
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if let Some(Modal::Processes { selected, .. }) = app.top_modal_mut() {
+                        *selected = selected.saturating_sub(1);
+                    }
+                }
+                KeyCode::Char('r') => app.refresh_processes().await,
+                KeyCode::Char('q') => {
+                    app.pop_modal();
+                }
+                _ => {}
+            }
+        }
+        Modal::FsDiff { changes, filter, .. } => {
+            let shown = changes.iter().filter(|c| filter.is_none_or(|kind| c.kind == kind)).count();
+            match key {
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if let Some(Modal::FsDiff { selected, .. }) = app.top_modal_mut() {
+                        *selected = (*selected + 1).min(shown.saturating_sub(1));
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    if let Some(Modal::FsDiff { selected, .. }) = app.top_modal_mut() {
+                        *selected = selected.saturating_sub(1);
+                    }
+                }
+                KeyCode::Char('f') | KeyCode::Tab => {
+                    if let Some(Modal::FsDiff { filter, selected, .. }) = app.top_modal_mut() {
+                        *filter = FsChangeKind::next_filter(*filter);
+                        *selected = 0;
+                    }
+                }
+                KeyCode::Char('r') => app.open_fs_diff().await,
+                KeyCode::Char('q') => {
+                    app.pop_modal();
+                }
+                _ => {}
+            }
+        }
+        Modal::Upload { container_id, container_name, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::Upload { form, .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let (source, dest) = match app.top_modal() {
+                    Some(Modal::Upload { form, .. }) => (form.text_value("source").trim().to_string(), form.text_value("dest").trim().to_string()),
+                    _ => return,
+                };
+                app.pop_modal();
+                if !app.deny_read_only("uploading files") {
+                    app.request_upload(container_id, container_name, source, dest).await;
+                }
+            }
+        }
+        Modal::UploadOverwriteConfirm { container_id, container_name, source, dest } => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.pop_modal();
+                app.start_upload(container_id, container_name, source, dest);
+            }
+            KeyCode::Char('n') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::WorkspacePicker { workspaces, selected } => match key {
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(Modal::WorkspacePicker { selected, .. }) = app.top_modal_mut() {
+                    *selected = (*selected + 1).min(workspaces.len().saturating_sub(1));
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(Modal::WorkspacePicker { selected, .. }) = app.top_modal_mut() {
+                    *selected = selected.saturating_sub(1);
+                }
+            }
+            KeyCode::Enter => {
+                app.pop_modal();
+                if let Some(workspace) = workspaces.into_iter().nth(selected) {
+                    app.apply_workspace(workspace).await;
+                    *needs_fetch = true;
+                }
+            }
+            KeyCode::Char('q') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::ExecChooser { container_id, container_name, commands, selected } => match key {
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(Modal::ExecChooser { selected, .. }) = app.top_modal_mut() {
+                    // One past the commands is the custom entry
+                    *selected = (*selected + 1).min(commands.len());
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(Modal::ExecChooser { selected, .. }) = app.top_modal_mut() {
+                    *selected = selected.saturating_sub(1);
+                }
+            }
+            KeyCode::Char('c') => {
+                app.pop_modal();
+                app.open_exec_custom_dialog(container_id, container_name);
+            }
+            KeyCode::Enter => {
+                app.pop_modal();
+                match commands.into_iter().nth(selected) {
+                    Some(command) => app.should_exec = Some((container_id, command)),
+                    None => app.open_exec_custom_dialog(container_id, container_name),
+                }
+            }
+            KeyCode::Char('q') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::ContextPicker { contexts, selected } => match key {
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(Modal::ContextPicker { selected, .. }) = app.top_modal_mut() {
+                    *selected = (*selected + 1).min(contexts.len().saturating_sub(1));
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(Modal::ContextPicker { selected, .. }) = app.top_modal_mut() {
+                    *selected = selected.saturating_sub(1);
+                }
+            }
+            KeyCode::Enter => {
+                app.pop_modal();
+                if let Some(context) = contexts.into_iter().nth(selected) {
+                    app.switch_context(context).await;
+                    *needs_fetch = true;
+                }
+            }
+            KeyCode::Char('q') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::Diagnostics(report) => {
+            let max = report.lines().count().saturating_sub(1) as u16;
+            match key {
+                KeyCode::Char('q') => {
+                    app.pop_modal();
+                }
+                KeyCode::Char('c') => match crate::clipboard::copy_to_clipboard(&report) {
+                    Ok(()) => app.show_toast("Copied diagnostics report to clipboard".to_string(), false),
+                    Err(e) => app.show_toast(format!("Copy failed: {}", e), true),
+                },
+                KeyCode::Char('r') => app.open_diagnostics().await,
+                KeyCode::Down | KeyCode::Char('j') => app.viewer_scroll = (app.viewer_scroll + 1).min(max),
+                KeyCode::Up | KeyCode::Char('k') => app.viewer_scroll = app.viewer_scroll.saturating_sub(1),
+                KeyCode::PageDown => app.viewer_scroll = (app.viewer_scroll + 20).min(max),
+                KeyCode::PageUp => app.viewer_scroll = app.viewer_scroll.saturating_sub(20),
+                _ => {}
+            }
+        }
+        Modal::ContainerSearch => match key {
+            KeyCode::Enter => {
+                app.pop_modal();
+            }
+            _ => {
+                if app.container_filter.handle_key(event) {
+                    app.update_filtered_containers();
+                    app.select_best_search_match();
+                    *needs_fetch = true;
+                }
+            }
+        },
+        Modal::Timeline { container_id, .. } => {
+            let len = app.container_timelines.read().unwrap().get(&container_id).map_or(0, |t| t.len());
+            let max = (len + 1) as u16;
+            match key {
+                KeyCode::Char('q') => {
+                    app.pop_modal();
+                }
+                KeyCode::Down | KeyCode::Char('j') => app.viewer_scroll = (app.viewer_scroll + 1).min(max),
+                KeyCode::Up | KeyCode::Char('k') => app.viewer_scroll = app.viewer_scroll.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Modal::PullImage => match key {
+            KeyCode::Enter if app.pull_input.is_valid() => {
+                app.pull_input.commit_history();
+                let image = app.pull_reference();
+                app.start_pull_image(image);
+                app.pop_modal();
+            }
+            KeyCode::Tab => app.cycle_pull_target(),
+            KeyCode::BackTab => app.cycle_pull_host(),
+            _ => {
+                app.pull_input.handle_key(event);
+            }
+        },
+        Modal::RemoveMarkedConfirm(_) => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.pop_modal();
+                app.run_marked_action(ContainerAction::Remove);
+                *needs_fetch = true;
+            }
+            KeyCode::Char('n') | KeyCode::Char('q') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::QuitConfirm(_) => match key {
+            KeyCode::Char('r') => {
+                app.pop_modal();
+                let now = Instant::now();
+                for pending in app.pending_actions.iter_mut() {
+                    pending.due = now;
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('q') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::BulkCommand => match key {
+            KeyCode::Enter if app.bulk_command.is_valid() => app.resolve_bulk_command().await,
+            _ => {
+                app.bulk_command.handle_key(event);
+            }
+        },
+        Modal::BulkCommandConfirm { command, action, targets } => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.pop_modal();
+                if !app.deny_read_only("Bulk commands") {
+                    let ids = targets.into_iter().map(|(id, _)| id).collect();
+                    app.container_action(format!("'{}'", command), ids, action);
+                    *needs_fetch = true;
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('q') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::DeleteImageConfirm { force } => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.pop_modal();
+                app.remove_current_image(force);
+            }
+            KeyCode::Char('n') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::PruneWizard { step: PruneStep::Select(_), estimates, .. } => {
+            let outcome = match app.top_modal_mut() {
+                Some(Modal::PruneWizard { step: PruneStep::Select(form), .. }) => form.handle_key(event),
+                _ => return,
+            };
+            if outcome == FormOutcome::Submitted {
+                let kinds: Vec<CleanupKind> = match app.top_modal() {
+                    Some(Modal::PruneWizard { step: PruneStep::Select(form), .. }) => estimates.iter()
+                        .map(|(kind, _)| *kind)
+                        .filter(|kind| form.checkbox_value(kind.key()))
+                        .collect(),
+                    _ => return,
+                };
+                if kinds.is_empty() {
+                    app.show_toast("Nothing selected to prune".to_string(), true);
+                } else if let Some(Modal::PruneWizard { step, .. }) = app.top_modal_mut() {
+                    *step = PruneStep::Confirm(kinds);
+                }
+            }
+        }
+        Modal::PruneWizard { step: PruneStep::Confirm(kinds), .. } => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                // The summary step opens again once the prune has run
+                app.pop_modal();
+                let labels: Vec<String> = kinds.iter().map(|k| k.label().to_lowercase()).collect();
+                app.defer(format!("Prune {}", labels.join(", ")), DeferredAction::Prune(kinds));
+            }
+            KeyCode::Char('n') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::PruneWizard { step: PruneStep::Summary(_), .. } => {
+            if matches!(key, KeyCode::Enter | KeyCode::Char('q')) {
+                app.pop_modal();
+            }
+        }
+        Modal::CleanupVolumesConfirm(_) => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.pop_modal();
+                let kind = CleanupKind::UnusedVolumes;
+                app.defer(format!("Clean up {}", kind.label().to_lowercase()), DeferredAction::Cleanup(kind));
+            }
+            KeyCode::Char('n') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::DrainNodeConfirm(node) => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.pop_modal();
+                app.set_node_availability(node, NodeAvailability::Drain).await;
+            }
+            KeyCode::Char('n') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::RemoveStackConfirm(name) => match key {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.pop_modal();
+                app.defer(format!("Remove stack {}", name), DeferredAction::RemoveStack(name));
+            }
+            KeyCode::Char('n') => {
+                app.pop_modal();
+            }
+            _ => {}
+        },
+        Modal::Help => match key {
+            KeyCode::Char('q') | KeyCode::Char('?') => {
+                app.pop_modal();
+            }
+            KeyCode::Tab => {
+                app.current_help_tab = match app.current_help_tab {
+                    crate::types::HelpTab::Keybindings => crate::types::HelpTab::Wiki,
+                    crate::types::HelpTab::Wiki => crate::types::HelpTab::Keybindings,
+                };
+                app.help_scroll = 0; // Reset scroll on tab switch
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.help_scroll = app.help_scroll.saturating_add(1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.help_scroll = app.help_scroll.saturating_sub(1);
+            }
+            _ => {}
+        },
+    }
+}
+
+/// Keys that change containers, images or the swarm, refused in `--read-only` mode.
+fn is_mutating(view: View, key: KeyCode) -> bool {
+    matches!(
+        (view, key),
+        (View::Containers, KeyCode::Char('r' | 's' | 'S' | 'p' | 'u' | 'e' | 'd' | 'w' | 'b' | 'B' | 'D' | 'W' | 'Z' | 'Q') | KeyCode::F(2) | KeyCode::F(4))
+            | (View::Top, KeyCode::Char('s' | 'K'))
+            | (View::Images, KeyCode::Char('p' | 'd' | 'D'))
+            | (View::Services, KeyCode::Char('u' | 'b'))
+            | (View::Stacks, KeyCode::Char('d'))
+            | (View::System, KeyCode::Enter | KeyCode::Char('p' | 'c' | 'n'))
+            | (View::Nodes, KeyCode::Char('d' | 'a'))
+    )
+}
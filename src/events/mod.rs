@@ -1,2 +1,3 @@
 pub mod handler;
 pub mod key_bindings;
+pub mod mouse;
@@ -0,0 +1,25 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crate::app::{App, View};
+
+pub async fn handle_mouse_event(event: MouseEvent, app: &mut App) {
+    if app.current_view != View::Containers || app.top_modal().is_some() {
+        return;
+    }
+
+    match event.kind {
+        MouseEventKind::Moved => match app.container_row_at(event.column, event.row) {
+            Some(row) => app.show_preview(row),
+            None => app.preview = None,
+        },
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            app.preview = None;
+        }
+        MouseEventKind::Down(MouseButton::Left) if event.row == app.status_bar_row => {
+            let badge = app.status_badges.iter().find(|(columns, _)| columns.contains(&event.column)).map(|(_, filter)| *filter);
+            if let Some(filter) = badge {
+                app.toggle_state_filter(filter).await;
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::Level;
+
+/// Sends `tracing` events at `level` and above to `path` (appending), for
+/// attaching to bug reports. Nothing is logged unless this is called, since
+/// the terminal itself is taken by the UI.
+pub fn init(path: &Path, level: &str) -> Result<()> {
+    let level: Level = level.parse()
+        .with_context(|| format!("Invalid log level '{}' (use error, warn, info, debug or trace)", level))?;
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+
+    tracing_subscriber::fmt()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_max_level(level)
+        .with_target(false)
+        .try_init()
+        .map_err(|e| anyhow::anyhow!(e))?;
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), %level, "dockyard started");
+    Ok(())
+}
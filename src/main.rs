@@ -1,7 +1,17 @@
 pub mod app;
+pub mod audit;
+pub mod backup;
+pub mod cli;
+pub mod clipboard;
+pub mod command;
 pub mod config;
 pub mod docker;
 pub mod events;
+pub mod logging;
+pub mod ratelimit;
+pub mod registry;
+pub mod scan;
+pub mod search;
 pub mod types;
 pub mod ui;
 
@@ -12,23 +22,62 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
+use clap::Parser;
 use std::io;
-use std::env;
+use std::path::PathBuf;
 
 use crate::app::App;
+use crate::cli::{Cli, Command};
+use crate::config::load_config;
+use crate::docker::client::DockerClient;
+use crate::docker::export::{export_running_containers, SNAPSHOT_SHARED_FILES};
 use crate::events::handler::run_event_loop;
 
+/// Snapshot directory used when `export` is given no path.
+pub fn default_snapshot_dir() -> PathBuf {
+    PathBuf::from(format!("dockyard-snapshot-{}", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Parse args
-    let args: Vec<String> = env::args().collect();
-    let mut stats_interval = 3;
-    for i in 0..args.len() {
-        if args[i] == "--stats-interval" && i + 1 < args.len() {
-             if let Ok(val) = args[i+1].parse::<u64>() {
-                 stats_interval = val.max(1).min(10); // Clamp to 1-10s range per requirements
-             }
+    let cli = Cli::parse();
+
+    // `--log-file PATH [--log-level LEVEL]` writes a debug log for bug reports
+    if let Some(path) = &cli.log_file {
+        logging::init(path, &cli.log_level)?;
+    }
+
+    // `dockyard backup [FILE]` and `dockyard restore FILE` move the settings and caches between machines and exit
+    match &cli.command {
+        Some(Command::Backup { file }) => {
+            let file = file.clone().unwrap_or_else(|| PathBuf::from(format!("dockyard-backup-{}.tar", chrono::Local::now().format("%Y%m%d-%H%M%S"))));
+            let written = backup::backup(&file)?;
+            println!("Backed up {} to {}", written.join(", "), file.display());
+            return Ok(());
+        }
+        Some(Command::Restore { file }) => {
+            for path in backup::restore(file)? {
+                println!("Restored {}", path.display());
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // `dockyard export [DIR]` writes run scripts for all running containers and exits
+    if let Some(Command::Export { dir }) = cli.command {
+        let dir = dir.unwrap_or_else(default_snapshot_dir);
+        let config = load_config().unwrap_or_default();
+        let host = cli.host.or(config.host.filter(|_| cli.context.is_none()));
+        let (client, _) = DockerClient::resolve(host.as_deref(), cli.context.as_deref(), config.tls.as_ref())?;
+        let mut client = client.with_timeouts(config.timeouts);
+        client.detect_engine().await;
+        let written = export_running_containers(&client, &dir).await?;
+        for path in &written {
+            println!("{}", path.display());
         }
+        println!("Exported {} container(s) to {}", written.len().saturating_sub(SNAPSHOT_SHARED_FILES), dir.display());
+        return Ok(());
     }
 
     // Setup terminal
@@ -38,8 +87,25 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Initialize app
-    let mut app = App::new(stats_interval).await?;
+    // Initialize app in the state asked for on the command line
+    let mut app = App::new(cli.stats_interval, cli.host, cli.context, cli.low_memory).await?;
+    app.read_only |= cli.read_only;
+    if let Some(rate) = cli.refresh {
+        app.set_refresh_flag(rate);
+    }
+    if let Some(name) = cli.filter {
+        app.container_filter.set_value(name);
+        app.update_filtered_containers();
+    }
+    if let Some(view) = cli.view {
+        app.enter_view(view.into()).await;
+    }
+    if let Some(name) = cli.workspace {
+        match app.find_workspace(&name) {
+            Some(workspace) => app.apply_workspace(workspace).await,
+            None => app.show_toast(format!("No workspace named {} in config.toml", name), true),
+        }
+    }
 
     // Run event loop
     let res = run_event_loop(&mut terminal, &mut app).await;
@@ -0,0 +1,76 @@
+//! Docker Hub pull rate limit. Pulls go through the daemon, so their response
+//! headers never reach dockyard; instead the limit is read the way Docker
+//! documents it, from a HEAD request for a preview manifest, which doesn't
+//! count as a pull. Anonymous limits are per IP, shared with the daemon when
+//! it runs on this machine or behind the same address.
+
+use anyhow::{Context, Result};
+use http_body_util::{BodyExt, Empty};
+use hyper::header::{HeaderMap, AUTHORIZATION};
+use hyper::{Method, Request, StatusCode};
+use std::time::Duration;
+
+use crate::registry::https_client;
+use crate::types::{ProxyConfig, RateLimit};
+
+const TOKEN_URL: &str = "https://auth.docker.io/token?service=registry.docker.io&scope=repository:ratelimitpreview/test:pull";
+const MANIFEST_URL: &str = "https://registry-1.docker.io/v2/ratelimitpreview/test/manifests/latest";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Asks Docker Hub how many anonymous pulls are left in the current window,
+/// through `proxy` when one applies.
+pub async fn check_hub(proxy: &ProxyConfig) -> Result<RateLimit> {
+    tokio::time::timeout(REQUEST_TIMEOUT, fetch(proxy))
+        .await
+        .context("Docker Hub did not answer within 10s")?
+}
+
+async fn fetch(proxy: &ProxyConfig) -> Result<RateLimit> {
+    let client = https_client(proxy)?;
+
+    let response = client.get(TOKEN_URL.parse()?).await.context("Failed to reach auth.docker.io")?;
+    if response.status() != StatusCode::OK {
+        anyhow::bail!("auth.docker.io returned {}", response.status());
+    }
+    let body = response.into_body().collect().await?.to_bytes();
+    let token: serde_json::Value = serde_json::from_slice(&body).context("Unexpected token response")?;
+    let token = token["token"].as_str().context("No token in auth.docker.io response")?;
+
+    let request = Request::builder()
+        .method(Method::HEAD)
+        .uri(MANIFEST_URL)
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Empty::new())?;
+    let response = client.request(request).await.context("Failed to reach registry-1.docker.io")?;
+    parse_headers(response.headers()).context("Docker Hub reported no rate limit")
+}
+
+/// Reads `ratelimit-limit: 100;w=21600` and `ratelimit-remaining: 76;w=21600`.
+fn parse_headers(headers: &HeaderMap) -> Option<RateLimit> {
+    let field = |name: &str| -> Option<(u32, Option<u64>)> {
+        let value = headers.get(name)?.to_str().ok()?;
+        let mut parts = value.split(';');
+        let count = parts.next()?.trim().parse().ok()?;
+        let window = parts.find_map(|p| p.trim().strip_prefix("w=")?.parse().ok());
+        Some((count, window))
+    };
+    let (limit, window) = field("ratelimit-limit")?;
+    let (remaining, _) = field("ratelimit-remaining")?;
+    Some(RateLimit {
+        limit,
+        remaining,
+        window_secs: window.unwrap_or_default(),
+        checked_at: chrono::Utc::now().timestamp(),
+    })
+}
+
+/// Whether pulling `reference` counts against the Docker Hub limit, i.e. it
+/// names no registry or names Docker Hub itself.
+pub fn is_docker_hub(reference: &str) -> bool {
+    match reference.split_once('/') {
+        Some((host, _)) if host.contains('.') || host.contains(':') || host == "localhost" => {
+            matches!(host, "docker.io" | "index.docker.io" | "registry-1.docker.io")
+        }
+        _ => true,
+    }
+}
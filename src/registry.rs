@@ -0,0 +1,541 @@
+//! Minimal registry (distribution API) client: resolves an image reference to
+//! its manifest and downloads blobs, anonymously or with the bearer tokens
+//! registries hand out for public repositories. Used where dockyard has to talk
+//! to a registry itself instead of going through the daemon.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use http_body_util::{BodyExt, Empty};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{ACCEPT, AUTHORIZATION, LOCATION, WWW_AUTHENTICATE};
+use hyper::http::uri::Scheme;
+use hyper::{Request, Response, StatusCode, Uri};
+use hyper_rustls::HttpsConnector;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use serde::Deserialize;
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{self, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tower_service::Service;
+
+use crate::types::ProxyConfig;
+
+pub type HttpClient = Client<HttpsConnector<ProxyConnector>, Empty<Bytes>>;
+
+const DOCKER_HUB: &str = "registry-1.docker.io";
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+const MAX_REDIRECTS: usize = 5;
+
+/// HTTPS client trusting the system's root certificates, going through the
+/// proxy `proxy` (or the environment) names for each URL; plain HTTP is only
+/// used for URLs that ask for it.
+pub fn https_client(proxy: &ProxyConfig) -> Result<HttpClient> {
+    let mut http = HttpConnector::new();
+    http.enforce_http(false);
+    let connector = ProxyConnector { http, proxies: Arc::new(Proxies::resolve(proxy)?) };
+    let https = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_native_roots()
+        .context("Failed to load system root certificates")?
+        .https_or_http()
+        .enable_http1()
+        .wrap_connector(connector);
+    Ok(Client::builder(TokioExecutor::new()).build(https))
+}
+
+/// The proxies in effect: the config's, or else the environment's.
+struct Proxies {
+    http: Option<Uri>,
+    https: Option<Uri>,
+    no_proxy: Vec<NoProxy>,
+}
+
+/// One `NO_PROXY` entry.
+#[derive(Debug)]
+enum NoProxy {
+    /// `*`: every host.
+    All,
+    /// A lowercase host and its subdomains, without a leading `*.` or `.`.
+    Domain(String),
+    /// An address, or a network when it has a prefix length.
+    Network(IpAddr, u8),
+}
+
+impl NoProxy {
+    /// Parses an entry such as `registry.local:5000`, `*.corp`, `10.0.0.0/8`
+    /// or `[::1]:5000`. Ports are dropped, so an entry covers every port.
+    fn parse(entry: &str) -> Option<Self> {
+        let entry = entry.trim().to_ascii_lowercase();
+        if entry == "*" {
+            return Some(NoProxy::All);
+        }
+        let (address, prefix) = match entry.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix.parse::<u8>().ok()?)),
+            None => (entry.as_str(), None),
+        };
+        // A bare IPv6 address has colons of its own, so only strip a port
+        // from bracketed addresses and names
+        let host = if address.parse::<IpAddr>().is_ok() {
+            address
+        } else if let Some(rest) = address.strip_prefix('[') {
+            rest.split_once(']').map_or(rest, |(host, _)| host)
+        } else {
+            address.rsplit_once(':').map_or(address, |(host, _)| host)
+        };
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            let max = if ip.is_ipv4() { 32 } else { 128 };
+            return Some(NoProxy::Network(ip, prefix.unwrap_or(max).min(max)));
+        }
+        let domain = host.trim_start_matches("*.").trim_start_matches('.');
+        (prefix.is_none() && !domain.is_empty()).then(|| NoProxy::Domain(domain.to_string()))
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        match self {
+            NoProxy::All => true,
+            NoProxy::Domain(domain) => {
+                host == domain || host.strip_suffix(domain.as_str()).is_some_and(|rest| rest.ends_with('.'))
+            }
+            NoProxy::Network(network, prefix) => match (host.parse::<IpAddr>(), network) {
+                (Ok(IpAddr::V4(ip)), IpAddr::V4(network)) => {
+                    let mask = u32::MAX.checked_shl(32 - u32::from(*prefix)).unwrap_or(0);
+                    u32::from(ip) & mask == u32::from(*network) & mask
+                }
+                (Ok(IpAddr::V6(ip)), IpAddr::V6(network)) => {
+                    let mask = u128::MAX.checked_shl(128 - u32::from(*prefix)).unwrap_or(0);
+                    u128::from(ip) & mask == u128::from(*network) & mask
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Proxies {
+    fn resolve(config: &ProxyConfig) -> Result<Self> {
+        let pick = |value: &Option<String>, vars: [&str; 2]| {
+            value.clone()
+                .or_else(|| vars.iter().find_map(|var| std::env::var(var).ok()))
+                .filter(|value| !value.trim().is_empty())
+        };
+        let parse = |value: Option<String>| -> Result<Option<Uri>> {
+            let Some(value) = value else { return Ok(None) };
+            let url = if value.contains("://") { value.trim().to_string() } else { format!("http://{}", value.trim()) };
+            let uri: Uri = url.parse().with_context(|| format!("Invalid proxy URL {}", value))?;
+            if uri.scheme() != Some(&Scheme::HTTP) {
+                anyhow::bail!("Proxy {} must be an http:// URL", value);
+            }
+            Ok(Some(uri))
+        };
+        Ok(Self {
+            http: parse(pick(&config.http, ["HTTP_PROXY", "http_proxy"]))?,
+            https: parse(pick(&config.https, ["HTTPS_PROXY", "https_proxy"]))?,
+            no_proxy: pick(&config.no_proxy, ["NO_PROXY", "no_proxy"])
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(NoProxy::parse)
+                .collect(),
+        })
+    }
+
+    /// The proxy for `uri`, unless `NO_PROXY` covers its host.
+    fn for_uri(&self, uri: &Uri) -> Option<&Uri> {
+        let proxy = if uri.scheme() == Some(&Scheme::HTTPS) { self.https.as_ref() } else { self.http.as_ref() }?;
+        let host = uri.host()?.trim_start_matches('[').trim_end_matches(']').to_ascii_lowercase();
+        let direct = self.no_proxy.iter().any(|entry| entry.matches(&host));
+        (!direct).then_some(proxy)
+    }
+}
+
+/// Connects directly, or through a proxy's CONNECT tunnel, inside which the
+/// TLS handshake then runs.
+#[derive(Clone)]
+pub struct ProxyConnector {
+    http: HttpConnector,
+    proxies: Arc<Proxies>,
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+impl Service<Uri> for ProxyConnector {
+    type Response = TokioIo<TcpStream>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut task::Context<'_>) -> Poll<std::result::Result<(), BoxError>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let Some(proxy) = self.proxies.for_uri(&dst).cloned() else {
+            let connecting = self.http.call(dst);
+            return Box::pin(async move { Ok(connecting.await?) });
+        };
+        let connecting = self.http.call(proxy.clone());
+        Box::pin(async move {
+            let stream = connecting.await?.into_inner();
+            Ok(TokioIo::new(tunnel(stream, &proxy, &dst).await?))
+        })
+    }
+}
+
+/// Asks the proxy on `stream` for a tunnel to `dst`'s host and port.
+async fn tunnel(mut stream: TcpStream, proxy: &Uri, dst: &Uri) -> Result<TcpStream> {
+    let host = dst.host().context("URL without a host")?;
+    let port = dst.port_u16().unwrap_or(if dst.scheme() == Some(&Scheme::HTTPS) { 443 } else { 80 });
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if let Some((userinfo, _)) = proxy.authority().and_then(|a| a.as_str().rsplit_once('@')) {
+        // `p%40ss` in the URL is the password `p@ss`
+        let (user, password) = userinfo.split_once(':').unwrap_or((userinfo, ""));
+        let mut credentials = percent_decode(user);
+        credentials.push(b':');
+        credentials.extend(percent_decode(password));
+        let credentials = base64::engine::general_purpose::STANDARD.encode(credentials);
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", credentials));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await.context("Failed to reach the proxy")?;
+
+    // One byte at a time, so nothing after the response head is consumed
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() > 8192 {
+            anyhow::bail!("The proxy sent an oversized response");
+        }
+        let mut byte = [0u8];
+        if stream.read(&mut byte).await? == 0 {
+            anyhow::bail!("The proxy closed the connection");
+        }
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let status = head.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        anyhow::bail!("The proxy refused a tunnel to {}:{}: {}", host, port, status);
+    }
+    Ok(stream)
+}
+
+/// Bytes of a URL component with its `%XX` escapes decoded.
+fn percent_decode(component: &str) -> Vec<u8> {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+/// An image reference split the way the registry API needs it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// Registry host to connect to, e.g. `registry-1.docker.io` or `localhost:5000`.
+    pub host: String,
+    /// Repository path on that registry, with `library/` for official images.
+    pub repository: String,
+    /// Tag or `sha256:` digest.
+    pub reference: String,
+    /// Name and tag the daemon would give the pulled image; None for digest references.
+    pub tag: Option<String>,
+}
+
+impl Reference {
+    pub fn parse(image: &str) -> Result<Self> {
+        let (name, reference, tagged) = match image.split_once('@') {
+            Some((name, digest)) => (name, digest.to_string(), false),
+            None => match image.rsplit_once(':') {
+                Some((name, tag)) if !tag.contains('/') => (name, tag.to_string(), true),
+                _ => (image, "latest".to_string(), true),
+            },
+        };
+        if name.is_empty() || reference.is_empty() {
+            anyhow::bail!("Invalid image reference '{}'", image);
+        }
+        let (host, path) = match name.split_once('/') {
+            Some((host, rest)) if host.contains('.') || host.contains(':') || host == "localhost" => {
+                (host.to_string(), rest.to_string())
+            }
+            _ => ("docker.io".to_string(), name.to_string()),
+        };
+        let (host, repository) = match host.as_str() {
+            "docker.io" | "index.docker.io" | DOCKER_HUB if !path.contains('/') => (DOCKER_HUB.to_string(), format!("library/{}", path)),
+            "docker.io" | "index.docker.io" => (DOCKER_HUB.to_string(), path),
+            _ => (host, path),
+        };
+        Ok(Self { host, repository, reference: reference.clone(), tag: tagged.then(|| format!("{}:{}", name, reference)) })
+    }
+
+    fn url(&self, kind: &str, reference: &str) -> String {
+        // Docker only talks plain HTTP to local registries unless configured otherwise
+        let scheme = if self.host.starts_with("localhost") || self.host.starts_with("127.0.0.1") { "http" } else { "https" };
+        format!("{}://{}/v2/{}/{}/{}", scheme, self.host, self.repository, kind, reference)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Descriptor {
+    pub digest: String,
+    pub size: u64,
+    #[serde(default)]
+    pub media_type: String,
+    /// Foreign layers are hosted elsewhere and not pushed to the registry.
+    #[serde(default)]
+    pub urls: Vec<String>,
+    #[serde(default)]
+    platform: Option<Platform>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Platform {
+    os: String,
+    architecture: String,
+    #[serde(default)]
+    variant: Option<String>,
+}
+
+/// An image manifest for a single platform.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Manifest {
+    pub config: Descriptor,
+    pub layers: Vec<Descriptor>,
+}
+
+#[derive(Deserialize)]
+struct Index {
+    manifests: Vec<Descriptor>,
+}
+
+/// An image dockyard can't download itself, but the daemon's own pull (with
+/// its credentials and foreign layer support) can.
+#[derive(Debug)]
+pub struct NeedsDaemonPull(pub String);
+
+impl std::fmt::Display for NeedsDaemonPull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NeedsDaemonPull {}
+
+/// A repository on a registry, holding whatever token it took to read it.
+pub struct Session {
+    client: HttpClient,
+    reference: Reference,
+    token: Option<String>,
+}
+
+impl Session {
+    pub fn new(reference: Reference, proxy: &ProxyConfig) -> Result<Self> {
+        Ok(Self { client: https_client(proxy)?, reference, token: None })
+    }
+
+    pub fn reference(&self) -> &Reference {
+        &self.reference
+    }
+
+    /// The manifest for `os`/`arch` (as the daemon reports them, e.g. `linux`/`amd64`),
+    /// picked from the image index when the image is multi-platform.
+    pub async fn manifest(&mut self, os: &str, arch: &str) -> Result<Manifest> {
+        let reference = self.reference.reference.clone();
+        let body = self.get_bytes(&self.reference.url("manifests", &reference), MANIFEST_TYPES).await?;
+        let value: serde_json::Value = serde_json::from_slice(&body).context("Unexpected manifest")?;
+        if value.get("manifests").is_none() {
+            return serde_json::from_value(value).context("Unsupported manifest format");
+        }
+
+        let index: Index = serde_json::from_value(value).context("Unexpected image index")?;
+        let (arch, variant) = match arch.split_once('/') {
+            Some((arch, variant)) => (arch, Some(variant)),
+            None => (arch, None),
+        };
+        let digest = index.manifests.iter()
+            .filter(|m| m.platform.as_ref().is_some_and(|p| p.os == os && p.architecture == arch))
+            .find(|m| variant.is_none() || m.platform.as_ref().and_then(|p| p.variant.as_deref()) == variant)
+            .map(|m| m.digest.clone())
+            .with_context(|| format!("No {}/{} image in {}", os, arch, self.reference.repository))?;
+        let body = self.get_bytes(&self.reference.url("manifests", &digest), MANIFEST_TYPES).await?;
+        serde_json::from_slice(&body).context("Unsupported manifest format")
+    }
+
+    pub async fn blob_bytes(&mut self, digest: &str) -> Result<Bytes> {
+        self.get_bytes(&self.reference.url("blobs", digest), "*/*").await
+    }
+
+    /// Starts downloading a blob; read it frame by frame from the body.
+    pub async fn blob(&mut self, digest: &str) -> Result<Incoming> {
+        let response = self.get(&self.reference.url("blobs", digest), "*/*").await?;
+        Ok(response.into_body())
+    }
+
+    async fn get_bytes(&mut self, url: &str, accept: &str) -> Result<Bytes> {
+        let response = self.get(url, accept).await?;
+        Ok(response.into_body().collect().await?.to_bytes())
+    }
+
+    /// GET with the session's token, fetching one when challenged and following
+    /// redirects to blob storage (which gets no credentials).
+    async fn get(&mut self, url: &str, accept: &str) -> Result<Response<Incoming>> {
+        let mut url: Uri = url.parse()?;
+        let mut authorized = true;
+        let mut challenged = false;
+        for _ in 0..MAX_REDIRECTS {
+            let mut request = Request::get(url.clone()).header(ACCEPT, accept);
+            if let (true, Some(token)) = (authorized, &self.token) {
+                request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+            }
+            let response = self.client.request(request.body(Empty::new())?).await
+                .with_context(|| format!("Failed to reach {}", url.host().unwrap_or_default()))?;
+            match response.status() {
+                StatusCode::UNAUTHORIZED if authorized && !challenged => {
+                    let challenge = response.headers().get(WWW_AUTHENTICATE)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string)
+                        .context("Registry requires authentication")?;
+                    self.token = Some(self.fetch_token(&challenge).await?);
+                    challenged = true;
+                }
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                    return Err(self.needs_credentials().into());
+                }
+                status if status.is_redirection() => {
+                    let location = response.headers().get(LOCATION)
+                        .and_then(|v| v.to_str().ok())
+                        .context("Redirect without a location")?;
+                    let next: Uri = location.parse()?;
+                    // Relative redirects stay on the registry
+                    url = if next.host().is_some() {
+                        authorized = false;
+                        next
+                    } else {
+                        Uri::builder()
+                            .scheme(url.scheme_str().unwrap_or("https"))
+                            .authority(url.authority().map(|a| a.as_str()).unwrap_or_default())
+                            .path_and_query(location)
+                            .build()?
+                    };
+                }
+                status if status.is_success() => return Ok(response),
+                StatusCode::NOT_FOUND => anyhow::bail!("{} not found on {}", url.path(), self.reference.host),
+                status => anyhow::bail!("{} returned {} for {}", self.reference.host, status, url.path()),
+            }
+        }
+        anyhow::bail!("Too many redirects fetching {}", url)
+    }
+
+    fn needs_credentials(&self) -> NeedsDaemonPull {
+        NeedsDaemonPull(format!("{} needs credentials, which only the daemon's own pull uses", self.reference.repository))
+    }
+
+    /// Anonymous token from a `Bearer realm="..",service="..",scope=".."` challenge.
+    async fn fetch_token(&self, challenge: &str) -> Result<String> {
+        let params = challenge.strip_prefix("Bearer ")
+            .with_context(|| format!("Unsupported registry authentication: {}", challenge))?;
+        let mut realm = None;
+        let mut query = Vec::new();
+        for param in split_params(params) {
+            let Some((key, value)) = param.split_once('=') else { continue };
+            let value = value.trim_matches('"');
+            match key.trim() {
+                "realm" => realm = Some(value.to_string()),
+                key @ ("service" | "scope") => query.push(format!("{}={}", key, value)),
+                _ => {}
+            }
+        }
+        let realm = realm.context("Registry challenge has no realm")?;
+        let url = if query.is_empty() { realm } else { format!("{}?{}", realm, query.join("&")) };
+
+        let response = self.client.get(url.parse()?).await.context("Failed to reach the registry's token service")?;
+        if !response.status().is_success() {
+            return Err(self.needs_credentials().into());
+        }
+        let body = response.into_body().collect().await?.to_bytes();
+        let token: serde_json::Value = serde_json::from_slice(&body).context("Unexpected token response")?;
+        token["token"].as_str()
+            .or_else(|| token["access_token"].as_str())
+            .map(str::to_string)
+            .context("No token in the registry's response")
+    }
+}
+
+/// Bytes a pull of `image` for `os`/`arch` transfers: the compressed layers
+/// and config as listed in the registry's manifest.
+pub async fn pull_size(image: &str, os: &str, arch: &str, proxy: &ProxyConfig) -> Result<u64> {
+    let fetch = async {
+        let mut session = Session::new(Reference::parse(image)?, proxy)?;
+        let manifest = session.manifest(os, arch).await?;
+        anyhow::Ok(manifest.config.size + manifest.layers.iter().map(|l| l.size).sum::<u64>())
+    };
+    tokio::time::timeout(Duration::from_secs(10), fetch)
+        .await
+        .context("The registry did not answer within 10s")?
+}
+
+/// Splits challenge parameters on commas outside quotes; scopes can contain commas.
+fn split_params(params: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in params.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                parts.push(&params[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&params[start..]);
+    parts
+}
+
+/// Token bucket limiting how fast downloaded bytes are consumed, with up to
+/// one second's worth of burst.
+pub struct Throttle {
+    bytes_per_sec: f64,
+    available: f64,
+    last: Instant,
+}
+
+impl Throttle {
+    pub fn new(kib_per_sec: u64) -> Self {
+        let bytes_per_sec = (kib_per_sec.max(1) * 1024) as f64;
+        Self { bytes_per_sec, available: bytes_per_sec, last: Instant::now() }
+    }
+
+    /// Waits until `bytes` fit within the rate.
+    pub async fn take(&mut self, bytes: usize) {
+        let now = Instant::now();
+        let refill = now.duration_since(self.last).as_secs_f64() * self.bytes_per_sec;
+        self.available = (self.available + refill).min(self.bytes_per_sec) - bytes as f64;
+        self.last = now;
+        if self.available < 0.0 {
+            tokio::time::sleep(Duration::from_secs_f64(-self.available / self.bytes_per_sec)).await;
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Image vulnerability scanning through the Trivy CLI. Results are cached on
+//! disk per image ID, so an image is only scanned again once it changes or
+//! its result expires.
+
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::process::Command;
+
+use crate::types::{ImageInfo, ScanSummary};
+
+pub fn cache_path() -> Option<PathBuf> {
+    ProjectDirs::from("com", "dockyard", "dockyard").map(|dirs| dirs.cache_dir().join("scans.json"))
+}
+
+/// Cached results by image ID; empty when there is no readable cache yet.
+pub fn load_cache() -> HashMap<String, ScanSummary> {
+    cache_path()
+        .and_then(|path| std::fs::read(path).ok())
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_cache(cache: &HashMap<String, ScanSummary>) -> Result<()> {
+    let path = cache_path().context("Failed to determine project directories")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, serde_json::to_vec(cache)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// What to hand the scanner: the first tag, or the ID of an untagged image.
+pub fn scan_reference(image: &ImageInfo) -> String {
+    image.repo_tags.iter()
+        .find(|t| *t != "<none>:<none>")
+        .cloned()
+        .unwrap_or_else(|| image.id.clone())
+}
+
+/// Runs `trivy image` on a local image and counts its vulnerabilities by severity.
+pub async fn scan_image(command: &str, reference: &str) -> Result<ScanSummary> {
+    let output = Command::new(command)
+        .args(["image", "--quiet", "--format", "json", "--scanners", "vuln", reference])
+        .kill_on_drop(true)
+        .output()
+        .await
+        .with_context(|| format!("Failed to run {}", command))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{} exited with {}: {}", command, output.status, stderr.lines().last().unwrap_or_default());
+    }
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .context("Unexpected scanner output")?;
+
+    let mut summary = ScanSummary { scanned_at: chrono::Utc::now().timestamp(), ..Default::default() };
+    let vulnerabilities = report["Results"].as_array().into_iter().flatten()
+        .flat_map(|result| result["Vulnerabilities"].as_array().into_iter().flatten());
+    for vulnerability in vulnerabilities {
+        match vulnerability["Severity"].as_str() {
+            Some("CRITICAL") => summary.critical += 1,
+            Some("HIGH") => summary.high += 1,
+            Some("MEDIUM") => summary.medium += 1,
+            _ => {}
+        }
+    }
+    Ok(summary)
+}
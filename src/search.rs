@@ -0,0 +1,92 @@
+//! Text matching shared by every search box, so they all agree on case rules.
+
+use serde::{Deserialize, Serialize};
+
+/// How letter case is treated when matching (`search_case` in `config.toml`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaseMode {
+    /// Ignore case unless the query contains an uppercase letter.
+    #[default]
+    Smart,
+    Insensitive,
+    Sensitive,
+}
+
+impl CaseMode {
+    fn is_sensitive(self, query: &str) -> bool {
+        match self {
+            CaseMode::Smart => query.chars().any(char::is_uppercase),
+            CaseMode::Insensitive => false,
+            CaseMode::Sensitive => true,
+        }
+    }
+}
+
+/// Byte range of the first occurrence of `query` in `text`, for highlighting.
+pub fn find_match(text: &str, query: &str, mode: CaseMode) -> Option<(usize, usize)> {
+    if query.is_empty() {
+        return None;
+    }
+    if mode.is_sensitive(query) {
+        return text.find(query).map(|start| (start, start + query.len()));
+    }
+    text.char_indices().find_map(|(start, _)| {
+        let mut rest = text[start..].char_indices();
+        for q in query.chars() {
+            let (_, c) = rest.next()?;
+            if !c.to_lowercase().eq(q.to_lowercase()) {
+                return None;
+            }
+        }
+        Some((start, rest.next().map_or(text.len(), |(i, _)| start + i)))
+    })
+}
+
+/// Characters that start a new word in names, images and IDs.
+const SEPARATORS: &str = "-_./:@ ";
+
+/// Fuzzy match: the characters of `query`, in order but not necessarily
+/// adjacent, in `text`. Returns a score, higher for better matches, and the
+/// byte offset of each matched character for highlighting. A plain substring
+/// match always outscores a scattered one; otherwise runs of adjacent
+/// characters and characters that start a word count extra, and gaps count
+/// against.
+pub fn fuzzy_match(text: &str, query: &str, mode: CaseMode) -> Option<(i64, Vec<usize>)> {
+    if query.is_empty() {
+        return None;
+    }
+    let sensitive = mode.is_sensitive(query);
+    let same = |a: char, b: char| if sensitive { a == b } else { a.to_lowercase().eq(b.to_lowercase()) };
+    let word_start = |i: usize| i == 0 || text[..i].chars().next_back().is_some_and(|c| SEPARATORS.contains(c));
+
+    if let Some((start, end)) = find_match(text, query, mode) {
+        let positions = text[start..end].char_indices().map(|(i, _)| start + i).collect();
+        let bonus = if word_start(start) { 50 } else { 0 };
+        return Some((1000 + bonus - start as i64, positions));
+    }
+
+    let mut positions = Vec::new();
+    let mut score = 0;
+    let mut wanted = query.chars().peekable();
+    let mut previous: Option<usize> = None;
+    for (i, c) in text.char_indices() {
+        let Some(&q) = wanted.peek() else { break };
+        if !same(c, q) {
+            continue;
+        }
+        score += 1;
+        if word_start(i) {
+            score += 8;
+        }
+        match previous {
+            Some(p) if text[p..].chars().next().map_or(0, char::len_utf8) + p == i => score += 5,
+            Some(p) => score -= ((i - p) as i64).min(10),
+            None => score -= (i as i64).min(10),
+        }
+        positions.push(i);
+        previous = Some(i);
+        wanted.next();
+    }
+    wanted.peek().is_none().then_some((score, positions))
+}
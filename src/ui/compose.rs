@@ -0,0 +1,45 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::ui::layout::centered_rect;
+
+pub fn render_project_menu(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let Some(project) = app.selected_project() else {
+        return;
+    };
+
+    let count = app.containers.read().unwrap()
+        .iter()
+        .filter(|c| c.compose_project() == Some(project.as_str()))
+        .count();
+
+    let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let lines = vec![
+        Line::from(format!("{} container(s) in this project", count)),
+        Line::from(""),
+        Line::from(vec![Span::styled("S", key_style), Span::raw("  Start all")]),
+        Line::from(vec![Span::styled("s", key_style), Span::raw("  Stop all")]),
+        Line::from(vec![Span::styled("r", key_style), Span::raw("  Restart all")]),
+        Line::from(vec![Span::styled("d", key_style), Span::raw("  Remove all (force)")]),
+        Line::from(vec![Span::styled("f", key_style), Span::raw("  View compose file")]),
+        Line::from(vec![Span::styled("n", key_style), Span::raw("  Scale selected service")]),
+        Line::from(vec![Span::styled("x", key_style), Span::raw("  Chaos testing…")]),
+        Line::from(""),
+        Line::from(Span::styled("Esc to cancel", Style::default().fg(Color::DarkGray))),
+    ];
+
+    let area = centered_rect(40, 40, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" Compose Project: {} ", project));
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -9,8 +9,8 @@ use ratatui::{
     Frame,
 };
 use ratatui::widgets::Clear;
-use crate::app::App;
-use crate::ui::layout::get_graphs_layout;
+use crate::app::{format_updated, App, Focus};
+use crate::ui::layout::{centered_rect, get_graphs_layout};
 use crate::types::{HealthStatus, StatsView};
 use ratatui::layout::{Constraint, Direction, Layout};
 
@@ -66,6 +66,7 @@ fn get_peak_percent(history: &[u64], limit: u64) -> f64 {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_enhanced_graph(
     f: &mut Frame,
     area: Rect,
@@ -76,10 +77,11 @@ fn render_enhanced_graph(
     datasets: Vec<Dataset>,
     y_max: f64,
     y_labels: Vec<String>,
+    focused: bool,
 ) {
     let mut block = Block::default()
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_type(if focused { BorderType::Thick } else { BorderType::Rounded })
         .border_style(Style::default().fg(current_val_color));
 
     // Title: Name (Left)
@@ -111,7 +113,7 @@ fn render_enhanced_graph(
         .y_axis(
             Axis::default()
                 .bounds([0.0, y_max])
-                .labels(y_labels.iter().map(|s| Span::raw(s)).collect::<Vec<_>>())
+                .labels(y_labels.iter().map(Span::raw).collect::<Vec<_>>())
                 .style(Style::default().fg(Color::DarkGray))
         );
         
@@ -203,14 +205,20 @@ pub fn render_container_details(f: &mut Frame<'_>, area: Rect, app: &App) {
         None
     };
 
+    let details_border = if app.focus.is(Focus::Details) {
+        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
     let block = Block::default()
         .borders(Borders::ALL)
         .title(" Details ")
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(details_border);
 
     let paragraph = Paragraph::new(details_text)
         .block(block)
-        .wrap(Wrap { trim: true });
+        .wrap(Wrap { trim: true })
+        .scroll((app.details_scroll, 0));
     
     f.render_widget(paragraph, text_area);
 
@@ -243,7 +251,8 @@ pub fn render_container_details(f: &mut Frame<'_>, area: Rect, app: &App) {
                 // Title construction
                 let cpu_title = Line::from(vec![
                     Span::raw("CPU "),
-                    Span::styled(format!("[Peak: {:.1}%]", cpu_peak), Style::default().fg(Color::DarkGray))
+                    Span::styled(format!("[Peak: {:.1}%] ", cpu_peak), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format_updated(stats.last_updated), Style::default().fg(Color::DarkGray)),
                 ]);
                 
                 let cpu_val_str = format!("{:.1}% {}", stats.cpu_percent, cpu_trend);
@@ -373,22 +382,21 @@ pub fn render_container_details(f: &mut Frame<'_>, area: Rect, app: &App) {
                 ];
 
                 // Render
-                render_enhanced_graph(f, cpu_area, cpu_title, cpu_val_str, cpu_color, is_cpu_critical, cpu_datasets, 100.0, vec!["0".into(), "50".into(), "100".into()]);
-                render_enhanced_graph(f, mem_area, mem_title, mem_val_str, mem_color, is_mem_critical, mem_datasets, 100.0, vec!["0".into(), "50".into(), "100".into()]);
+                let graphs_focused = app.focus.is(Focus::Graphs);
+                render_enhanced_graph(f, cpu_area, cpu_title, cpu_val_str, cpu_color, is_cpu_critical, cpu_datasets, 100.0, vec!["0".into(), "50".into(), "100".into()], graphs_focused);
+                render_enhanced_graph(f, mem_area, mem_title, mem_val_str, mem_color, is_mem_critical, mem_datasets, 100.0, vec!["0".into(), "50".into(), "100".into()], graphs_focused);
             }
         }
     }
 }
 
-pub fn render_health_log_dialog(f: &mut Frame, area: Rect, app: &App) {
-    if !app.show_health_log_dialog { return; }
-    
+pub fn render_health_log_dialog(f: &mut Frame, area: Rect, content: &str) {
     let block = Block::default()
         .title(" Health Check Output ")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow));
         
-    let paragraph = Paragraph::new(app.health_log_content.clone())
+    let paragraph = Paragraph::new(content.to_string())
         .block(block)
         .wrap(Wrap { trim: false });
         
@@ -396,23 +404,3 @@ pub fn render_health_log_dialog(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(Clear, area);
     f.render_widget(paragraph, area);
 }
-
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
@@ -1,13 +1,17 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style, Stylize},
-    widgets::{Block, Borders, Cell, Row, Table},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
     Frame,
     layout::Constraint,
 };
 use chrono::Utc;
-use crate::app::App;
-use crate::types::{HealthStatus, RefreshRate};
+use crate::app::{format_updated, App, ListRow, SortOrder};
+use crate::ui::layout::centered_rect;
+use crate::search::{fuzzy_match, CaseMode};
+use crate::command::SYNTAX;
+use crate::types::{ContainerAction, ContainerInfo, HealthStatus, RefreshRate};
 
 fn format_uptime(created: i64) -> String {
     let now = Utc::now().timestamp();
@@ -39,6 +43,114 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// `text` with the characters matching the search marked, so it's clear why a row is listed.
+fn highlight_match(text: String, query: &str, case: CaseMode, style: Style) -> Line<'static> {
+    let Some((_, positions)) = fuzzy_match(&text, query, case) else {
+        return Line::styled(text, style);
+    };
+    let marked = style.fg(Color::Black).bg(Color::Yellow);
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for (i, c) in text.char_indices() {
+        let char_style = if positions.contains(&i) { marked } else { style };
+        match spans.last_mut() {
+            Some(span) if span.style == char_style => span.content.to_mut().push(c),
+            _ => spans.push(Span::styled(c.to_string(), char_style)),
+        }
+    }
+    Line::from(spans)
+}
+
+/// Container list columns, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Name,
+    Host,
+    Status,
+    Health,
+    Image,
+    Uptime,
+    Stats,
+}
+
+const COLUMNS: [Column; 7] = [Column::Name, Column::Host, Column::Status, Column::Health, Column::Image, Column::Uptime, Column::Stats];
+
+/// Optional columns, hidden in this order as the list narrows so names and
+/// status keep room instead of every column turning into ellipses.
+const HIDE_ORDER: [Column; 5] = [Column::Uptime, Column::Image, Column::Health, Column::Host, Column::Stats];
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Name => "NAME",
+            Column::Host => "HOST",
+            Column::Status => "STATUS",
+            Column::Health => "HEALTH",
+            Column::Image => "IMG",
+            Column::Uptime => "UP",
+            Column::Stats => "CPU / MEM",
+        }
+    }
+
+    /// The header with an arrow when the list is sorted by this column.
+    fn sorted_header(self, sort: &SortOrder) -> String {
+        match (self, sort) {
+            (Column::Name, SortOrder::NameAsc) => "NAME ▲".to_string(),
+            // Both health orders put unhealthy containers first
+            (Column::Health, SortOrder::HealthDesc | SortOrder::HealthAsc) => "HEALTH ▼".to_string(),
+            // Newest first means the shortest uptimes first
+            (Column::Uptime, SortOrder::UptimeDesc | SortOrder::CreatedAsc) => "UP ▼".to_string(),
+            (Column::Uptime, SortOrder::CreatedDesc) => "UP ▲".to_string(),
+            (Column::Stats, SortOrder::CpuDesc) => "CPU ▼ / MEM".to_string(),
+            (Column::Stats, SortOrder::MemDesc) => "CPU / MEM ▼".to_string(),
+            _ => self.header().to_string(),
+        }
+    }
+
+    /// Narrowest width the column is still readable at.
+    fn min_width(self) -> u16 {
+        match self {
+            Column::Name => 16,
+            Column::Host => 8,
+            Column::Status => 10,
+            Column::Health => 11,
+            Column::Image => 15,
+            Column::Uptime => 6,
+            Column::Stats => 14,
+        }
+    }
+
+    /// Share of the width left once every visible column has its minimum.
+    fn weight(self) -> u16 {
+        match self {
+            Column::Name => 20,
+            Column::Host | Column::Status | Column::Uptime => 10,
+            Column::Health => 15,
+            Column::Image => 20,
+            Column::Stats => 25,
+        }
+    }
+}
+
+/// The columns that fit in `width` (inside the borders) and their widths.
+fn layout_columns(width: u16, multi_host: bool) -> (Vec<Column>, Vec<Constraint>) {
+    let mut visible: Vec<Column> = COLUMNS.into_iter().filter(|c| multi_host || *c != Column::Host).collect();
+    // The highlight symbol and one space between columns
+    let needed = |cols: &[Column]| cols.iter().map(|c| c.min_width()).sum::<u16>() + 2 + cols.len().saturating_sub(1) as u16;
+    for column in HIDE_ORDER {
+        if needed(&visible) <= width {
+            break;
+        }
+        visible.retain(|c| *c != column);
+    }
+
+    let spare = width.saturating_sub(needed(&visible));
+    let total_weight: u16 = visible.iter().map(|c| c.weight()).sum();
+    let widths = visible.iter()
+        .map(|c| Constraint::Length(c.min_width() + (u32::from(spare) * u32::from(c.weight()) / u32::from(total_weight.max(1))) as u16))
+        .collect();
+    (visible, widths)
+}
+
 pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
     // Ensure filtered list is up to date with any background changes
     app.update_filtered_containers();
@@ -51,9 +163,12 @@ pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
     let containers_lock = app.containers.read().unwrap();
     
     // Header cells - simplified for compact view if needed, but we have space
-    let header_cells = ["NAME", "STATUS", "HEALTH", "IMG", "UP", "CPU / MEM"]
+    // The HOST column only appears once extra hosts are configured
+    let multi_host = !app.remote_clients.is_empty();
+    let (columns, widths) = layout_columns(area.width.saturating_sub(2), multi_host);
+    let header_cells = columns
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()));
+        .map(|c| Cell::from(c.sorted_header(&app.container_sort)).style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()));
     let header = Row::new(header_cells).height(1);
     
     let stats_map = app.container_stats.read().unwrap();
@@ -85,13 +200,17 @@ pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
     }
 
     // Use filtered containers for display
-    let rows = app.filtered_containers.iter().map(|c| {
+    let query = app.container_filter.value();
+    let case = app.config.read().unwrap().search_case;
+    let shaped = app.netem_rules.read().unwrap();
+    let container_row = |c: &ContainerInfo| {
         let (status_symbol, status_color) = match c.state.as_str() {
             "running" => ("●", Color::Green),
             "exited" => ("■", Color::Red),
             "paused" => ("‖", Color::Yellow),
             _ => ("○", Color::Gray),
         };
+        let (restarts, storming) = app.restart_storm(&c.id);
 
         let uptime = if c.state == "running" {
             format_uptime(c.created)
@@ -123,11 +242,13 @@ pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
             Cell::from("-")
         };
 
-        // Shorten image name
-        let image = if c.image.len() > 15 {
-             format!("{}...", &c.image[0..12])
-        } else {
-             c.image.clone()
+        // Shorten image name, keeping a search match past the cut visible
+        let image = match fuzzy_match(&c.image, query, case).and_then(|(_, p)| Some((*p.first()?, *p.last()?))) {
+            Some((start, end)) if c.image.len() > 15 && end >= 12 => {
+                format!("...{}", c.image[start..].chars().take(12).collect::<String>())
+            }
+            _ if c.image.len() > 15 => format!("{}...", &c.image[0..12]),
+            _ => c.image.clone(),
         };
         
         // Stats
@@ -155,39 +276,91 @@ pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
             Style::default()
         };
 
-        let cells = vec![
-            Cell::from(c.name.clone()).style(if is_stale_row { row_style } else { Style::default().fg(Color::Cyan) }),
-            Cell::from(format!("{} {}", status_symbol, c.state))
-                .style(if is_stale_row { row_style } else { Style::default().fg(status_color).bold() }),
+        let mut cells = [
+            Cell::from({
+                let mut name = highlight_match(c.name.clone(), query, case, if is_stale_row { row_style } else { Style::default().fg(Color::Cyan) });
+                if app.is_pinned(c) {
+                    name.spans.insert(0, Span::styled("★ ", Style::default().fg(Color::Yellow)));
+                }
+                if app.show_hidden && app.is_hidden(c) {
+                    name.spans.insert(0, Span::styled("⊘ ", Style::default().fg(Color::DarkGray)));
+                }
+                if app.marked.contains(&c.id) {
+                    name.spans.insert(0, Span::styled("✓ ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)));
+                }
+                name
+            }),
+            Cell::from(app.host_label(c).to_string()).style(Style::default().fg(Color::Magenta)),
+            if storming {
+                // Restart loops are easy to miss in a plain status column
+                Cell::from(format!("⟳{} {}", restarts, c.state))
+                    .style(Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD | Modifier::REVERSED))
+            } else {
+                let shaped = if shaped.contains_key(&c.id) { " ~netem" } else { "" };
+                Cell::from(format!("{} {}{}", status_symbol, c.state, shaped))
+                    .style(if is_stale_row { row_style } else { Style::default().fg(status_color).bold() })
+            },
             health_cell, // Health cell has its own coloring, we might want to override if stale?
-            Cell::from(image).style(row_style),
+            Cell::from(highlight_match(image, query, case, row_style)),
             Cell::from(uptime).style(row_style),
             Cell::from(stats_str).style(row_style),
         ];
+        // Cells are built in COLUMNS order; keep the visible ones
+        let cells = columns.iter().map(|col| {
+            let index = COLUMNS.iter().position(|c| c == col).unwrap_or_default();
+            std::mem::take(&mut cells[index])
+        }).collect::<Vec<_>>();
         Row::new(cells).height(1)
+    };
+    let group_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let rows = app.list_rows.iter().map(|row| match row {
+        ListRow::Container(i) => container_row(&app.filtered_containers[*i]),
+        ListRow::Group { key, count, running, collapsed } => {
+            let cells = columns.iter().map(|col| match col {
+                Column::Name => Cell::from(format!("{} {} ({})", if *collapsed { "▸" } else { "▾" }, key, count)),
+                Column::Status => Cell::from(format!("{} running", running)),
+                _ => Cell::from(""),
+            });
+            Row::new(cells).style(group_style).height(1)
+        }
     });
 
-    // Adjust constraints for the list columns
-    let widths = [
-        Constraint::Percentage(20),
-        Constraint::Percentage(10),
-        Constraint::Percentage(15),
-        Constraint::Percentage(20),
-        Constraint::Percentage(10),
-        Constraint::Percentage(25),
-    ];
-
-    let border_style = if app.focus == crate::app::Focus::ContainerList {
+    let border_style = if app.focus.is(crate::app::Focus::ContainerList) {
         Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(Color::Magenta)
     };
 
-    let title = if unhealthy_count > 0 || starting_count > 0 || healthy_count > 0 {
-        format!(" Containers ({}) | Health: ✓{} ⚠{} ✗{} ", app.total_containers, healthy_count, starting_count, unhealthy_count)
-    } else {
-        format!(" Containers ({}) ", app.total_containers)
-    };
+    // Active view configuration, so a short list is never a mystery
+    let mut title = format!(
+        " Containers ({}) [{}]",
+        app.total_containers,
+        if app.show_all.load(std::sync::atomic::Ordering::Relaxed) { "all" } else { "running" },
+    );
+    if let Some(filter) = app.state_filter {
+        title.push_str(&format!(" [{}]", filter.label()));
+    }
+    if let Some(filter) = app.health_filter.label() {
+        title.push_str(&format!(" [{}]", filter));
+    }
+    if !app.container_filter.is_empty() {
+        title.push_str(&format!(" [filter: {}]", app.container_filter.value()));
+    }
+    if !app.marked.is_empty() {
+        title.push_str(&format!(" [{} marked]", app.marked.len()));
+    }
+    if app.show_hidden {
+        title.push_str(" [showing hidden]");
+    } else if app.hidden_count > 0 {
+        title.push_str(&format!(" [{} hidden]", app.hidden_count));
+    }
+    if let Some(group_by) = &app.group_by {
+        title.push_str(&format!(" [group: {}]", group_by.label()));
+    }
+    title.push_str(&format!(" [sort: {}] ", app.container_sort.label()));
+    if unhealthy_count > 0 || starting_count > 0 || healthy_count > 0 {
+        title.push_str(&format!("| Health: ✓{} ⚠{} ✗{} ", healthy_count, starting_count, unhealthy_count));
+    }
 
     let table = Table::new(rows, widths)
         .header(header)
@@ -195,6 +368,10 @@ pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
+                .title_bottom(Line::styled(
+                    format!(" {} ", format_updated(app.containers_updated.load(std::sync::atomic::Ordering::Relaxed))),
+                    Style::default().fg(Color::DarkGray),
+                ).right_aligned())
                 .border_style(border_style)
         )
         .highlight_style(
@@ -205,6 +382,7 @@ pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
         .highlight_symbol("▶ ");
 
     f.render_stateful_widget(table, area, &mut app.table_state);
+    app.container_list_area = area;
 
     // Update viewport state for background fetching
     let height = area.height.saturating_sub(2); // Subtract borders
@@ -215,3 +393,90 @@ pub fn render_container_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
         viewport.offset = offset;
     }
 }
+
+pub fn render_remove_marked_confirm(f: &mut Frame<'_>, area: Rect, count: usize) {
+    let area = centered_rect(40, 10, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(" Confirm Removal ");
+
+    let text = format!("Force-remove {} marked container{}?\nPress 'y' to confirm, 'n' or Esc to cancel.", count, if count == 1 { "" } else { "s" });
+    let p = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    f.render_widget(p, area);
+}
+
+/// Search input docked to the bottom edge of the container list.
+pub fn render_container_search(f: &mut Frame<'_>, app: &App) {
+    let list = app.container_list_area;
+    if list.height < 3 {
+        return;
+    }
+    let area = Rect { x: list.x, y: list.y + list.height - 3, width: list.width, height: 3 };
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Search name / image / ID ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    app.container_filter.render(f, inner, true);
+}
+
+/// Bulk command prompt (`:`), docked like the search box, with the parse
+/// error or the syntax under it.
+pub fn render_bulk_command(f: &mut Frame<'_>, app: &App) {
+    let list = app.container_list_area;
+    if list.height < 4 {
+        return;
+    }
+    let area = Rect { x: list.x, y: list.y + list.height - 4, width: list.width, height: 4 };
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Bulk command ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    app.bulk_command.render(f, Rect { height: 1, ..inner }, true);
+
+    let hint = match app.bulk_command.error() {
+        Some(e) if !app.bulk_command.is_empty() => Span::styled(e, Style::default().fg(Color::Red)),
+        _ => Span::styled(SYNTAX, Style::default().fg(Color::DarkGray)),
+    };
+    f.render_widget(Paragraph::new(Line::from(hint)), Rect { y: inner.y + 1, height: 1, ..inner });
+}
+
+/// The containers a bulk command resolved to, before it runs.
+pub fn render_bulk_command_confirm(f: &mut Frame<'_>, area: Rect, command: &str, action: ContainerAction, targets: &[(String, String)]) {
+    let area = centered_rect(50, 60, area);
+    f.render_widget(Clear, area);
+
+    let color = if action == ContainerAction::Remove { Color::Red } else { Color::Yellow };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(format!(" {} ", command));
+
+    let count = targets.len();
+    let mut lines = vec![
+        Line::from(format!("{} {} container{}:", action.verb(), count, if count == 1 { "" } else { "s" })),
+        Line::from(""),
+    ];
+    // Leave room for the header, the prompt and the borders
+    let room = (area.height as usize).saturating_sub(6).max(1);
+    let shown = if count > room { room - 1 } else { count };
+    lines.extend(targets.iter().take(shown).map(|(_, name)| Line::from(format!("  {}", name))));
+    if count > shown {
+        lines.push(Line::from(format!("  ... and {} more", count - shown)).fg(Color::DarkGray));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("Press 'y' to run, 'n' or Esc to cancel."));
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::types::{DockerContext, Workspace};
+use crate::ui::layout::centered_rect_fixed_height;
+
+pub fn render_context_picker(f: &mut Frame<'_>, area: Rect, contexts: &[DockerContext], selected: usize, active: &str) {
+    let height = (contexts.len() as u16 * 2 + 2).min(area.height);
+    let area = centered_rect_fixed_height(60, height, area);
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = contexts.iter().map(|c| {
+        let mut name = vec![Span::styled(c.name.clone(), Style::default().add_modifier(Modifier::BOLD))];
+        if c.name == active {
+            name.push(Span::styled("  (connected)", Style::default().fg(Color::Green)));
+        } else if c.current {
+            name.push(Span::styled("  (CLI current)", Style::default().fg(Color::Cyan)));
+        }
+        let mut endpoint = format!("  {}", c.host);
+        if c.tls.is_some() {
+            endpoint.push_str(" · TLS");
+        }
+        if !c.description.is_empty() {
+            endpoint.push_str(&format!(" · {}", c.description));
+        }
+        ListItem::new(vec![
+            Line::from(name),
+            Line::from(Span::styled(endpoint, Style::default().fg(Color::DarkGray))),
+        ])
+    }).collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Docker Contexts "))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(selected));
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+pub fn render_workspace_picker(f: &mut Frame<'_>, area: Rect, workspaces: &[Workspace], selected: usize, active: Option<&str>) {
+    let height = (workspaces.len() as u16 * 2 + 2).min(area.height);
+    let area = centered_rect_fixed_height(60, height, area);
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = workspaces.iter().map(|w| {
+        let mut name = vec![Span::styled(w.name.clone(), Style::default().add_modifier(Modifier::BOLD))];
+        if Some(w.name.as_str()) == active {
+            name.push(Span::styled("  (active)", Style::default().fg(Color::Green)));
+        }
+        if w.read_only {
+            name.push(Span::styled("  read-only", Style::default().fg(Color::Yellow)));
+        }
+        let mut summary = Vec::new();
+        if let Some(context) = &w.context {
+            summary.push(format!("ctx {}", context));
+        }
+        if let Some(filter) = w.filter.as_ref().filter(|f| !f.is_empty()) {
+            summary.push(format!("filter '{}'", filter));
+        }
+        if !w.pinned.is_empty() {
+            summary.push(format!("{} pinned", w.pinned.len()));
+        }
+        if let Some(view) = w.view {
+            summary.push(format!("{:?} view", view));
+        }
+        ListItem::new(vec![
+            Line::from(name),
+            Line::from(Span::styled(format!("  {}", summary.join(" · ")), Style::default().fg(Color::DarkGray))),
+        ])
+    }).collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Workspaces "))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(selected));
+    f.render_stateful_widget(list, area, &mut state);
+}
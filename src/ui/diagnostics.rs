@@ -0,0 +1,41 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::ui::layout::centered_rect;
+
+/// Section headings in bold, problem lines in red; the report text itself
+/// stays plain so it can be copied as-is.
+fn style_line(line: &str) -> Line<'_> {
+    if !line.starts_with(' ') && line.ends_with(':') {
+        return Line::from(Span::styled(line, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+    }
+    let problem = ["unreachable", "STOPPED", "permission denied", "does not exist", "no response", "may fail", "below the daemon"]
+        .iter()
+        .any(|p| line.contains(p));
+    if problem {
+        Line::from(Span::styled(line, Style::default().fg(Color::Red)))
+    } else {
+        Line::from(line)
+    }
+}
+
+pub fn render_diagnostics(f: &mut Frame<'_>, area: Rect, app: &App, report: &str) {
+    let area = centered_rect(80, 80, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Diagnostics ")
+        .title_bottom(" ↑/↓: Scroll | c: Copy Report | r: Re-run | Esc: Close ");
+
+    let paragraph = Paragraph::new(report.lines().map(style_line).collect::<Vec<_>>())
+        .block(block)
+        .scroll((app.viewer_scroll, 0));
+    f.render_widget(paragraph, area);
+}
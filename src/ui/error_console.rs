@@ -0,0 +1,38 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use chrono::{DateTime, Local};
+use crate::app::App;
+
+/// Bottom drawer listing recent background errors, newest first.
+pub fn render_error_console(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let height = 12.min(area.height);
+    let area = Rect { x: area.x, y: area.y + area.height - height, width: area.width, height };
+    f.render_widget(Clear, area);
+
+    let errors = app.error_log.read().unwrap();
+    let lines: Vec<Line> = if errors.is_empty() {
+        vec![Line::from(Span::styled("No errors", Style::default().fg(Color::DarkGray)))]
+    } else {
+        errors.iter().rev().map(|e| {
+            let time = DateTime::from_timestamp(e.time, 0)
+                .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+                .unwrap_or_default();
+            Line::from(vec![
+                Span::styled(format!("{} ", time), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("[{}] ", e.source), Style::default().fg(Color::Yellow)),
+                Span::raw(e.message.clone()),
+            ])
+        }).collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(format!(" Errors ({}) - !: close ", errors.len()));
+    f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+}
@@ -0,0 +1,34 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::docker::exec::DEFAULT_COMMAND;
+use crate::ui::layout::centered_rect_fixed_height;
+
+pub fn render_exec_chooser(f: &mut Frame<'_>, area: Rect, container_name: &str, commands: &[String], selected: usize) {
+    let height = (commands.len() as u16 + 3).min(area.height);
+    let area = centered_rect_fixed_height(50, height, area);
+    f.render_widget(Clear, area);
+
+    let mut items: Vec<ListItem> = commands.iter().map(|command| {
+        let mut line = vec![Span::raw(command.clone())];
+        if command == DEFAULT_COMMAND {
+            line.push(Span::styled("  (sh if there is no bash)", Style::default().fg(Color::DarkGray)));
+        }
+        ListItem::new(Line::from(line))
+    }).collect();
+    items.push(ListItem::new(Line::styled("Custom command…", Style::default().add_modifier(Modifier::ITALIC))));
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(" Exec in {} ", container_name)))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+    let mut state = ListState::default().with_selected(Some(selected));
+    f.render_stateful_widget(list, area, &mut state);
+}
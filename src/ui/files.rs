@@ -0,0 +1,112 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use crate::app::format_bytes;
+use crate::types::{FileEntry, FsChange, FsChangeKind};
+use crate::ui::layout::centered_rect;
+
+pub fn render_file_browser(f: &mut Frame<'_>, area: Rect, container_name: &str, path: &str, entries: &[FileEntry], selected: usize) {
+    let area = centered_rect(80, 80, area);
+    f.render_widget(Clear, area);
+
+    let rows = entries.iter().map(|e| {
+        let (color, suffix) = match e.mode.chars().next() {
+            Some('d') => (Color::Cyan, "/"),
+            Some('l') => (Color::Magenta, ""),
+            _ => (Color::White, ""),
+        };
+        let mut name = vec![Span::styled(format!("{}{}", e.name, suffix), Style::default().fg(color))];
+        if let Some(target) = &e.link_target {
+            name.push(Span::styled(format!(" -> {}", target), Style::default().fg(Color::DarkGray)));
+        }
+        Row::new(vec![
+            Cell::from(e.mode.clone()).style(Style::default().fg(Color::DarkGray)),
+            Cell::from(if e.is_dir() { "-".to_string() } else { format_bytes(e.size) }),
+            Cell::from(e.modified.clone()).style(Style::default().fg(Color::DarkGray)),
+            Cell::from(Line::from(name)),
+        ])
+    });
+
+    let title = if entries.is_empty() {
+        format!(" {}:{} (empty) ", container_name, path)
+    } else {
+        format!(" {}:{} ", container_name, path)
+    };
+    let table = Table::new(rows, [Constraint::Length(11), Constraint::Length(10), Constraint::Length(13), Constraint::Min(10)])
+        .header(Row::new(vec!["MODE", "SIZE", "MODIFIED", "NAME"]).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(title)
+            .title_bottom(" Enter: Open | Backspace: Up | d: Download | u: Upload | r: Reload | Esc: Close "))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+    let mut state = TableState::default().with_selected(Some(selected));
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+pub fn render_upload_overwrite_confirm(f: &mut Frame<'_>, area: Rect, container_name: &str, source: &str, dest: &str) {
+    let area = centered_rect(50, 12, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(" Confirm Overwrite ");
+
+    let name = std::path::Path::new(source).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let text = format!("{}:{} already contains '{}'. Replace it with {}?\nPress 'y' to confirm, 'n' or Esc to cancel.", container_name, dest, name, source);
+    let p = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    f.render_widget(p, area);
+}
+
+pub fn render_fs_diff(f: &mut Frame<'_>, area: Rect, container_name: &str, changes: &[FsChange], filter: Option<FsChangeKind>, selected: usize) {
+    let area = centered_rect(80, 80, area);
+    f.render_widget(Clear, area);
+
+    let color = |kind| match kind {
+        FsChangeKind::Changed => Color::Yellow,
+        FsChangeKind::Added => Color::Green,
+        FsChangeKind::Deleted => Color::Red,
+    };
+    let rows: Vec<Row> = changes.iter()
+        .filter(|c| filter.is_none_or(|kind| c.kind == kind))
+        .map(|c| Row::new(vec![
+            Cell::from(c.kind.symbol().to_string()).style(Style::default().fg(color(c.kind)).add_modifier(Modifier::BOLD)),
+            Cell::from(c.path.clone()),
+        ]))
+        .collect();
+
+    let counts: Vec<Span> = FsChangeKind::ALL.iter()
+        .map(|kind| {
+            let count = changes.iter().filter(|c| c.kind == *kind).count();
+            let style = if filter == Some(*kind) { Style::default().fg(color(*kind)).add_modifier(Modifier::REVERSED) } else { Style::default().fg(color(*kind)) };
+            Span::styled(format!(" {} {} ", count, kind.name()), style)
+        })
+        .collect();
+    let mut title = vec![Span::raw(format!(" {} vs image: ", container_name))];
+    title.extend(counts);
+    let placeholder = if changes.is_empty() { "No changes since the container was created" } else { "No changes of this kind" };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Line::from(title))
+        .title_bottom(" f: Filter | r: Reload | Esc: Close ");
+    if rows.is_empty() {
+        let p = Paragraph::new(placeholder).style(Style::default().fg(Color::DarkGray)).block(block);
+        f.render_widget(p, area);
+        return;
+    }
+    let table = Table::new(rows, [Constraint::Length(2), Constraint::Min(10)])
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+    let mut state = TableState::default().with_selected(Some(selected));
+    f.render_stateful_widget(table, area, &mut state);
+}
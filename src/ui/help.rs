@@ -111,19 +111,29 @@ fn render_keybindings(f: &mut Frame<'_>, area: Rect, scroll: u16) {
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled("GLOBAL KEYS", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "?"), Style::default().fg(Color::Yellow)), Span::raw("Help menu")]));
-    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Tab"), Style::default().fg(Color::Yellow)), Span::raw("Switch focus (Containers) or Switch Help Tab (Help Menu)")]));
-    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Sh+Tab/v"), Style::default().fg(Color::Yellow)), Span::raw("Switch between Containers and Images views")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Tab"), Style::default().fg(Color::Yellow)), Span::raw("Next pane: list, logs, details, graphs (Containers) or Switch Help Tab (Help Menu)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Shift+Tab"), Style::default().fg(Color::Yellow)), Span::raw("Previous pane (Containers) or previous view (other views)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "v"), Style::default().fg(Color::Yellow)), Span::raw("Cycle Containers, Images, Services, Stacks, Nodes and System views")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "!"), Style::default().fg(Color::Yellow)), Span::raw("Toggle the error console (background task failures)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "C"), Style::default().fg(Color::Yellow)), Span::raw("Switch Docker context (reconnects to the chosen endpoint)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "G"), Style::default().fg(Color::Yellow)), Span::raw("Switch workspace: context, filters, pinned containers and layout from config.toml")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "I"), Style::default().fg(Color::Yellow)), Span::raw("Diagnostics: connection, API version, task health (c copies the report)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Ctrl+Z"), Style::default().fg(Color::Yellow)), Span::raw("Undo the last remove or prune within 5s")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "q"), Style::default().fg(Color::Yellow)), Span::raw("Quit")]));
-    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "R"), Style::default().fg(Color::Yellow)), Span::raw("Refresh containers and images manually")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "R"), Style::default().fg(Color::Yellow)), Span::raw("Refresh the current view and its panes (details, logs, stats) now")]));
 
     // CONTAINER VIEW
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled("CONTAINER VIEW", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
-    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Up/Down"), Style::default().fg(Color::Yellow)), Span::raw("Navigate containers")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Up/Down"), Style::default().fg(Color::Yellow)), Span::raw("Navigate containers, or scroll the focused logs/details pane")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Enter"), Style::default().fg(Color::Yellow)), Span::raw("View detailed container info")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "o / hover"), Style::default().fg(Color::Yellow)), Span::raw("Quick info popup (ports, health, last log lines)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "/"), Style::default().fg(Color::Yellow)), Span::raw("Fuzzy search by name, image or ID prefix (Enter keeps, Esc clears)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "n / N"), Style::default().fg(Color::Yellow)), Span::raw("Next / previous search match, best first")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", ":"), Style::default().fg(Color::Yellow)), Span::raw("Bulk command by pattern, e.g. stop api-* or rm exited>7d (previews first)")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "i"), Style::default().fg(Color::Yellow)), Span::raw("View resource history graphs")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "l"), Style::default().fg(Color::Yellow)), Span::raw("View container logs")]));
-    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "e"), Style::default().fg(Color::Yellow)), Span::raw("Launch interactive shell")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "e"), Style::default().fg(Color::Yellow)), Span::raw("Launch interactive shell (choose bash, sh, ash, a profile preset or a custom command)")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "r"), Style::default().fg(Color::Yellow)), Span::raw("Restart container")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "s"), Style::default().fg(Color::Yellow)), Span::raw("Stop container")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "t"), Style::default().fg(Color::Yellow)), Span::raw("Start container")]));
@@ -131,6 +141,46 @@ fn render_keybindings(f: &mut Frame<'_>, area: Rect, scroll: u16) {
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "u"), Style::default().fg(Color::Yellow)), Span::raw("Unpause container")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "d"), Style::default().fg(Color::Yellow)), Span::raw("Remove container (force)")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "f"), Style::default().fg(Color::Yellow)), Span::raw("Toggle filter (all/running)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "F5-F8"), Style::default().fg(Color::Yellow)), Span::raw("Show only running / stopped / paused / unhealthy (or click the status bar counts)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Space / A"), Style::default().fg(Color::Yellow)), Span::raw("Mark container / mark all listed; r, s, S and d then act on every marked one")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "*"), Style::default().fg(Color::Yellow)), Span::raw("Pin / unpin container: listed first whatever the filters, saved in config.toml")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "- / ."), Style::default().fg(Color::Yellow)), Span::raw("Hide / unhide container by name (saved in config.toml) / reveal hidden containers")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "H"), Style::default().fg(Color::Yellow)), Span::raw("Cycle sort: created, health, CPU, memory, name, uptime")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "c"), Style::default().fg(Color::Yellow)), Span::raw("Compose project actions (start/stop/restart/remove all, f: compose file, n: scale, x: chaos testing)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "L"), Style::default().fg(Color::Yellow)), Span::raw("Toggle aggregated compose project logs")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "c"), Style::default().fg(Color::Yellow)), Span::raw("Details pane: check the container's clock against the host (runs date in it)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "T"), Style::default().fg(Color::Yellow)), Span::raw("Logs pane: jump to the line nearest a time (HH:MM, HH:MM:SS or YYYY-MM-DD HH:MM)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "g"), Style::default().fg(Color::Yellow)), Span::raw("Group the list by compose project or a label key")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Enter"), Style::default().fg(Color::Yellow)), Span::raw("Grouped list: collapse/expand the selected group")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "l"), Style::default().fg(Color::Yellow)), Span::raw("Lifecycle timeline (starts, exits, OOM kills, health changes)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "i"), Style::default().fg(Color::Yellow)), Span::raw("Signals: show stop signal/grace period and kill with any signal (SIGTERM, SIGKILL, SIGHUP, SIGUSR1...)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "w"), Style::default().fg(Color::Yellow)), Span::raw("Write a line or a file's contents to the container's stdin (needs -i)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "y"), Style::default().fg(Color::Yellow)), Span::raw("Show an equivalent `docker run` command (c copies it)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Y"), Style::default().fg(Color::Yellow)), Span::raw("Show the container as a compose service (c copies it)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "b"), Style::default().fg(Color::Yellow)), Span::raw("Edit labels (recreates the container with the same config)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "D"), Style::default().fg(Color::Yellow)), Span::raw("Duplicate: new container from the same config, with a new name and ports")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "F2"), Style::default().fg(Color::Yellow)), Span::raw("Rename container")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "F4"), Style::default().fg(Color::Yellow)), Span::raw("Edit image, env or ports, and recreate the container with them")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "W"), Style::default().fg(Color::Yellow)), Span::raw("Commit: save the container's filesystem as a new image")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Z"), Style::default().fg(Color::Yellow)), Span::raw("Network shaping: add latency, loss or a bandwidth cap, or revert it")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Q"), Style::default().fg(Color::Yellow)), Span::raw("Migrate: recreate the container on another host, with rollback on failure")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "F"), Style::default().fg(Color::Yellow)), Span::raw("Export the container's filesystem to a tar file")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "z"), Style::default().fg(Color::Yellow)), Span::raw("Incident report: Markdown template with digest, errors, health and stats")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "B"), Style::default().fg(Color::Yellow)), Span::raw("Browse the container's files; d downloads the selection as a tar, u uploads")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "O"), Style::default().fg(Color::Yellow)), Span::raw("Copy the commit URL the image was built from (OCI source/revision labels)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "U"), Style::default().fg(Color::Yellow)), Span::raw("Processes (docker top), refreshed on the stats interval")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "V"), Style::default().fg(Color::Yellow)), Span::raw("Filesystem diff against the image (docker diff); f filters by change type")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "x"), Style::default().fg(Color::Yellow)), Span::raw("Export last N minutes of CPU/memory samples as CSV (clipboard or file)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "X"), Style::default().fg(Color::Yellow)), Span::raw("Snapshot run commands and a compose.yml of all running containers to a directory")]));
+
+    // TOP VIEW
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled("TOP VIEW", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Up/Down"), Style::default().fg(Color::Yellow)), Span::raw("Move through the ranking (the selection follows its container)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Tab"), Style::default().fg(Color::Yellow)), Span::raw("Rank by CPU or memory")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "s"), Style::default().fg(Color::Yellow)), Span::raw("Stop the selected container")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "K"), Style::default().fg(Color::Yellow)), Span::raw("Kill the selected container (SIGKILL)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Enter"), Style::default().fg(Color::Yellow)), Span::raw("Show the selected container in the Containers view")]));
 
     // IMAGE VIEW
     lines.push(Line::from(""));
@@ -139,10 +189,41 @@ fn render_keybindings(f: &mut Frame<'_>, area: Rect, scroll: u16) {
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Enter"), Style::default().fg(Color::Yellow)), Span::raw("Inspect image details")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "s"), Style::default().fg(Color::Yellow)), Span::raw("Toggle sort (Date / Size)")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "f"), Style::default().fg(Color::Yellow)), Span::raw("Toggle dangling image filter")]));
-    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "p"), Style::default().fg(Color::Yellow)), Span::raw("Pull new image")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "p"), Style::default().fg(Color::Yellow)), Span::raw("Pull new image (Tab in the dialog picks a mirror/registry, Shift+Tab the host)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "O"), Style::default().fg(Color::Yellow)), Span::raw("Copy the commit URL the image was built from")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "d"), Style::default().fg(Color::Yellow)), Span::raw("Remove image")]));
     lines.push(Line::from(vec![Span::styled(format!("{: <12}", "D"), Style::default().fg(Color::Yellow)), Span::raw("Force remove image")]));
 
+    // SERVICE VIEW
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled("SERVICE VIEW", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Up/Down"), Style::default().fg(Color::Yellow)), Span::raw("Navigate swarm services")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "u"), Style::default().fg(Color::Yellow)), Span::raw("Force-update service (optionally with a new image tag)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "b"), Style::default().fg(Color::Yellow)), Span::raw("Roll back to the previous service spec")]));
+
+    // STACK VIEW
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled("STACK VIEW", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Up/Down"), Style::default().fg(Color::Yellow)), Span::raw("Navigate swarm stacks")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "d"), Style::default().fg(Color::Yellow)), Span::raw("Remove stack (services, networks, secrets and configs, with prompt)")]));
+
+    // NODE VIEW
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled("NODE VIEW", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Up/Down"), Style::default().fg(Color::Yellow)), Span::raw("Navigate swarm nodes")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "d"), Style::default().fg(Color::Yellow)), Span::raw("Drain node after confirming (reschedules its tasks elsewhere)")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "a"), Style::default().fg(Color::Yellow)), Span::raw("Activate node")]));
+
+    // SYSTEM VIEW
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled("SYSTEM VIEW", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Up/Down"), Style::default().fg(Color::Yellow)), Span::raw("Navigate the cleanup checklist")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "Enter"), Style::default().fg(Color::Yellow)), Span::raw("Run the selected cleanup item")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "p"), Style::default().fg(Color::Yellow)), Span::raw("Open the guided prune wizard")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "c"), Style::default().fg(Color::Yellow)), Span::raw("Prune build cache by size/age threshold")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "n"), Style::default().fg(Color::Yellow)), Span::raw("Create a volume or network from a config.toml template")]));
+    lines.push(Line::from(vec![Span::styled(format!("{: <12}", "R"), Style::default().fg(Color::Yellow)), Span::raw("Re-analyze disk usage")]));
+
     let paragraph = Paragraph::new(lines)
         .scroll((scroll, 0))
         .block(Block::default().padding(ratatui::widgets::Padding::new(2, 2, 0, 1)));
@@ -151,11 +232,9 @@ fn render_keybindings(f: &mut Frame<'_>, area: Rect, scroll: u16) {
 }
 
 fn render_wiki(f: &mut Frame<'_>, area: Rect, scroll: u16) {
-    let mut lines = Vec::new();
-
-    lines.push(Line::from(vec![
+    let mut lines = vec![Line::from(vec![
         Span::styled(" DOCKYARD WIKI ", Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)),
-    ]).alignment(Alignment::Center));
+    ]).alignment(Alignment::Center)];
 
     // MANAGING CONTAINERS
     lines.push(Line::from(""));
@@ -177,6 +256,7 @@ fn render_wiki(f: &mut Frame<'_>, area: Rect, scroll: u16) {
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled("PULLING & REMOVING IMAGES", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]));
     lines.push(Line::from("Press 'p' in image view. Enter image name (e.g., nginx:latest)."));
+    lines.push(Line::from("Text inputs support Left/Right, Home/End, Ctrl+W (delete word), Ctrl+U and Up/Down history."));
     lines.push(Line::from("Progress streams in the bottom-right pane. The UI stays responsive during pull."));
     lines.push(Line::from("Press 'd' to remove (with prompt) or 'D' to force remove."));
 
@@ -0,0 +1,99 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+use crate::app::{App, Focus, Modal, PruneStep, View};
+
+/// Key hints for whatever currently receives input: the top modal if any,
+/// otherwise the focused pane of the current view.
+pub fn key_hints(app: &App) -> Vec<(&'static str, &'static str)> {
+    if let Some(modal) = app.top_modal() {
+        return match modal {
+            Modal::Help => vec![("Tab", "Switch Tab"), ("↑/↓", "Scroll"), ("Esc", "Close")],
+            Modal::PullImage => vec![("Enter", "Pull"), ("Tab", "Registry"), ("Shift+Tab", "Host"), ("←/→", "Move"), ("Ctrl+W", "Delete Word"), ("↑/↓", "History"), ("Esc", "Cancel")],
+            Modal::PruneWizard { step: PruneStep::Select(_), .. } => vec![("Space", "Toggle"), ("Tab", "Next"), ("Enter", "Continue"), ("Esc", "Cancel")],
+            Modal::PruneWizard { step: PruneStep::Confirm(_), .. } => vec![("y", "Prune"), ("n/Esc", "Cancel")],
+            Modal::PruneWizard { step: PruneStep::Summary(_), .. } => vec![("Enter/Esc", "Close")],
+            Modal::DeleteImageConfirm { .. } | Modal::RemoveStackConfirm(_) | Modal::DrainNodeConfirm(_) | Modal::CleanupVolumesConfirm(_) | Modal::RemoveMarkedConfirm(_) | Modal::UploadOverwriteConfirm { .. } => vec![("y", "Confirm"), ("n/Esc", "Cancel")],
+            Modal::QuitConfirm(_) => vec![("y", "Quit"), ("r", "Run Now"), ("n/Esc", "Stay")],
+            Modal::HealthLog(_) => vec![("Esc/q", "Close")],
+            Modal::ProjectMenu => vec![("S/s/r/d", "Start/Stop/Restart/Remove"), ("f", "Compose File"), ("n", "Scale"), ("x", "Chaos"), ("Esc", "Cancel")],
+            Modal::SendSignal { .. } => vec![("Enter", "Send"), ("←/→", "Signal"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::SendStdin { .. } => vec![("Enter", "Send"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::EditLabels { .. } => vec![("Enter", "Recreate"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::Duplicate { .. } => vec![("Enter", "Create"), ("Tab", "Next Field"), ("Space", "Toggle"), ("Esc", "Cancel")],
+            Modal::EditContainer { .. } => vec![("Enter", "Recreate"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::JumpToTime { .. } => vec![("Enter", "Jump"), ("Esc", "Cancel")],
+            Modal::GroupBy { .. } => vec![("Enter", "Group"), ("Esc", "Cancel")],
+            Modal::Rename { .. } => vec![("Enter", "Rename"), ("Ctrl+W", "Delete Word"), ("Esc", "Cancel")],
+            Modal::Commit { .. } => vec![("Enter", "Commit"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::IncidentReport { .. } => vec![("Enter", "Export"), ("←/→", "Destination"), ("Esc", "Cancel")],
+            Modal::Processes { .. } => vec![("↑/↓", "Select"), ("r", "Refresh"), ("Esc", "Close")],
+            Modal::FsDiff { .. } => vec![("↑/↓", "Select"), ("f", "Filter"), ("r", "Reload"), ("Esc", "Close")],
+            Modal::CreateFromTemplate { .. } => vec![("Enter", "Create"), ("←/→", "Template"), ("Esc", "Cancel")],
+            Modal::Upload { .. } => vec![("Enter", "Upload"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::ExecChooser { .. } => vec![("↑/↓", "Select"), ("Enter", "Exec"), ("c", "Custom"), ("Esc", "Cancel")],
+            Modal::ExecCustom { .. } => vec![("Enter", "Exec"), ("Esc", "Cancel")],
+            Modal::Migrate { .. } => vec![("Enter", "Migrate"), ("←/→", "Host"), ("Tab", "Next Field"), ("Space", "Toggle"), ("Esc", "Cancel")],
+            Modal::ExportFilesystem { .. } => vec![("Enter", "Export"), ("Ctrl+W", "Delete Word"), ("Esc", "Cancel")],
+            Modal::Chaos { .. } => vec![("Enter", "Run"), ("←/→", "Choose"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::Netem { .. } => vec![("Enter", "Apply"), ("←/→", "Action"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::Files { .. } => vec![("↑/↓", "Select"), ("Enter", "Open"), ("Backspace", "Up"), ("d", "Download"), ("u", "Upload"), ("Esc", "Close")],
+            Modal::Timeline { .. } => vec![("↑/↓", "Scroll"), ("Esc", "Close")],
+            Modal::ContextPicker { .. } => vec![("↑/↓", "Select"), ("Enter", "Connect"), ("Esc", "Cancel")],
+            Modal::WorkspacePicker { .. } => vec![("↑/↓", "Select"), ("Enter", "Switch"), ("Esc", "Cancel")],
+            Modal::BulkCommand => vec![("Enter", "Preview"), ("Esc", "Cancel"), ("↑/↓", "History")],
+            Modal::BulkCommandConfirm { .. } => vec![("y", "Run"), ("n/Esc", "Cancel")],
+            Modal::ContainerSearch => vec![("Enter", "Keep Filter"), ("Esc", "Clear"), ("Ctrl+U", "Erase")],
+            Modal::Diagnostics(_) => vec![("↑/↓", "Scroll"), ("c", "Copy Report"), ("r", "Re-run"), ("Esc", "Close")],
+            Modal::TextViewer { .. } => vec![("↑/↓", "Scroll"), ("PgUp/PgDn", "Page"), ("g/G", "Top/Bottom"), ("c", "Copy"), ("Esc", "Close")],
+            Modal::ScaleService { .. } => vec![("Enter", "Apply"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::UpdateService { .. } => vec![("Enter", "Update"), ("Ctrl+W", "Delete Word"), ("Esc", "Cancel")],
+            Modal::PruneBuildCache { .. } => vec![("Enter", "Prune"), ("Tab", "Next Field"), ("Esc", "Cancel")],
+            Modal::ExportStats { .. } => vec![("Enter", "Export"), ("Tab", "Next Field"), ("←/→", "Destination"), ("Esc", "Cancel")],
+        };
+    }
+
+    match app.current_view {
+        View::Containers => match app.focus.current() {
+            Focus::ContainerList => vec![
+                ("↑/↓", "Select"), ("s/S", "Stop/Start"), ("r", "Restart"), ("d", "Remove"), ("e", "Shell"),
+                ("c", "Project"), ("/", "Search"), ("o", "Quick Info"), ("l", "Timeline"), ("i", "Signals"), ("Tab", "Pane"), ("v", "Top"), ("?", "Help"), ("q", "Quit"),
+            ],
+            Focus::Logs => vec![
+                ("↑/↓", "Scroll"), ("a", "Auto Scroll"), ("T", "Jump to Time"), ("L", "Project Logs"), ("Tab", "Pane"), ("?", "Help"),
+            ],
+            Focus::Details => vec![("↑/↓", "Scroll"), ("g", "Top"), ("c", "Clock Check"), ("E", "Health Output"), ("Tab", "Pane"), ("?", "Help")],
+            Focus::Graphs => vec![("m", "Minimal/Detailed"), ("[/]", "Refresh Rate"), ("x", "Export CSV"), ("Tab", "Pane"), ("?", "Help")],
+        },
+        View::Top => vec![
+            ("↑/↓", "Select"), ("Tab", "CPU/Memory"), ("s", "Stop"), ("K", "Kill"), ("Enter", "Show in Containers"), ("v", "Images"), ("?", "Help"), ("q", "Quit"),
+        ],
+        View::Images => vec![
+            ("↑/↓", "Select"), ("Enter", "Details"), ("p", "Pull"), ("d/D", "Remove/Force"), ("s", "Sort"),
+            ("f", "Dangling"), ("O", "Commit URL"), ("v", "Services"), ("?", "Help"), ("q", "Quit"),
+        ],
+        View::Services => vec![
+            ("↑/↓", "Select"), ("u", "Update"), ("b", "Rollback"), ("R", "Refresh"), ("v", "Stacks"), ("?", "Help"), ("q", "Quit"),
+        ],
+        View::Stacks => vec![
+            ("↑/↓", "Select"), ("d", "Remove Stack"), ("R", "Refresh"), ("v", "Nodes"), ("?", "Help"), ("q", "Quit"),
+        ],
+        View::Nodes => vec![
+            ("↑/↓", "Select"), ("d", "Drain"), ("a", "Activate"), ("R", "Refresh"), ("v", "System"), ("?", "Help"), ("q", "Quit"),
+        ],
+        View::System => vec![
+            ("↑/↓", "Select"), ("Enter", "Run Cleanup"), ("p", "Prune Wizard"), ("c", "Prune Build Cache"), ("n", "New from Template"), ("R", "Re-analyze"), ("v", "Containers"), ("?", "Help"), ("q", "Quit"),
+        ],
+    }
+}
+
+pub fn hint_spans(app: &App) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (i, (key, desc)) in key_hints(app).into_iter().enumerate() {
+        spans.push(Span::raw(if i == 0 { " " } else { " | " }));
+        spans.push(Span::styled(key, Style::default().add_modifier(Modifier::BOLD)));
+        spans.push(Span::raw(format!(": {}", desc)));
+    }
+    spans
+}
@@ -1,10 +1,12 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 use crate::app::App;
+use crate::ui::layout::{centered_rect, centered_rect_fixed_height};
 
 pub fn render_image_details(f: &mut Frame<'_>, area: Rect, app: &App) {
     let details_lock = app.selected_image_details.read().unwrap();
@@ -33,13 +35,14 @@ pub fn render_image_context(f: &mut Frame<'_>, area: Rect, app: &App) {
         .border_style(Style::default().fg(Color::Cyan));
 
     // Check if pulling
-    if !app.pull_progress.read().unwrap().is_empty() || app.is_pulling.load(std::sync::atomic::Ordering::Relaxed) {
-         let progress = app.pull_progress.read().unwrap();
+    let is_pulling = app.is_pulling.load(std::sync::atomic::Ordering::Relaxed);
+    if !app.output_log.read().unwrap().is_empty() || is_pulling {
+         let progress = app.output_log.read().unwrap();
          // Show last few lines
          let progress_text: String = progress.iter().rev().take(10).rev().cloned().collect::<Vec<String>>().join("\n");
          
          let paragraph = Paragraph::new(progress_text)
-            .block(block.title(" Pull Progress "))
+            .block(block.title(if is_pulling { " Pull Progress " } else { " Output " }))
             .wrap(Wrap { trim: true });
          f.render_widget(paragraph, area);
     } else {
@@ -52,13 +55,13 @@ pub fn render_image_context(f: &mut Frame<'_>, area: Rect, app: &App) {
 }
 
 pub fn render_pull_dialog(f: &mut Frame<'_>, area: Rect, app: &App) {
-    if !app.show_pull_dialog {
-        return;
-    }
-
-    let area = centered_rect(50, 10, area); // Smaller height for just input
+    let info = &app.registry_info;
+    let proxy = info.https_proxy.as_ref().or(info.http_proxy.as_ref());
+    let hub_warning = if crate::ratelimit::is_docker_hub(&app.pull_reference()) { app.hub_pull_warning(1) } else { None };
+    let multi_host = !app.remote_clients.is_empty();
+    let area = centered_rect_fixed_height(50, 8 + u16::from(multi_host) + u16::from(proxy.is_some()) + u16::from(hub_warning.is_some()), area);
     f.render_widget(Clear, area);
-    
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow))
@@ -66,53 +69,56 @@ pub fn render_pull_dialog(f: &mut Frame<'_>, area: Rect, app: &App) {
         
     f.render_widget(block, area);
 
-    let inner = Layout::default()
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
-        .constraints([Constraint::Min(1)])
-        .split(area)[0];
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(area);
 
-    // Input
-    let input_text = format!("> {}", app.pull_input);
-    let input = Paragraph::new(input_text);
-    f.render_widget(input, inner);
-}
+    app.pull_input.render(f, chunks[0], true);
 
-pub fn render_delete_confirm(f: &mut Frame<'_>, area: Rect, app: &App) {
-    if !app.show_delete_confirm {
-        return;
+    let dim = Style::default().fg(Color::DarkGray);
+    let target = match app.pull_registry() {
+        Some(registry) => Span::styled(format!("< {} >", registry), Style::default().fg(Color::Cyan)),
+        None => Span::raw("< as typed >"),
+    };
+    let mut lines = vec![Line::from(vec![Span::styled("Registry: ", dim), target, Span::styled("  (Tab to change)", dim)])];
+    if multi_host {
+        let host = app.pull_host.as_deref().unwrap_or(&app.docker_context);
+        lines.push(Line::from(vec![
+            Span::styled("Pull on:  ", dim),
+            Span::styled(format!("< {} >", host), Style::default().fg(Color::Cyan)),
+            Span::styled("  (Shift+Tab to change)", dim),
+        ]));
     }
-    
+    lines.push(Line::from(vec![Span::styled("Fetched by: ", dim), Span::raw(app.pull_side())]));
+    if app.pull_registry().is_some() && !app.pull_input.value().trim().is_empty() {
+        lines.push(Line::from(vec![Span::styled("Pulls:    ", dim), Span::raw(app.pull_reference())]));
+    } else if !info.mirrors.is_empty() {
+        lines.push(Line::from(vec![Span::styled("Mirrors:  ", dim), Span::raw(info.mirrors.join(", "))]));
+    } else {
+        lines.push(Line::from(Span::styled("No registry mirrors configured on the daemon", dim)));
+    }
+    if let Some(proxy) = proxy {
+        lines.push(Line::from(vec![Span::styled("Proxy:    ", dim), Span::raw(proxy.clone())]));
+    }
+    if let Some(warning) = hub_warning {
+        lines.push(Line::from(Span::styled(warning, Style::default().fg(Color::Red))));
+    }
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+}
+
+pub fn render_delete_confirm(f: &mut Frame<'_>, area: Rect, force: bool) {
     let area = centered_rect(40, 10, area);
     f.render_widget(Clear, area);
     
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Red))
-        .title(" Confirm Deletion ");
+        .title(if force { " Confirm Forced Deletion " } else { " Confirm Deletion " });
         
     let text = "Are you sure you want to delete the selected image?\nPress 'y' to confirm, 'n' or Esc to cancel.";
     let p = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
     
     f.render_widget(p, area);
 }
-
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
@@ -1,12 +1,14 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
     widgets::{Block, Borders, Cell, Row, Table},
     Frame,
     layout::Constraint,
 };
 use chrono::{DateTime, Utc};
-use crate::app::{App, SortOrder};
+use crate::app::{format_updated, App, SortOrder, IMAGE_USAGE_DAYS};
+use crate::types::{ImageUsage, ScanSummary};
 
 fn format_bytes(bytes: u64) -> String {
     const GB: u64 = 1024 * 1024 * 1024;
@@ -36,8 +38,47 @@ fn format_time(timestamp: i64) -> String {
     }
 }
 
+/// Usage cell colored like a heatmap: unused images are dimmed, heavily used ones stand out.
+fn usage_cell(usage: ImageUsage) -> Cell<'static> {
+    if usage.containers == 0 && usage.last_run.is_none() {
+        return Cell::from("unused").style(Style::default().fg(Color::DarkGray));
+    }
+    let color = match usage.containers {
+        0..=1 => Color::Yellow,
+        2..=4 => Color::LightRed,
+        _ => Color::Red,
+    };
+    let text = match usage.last_run {
+        Some(ts) => format!("{} ctr · {}", usage.containers, format_time(ts)),
+        None => format!("{} ctr", usage.containers),
+    };
+    Cell::from(text).style(Style::default().fg(color))
+}
+
+/// Severity badge: critical/high/medium counts, or `-` until the image is scanned.
+fn scan_cell(scan: Option<&ScanSummary>) -> Cell<'static> {
+    let Some(scan) = scan else {
+        return Cell::from("-").style(Style::default().fg(Color::DarkGray));
+    };
+    if scan.critical + scan.high + scan.medium == 0 {
+        return Cell::from("clean").style(Style::default().fg(Color::Green));
+    }
+    let badge = |label: &str, count: u32, color: Color| {
+        let style = if count > 0 { Style::default().fg(color).bold() } else { Style::default().fg(Color::DarkGray) };
+        Span::styled(format!("{}{} ", label, count), style)
+    };
+    Cell::from(Line::from(vec![
+        badge("C", scan.critical, Color::Red),
+        badge("H", scan.high, Color::LightRed),
+        badge("M", scan.medium, Color::Yellow),
+    ]))
+}
+
 pub fn render_image_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
     let images = app.images.read().unwrap();
+    let usage = app.image_usage.read().unwrap();
+    let scans = app.image_scans.read().unwrap();
+    let scanning = app.config.read().unwrap().scan.enabled;
     
     // Prepare Headers with Sort Indicator
     let mut headers = vec![
@@ -45,16 +86,20 @@ pub fn render_image_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
         "TAG".to_string(), 
         "IMAGE ID".to_string(), 
         "SIZE".to_string(), 
-        "CREATED".to_string()
+        "CREATED".to_string(),
+        format!("USAGE ({}d)", IMAGE_USAGE_DAYS),
     ];
+    if scanning {
+        headers.push("VULNS".to_string());
+    }
 
     match app.image_sort {
         SortOrder::SizeDesc => headers[3].push_str(" ▼"),
         SortOrder::SizeAsc => headers[3].push_str(" ▲"),
         SortOrder::CreatedDesc => headers[4].push_str(" ▼"),
         SortOrder::CreatedAsc => headers[4].push_str(" ▲"),
-        SortOrder::HealthDesc | SortOrder::HealthAsc => {
-            // Health sort not applicable to images, no indicator shown
+        SortOrder::HealthDesc | SortOrder::HealthAsc | SortOrder::CpuDesc | SortOrder::MemDesc | SortOrder::NameAsc | SortOrder::UptimeDesc => {
+            // Container sorts not applicable to images, no indicator shown
         }
     }
 
@@ -77,28 +122,46 @@ pub fn render_image_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
             ("<none>".to_string(), "<none>".to_string())
         };
 
-        let cells = vec![
+        let mut cells = vec![
             Cell::from(repo).style(Style::default().fg(Color::Cyan)),
             Cell::from(tag),
             Cell::from(i.id.clone()),
             Cell::from(format_bytes(i.size as u64)),
             Cell::from(format_time(i.created)),
+            usage_cell(i.usage(&usage)),
         ];
+        if scanning {
+            cells.push(scan_cell(scans.get(&i.id)));
+        }
         Row::new(cells).height(1)
     });
 
-    let widths = [
-        Constraint::Percentage(30),
-        Constraint::Percentage(20),
-        Constraint::Percentage(15),
-        Constraint::Percentage(15),
-        Constraint::Percentage(20),
-    ];
+    let widths = if scanning {
+        vec![
+            Constraint::Percentage(22),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+            Constraint::Percentage(10),
+            Constraint::Percentage(11),
+            Constraint::Percentage(17),
+            Constraint::Percentage(16),
+        ]
+    } else {
+        vec![
+            Constraint::Percentage(25),
+            Constraint::Percentage(15),
+            Constraint::Percentage(14),
+            Constraint::Percentage(12),
+            Constraint::Percentage(14),
+            Constraint::Percentage(20),
+        ]
+    };
 
-    let title_text = format!(" Images ({}) - Space: {} {} ", 
-        app.total_images, 
+    let title_text = format!(" Images ({}) [{}] [sort: {}] - Space: {} ",
+        app.total_images,
+        if app.show_dangling.load(std::sync::atomic::Ordering::Relaxed) { "all" } else { "hide dangling" },
+        app.image_sort.label(),
         format_bytes(app.total_image_size),
-        if app.show_dangling.load(std::sync::atomic::Ordering::Relaxed) { "[ALL]" } else { "[Hide Dangling]" }
     );
 
     let table = Table::new(rows, widths)
@@ -107,6 +170,10 @@ pub fn render_image_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(title_text)
+                .title_bottom(Line::styled(
+                    format!(" {} ", format_updated(app.images_updated.load(std::sync::atomic::Ordering::Relaxed))),
+                    Style::default().fg(Color::DarkGray),
+                ).right_aligned())
                 .border_style(Style::default().fg(Color::Magenta))
         )
         .highlight_style(
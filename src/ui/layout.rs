@@ -43,3 +43,35 @@ pub fn get_graphs_layout(area: Rect) -> (Rect, Rect) {
         .split(area);
     (chunks[0], chunks[1])
 }
+
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// Like `centered_rect`, but with a fixed height in rows (used by forms sized to their content).
+pub fn centered_rect_fixed_height(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let height = height.min(r.height);
+    let area = Rect {
+        x: r.x,
+        y: r.y + (r.height - height) / 2,
+        width: r.width,
+        height,
+    };
+    centered_rect(percent_x, 100, area)
+}
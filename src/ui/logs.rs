@@ -1,39 +1,144 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, List, ListItem},
     text::{Line, Span},
     Frame,
 };
+use chrono::{DateTime, Local};
 use crate::app::App;
+use crate::docker::logs::line_time;
+use crate::types::LifecycleEvent;
+use crate::ui::timeline::event_color;
+
+const SERVICE_COLORS: [Color; 8] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::LightRed,
+    Color::LightCyan,
+    Color::LightMagenta,
+];
+
+fn service_color(service: &str) -> Color {
+    let hash = service.trim().bytes().fold(0usize, |acc, b| acc.wrapping_mul(31).wrapping_add(b as usize));
+    SERVICE_COLORS[hash % SERVICE_COLORS.len()]
+}
+
+fn line_style(text: &str) -> Style {
+    let lower = text.to_lowercase();
+    if lower.contains("error") {
+        Style::default().fg(Color::Red)
+    } else if lower.contains("warn") {
+        Style::default().fg(Color::Yellow)
+    } else if lower.contains("info") {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default().fg(Color::White)
+    }
+}
+
+/// Number of newest lines pinned below the list while scrolling back.
+const LIVE_TAIL_LINES: usize = 3;
+
+fn log_item(log: &str, project_mode: bool) -> ListItem<'_> {
+    // Project mode lines look like "service | message"
+    if project_mode {
+        if let Some((service, rest)) = log.split_once(" | ") {
+            return ListItem::new(Line::from(vec![
+                Span::styled(format!("{} | ", service), Style::default().fg(service_color(service)).add_modifier(Modifier::BOLD)),
+                Span::styled(rest, line_style(rest)),
+            ]));
+        }
+    }
+    ListItem::new(Line::from(Span::styled(log, line_style(log))))
+}
+
+fn event_divider(event: &LifecycleEvent) -> ListItem<'static> {
+    let time = DateTime::from_timestamp(event.time, 0)
+        .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+    let detail = if event.detail.is_empty() { String::new() } else { format!(" ({})", event.detail) };
+    let text = format!("── {} {}{} ──", time, event.action.replace('_', " "), detail);
+    ListItem::new(Line::from(Span::styled(text, Style::default().fg(event_color(event)).add_modifier(Modifier::BOLD))))
+}
+
+/// Log lines with the container's Docker events placed between them by time,
+/// and the item index of each log line. Events older than the first line are
+/// left out, since the lines around them aren't loaded.
+fn with_events<'a>(logs: &'a [String], events: &[LifecycleEvent]) -> (Vec<ListItem<'a>>, Vec<usize>) {
+    let mut events: Vec<&LifecycleEvent> = events.iter().collect();
+    events.sort_by_key(|e| e.time_nano);
+    let mut events = events.into_iter().peekable();
+    let mut items = Vec::with_capacity(logs.len());
+    let mut positions = Vec::with_capacity(logs.len());
+    let mut started = false;
+    for log in logs {
+        if let Some(time) = line_time(log) {
+            while let Some(event) = events.next_if(|e| e.time_nano <= time) {
+                if started {
+                    items.push(event_divider(event));
+                }
+            }
+            started = true;
+        }
+        positions.push(items.len());
+        items.push(log_item(log, false));
+    }
+    if started {
+        for event in events {
+            items.push(event_divider(event));
+        }
+    }
+    (items, positions)
+}
 
 pub fn render_container_logs(f: &mut Frame<'_>, area: Rect, app: &App) {
     let logs_lock = app.selected_container_logs.read().unwrap();
-    
-    let logs_items: Vec<ListItem> = logs_lock
-        .iter()
-        .map(|log| {
-             let lower = log.to_lowercase();
-             let style = if lower.contains("error") {
-                 Style::default().fg(Color::Red)
-             } else if lower.contains("warn") {
-                 Style::default().fg(Color::Yellow)
-             } else if lower.contains("info") {
-                 Style::default().fg(Color::Green)
-             } else {
-                 Style::default().fg(Color::White)
-             };
-             ListItem::new(Line::from(Span::styled(log.as_str(), style)))
-        })
-        .collect();
-
-    let title = if app.auto_scroll {
-        " Logs (Live - Auto Scroll) "
+    let project = if app.project_logs { app.selected_project() } else { None };
+
+    // A single container's events are interleaved; a project's would need every member's
+    let events = match (&project, &app.last_fetched_id) {
+        (None, Some(id)) => app.container_timelines.read().unwrap()
+            .get(id)
+            .map(|t| t.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    let (logs_items, positions) = if events.is_empty() {
+        let items: Vec<ListItem> = logs_lock
+            .iter()
+            .map(|log| log_item(log, project.is_some()))
+            .collect();
+        let positions = (0..items.len()).collect();
+        (items, positions)
     } else {
-        " Logs (Live - Manual Scroll) "
+        with_events(&logs_lock, &events)
     };
+    let item_count = logs_items.len();
 
-    let border_style = if app.focus == crate::app::Focus::Logs {
+    // While reading history, keep the newest lines visible in a strip at the bottom
+    let scrolled_back = !app.auto_scroll
+        && app.logs_state.selected().is_some_and(|i| i + LIVE_TAIL_LINES < logs_lock.len());
+    let (list_area, tail_area) = if scrolled_back && area.height > 12 {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(LIVE_TAIL_LINES as u16 + 2)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (area, None)
+    };
+
+    let scroll_mode = if app.auto_scroll { "Auto Scroll" } else { "Manual Scroll" };
+    let title = match &project {
+        Some(p) => format!(" Logs (Project: {} - {}) ", p, scroll_mode),
+        None => format!(" Logs (Live - {}) ", scroll_mode),
+    };
+
+    let border_style = if app.focus.is(crate::app::Focus::Logs) {
         Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(Color::Yellow)
@@ -49,5 +154,27 @@ pub fn render_container_logs(f: &mut Frame<'_>, area: Rect, app: &App) {
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
 
     let mut state = app.logs_state.clone();
-    f.render_stateful_widget(logs_list, area, &mut state);
+    // Selection counts log lines; map it past the dividers, and follow trailing events too
+    let selected = if app.auto_scroll && item_count > 0 {
+        Some(item_count - 1)
+    } else {
+        app.logs_state.selected().map(|i| positions.get(i).copied().unwrap_or(i))
+    };
+    state.select(selected);
+    f.render_stateful_widget(logs_list, list_area, &mut state);
+
+    if let Some(tail_area) = tail_area {
+        let tail_items: Vec<ListItem> = logs_lock
+            .iter()
+            .skip(logs_lock.len().saturating_sub(LIVE_TAIL_LINES))
+            .map(|log| log_item(log, project.is_some()))
+            .collect();
+        let tail = List::new(tail_items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Live Tail ")
+                .border_style(Style::default().fg(Color::DarkGray))
+        );
+        f.render_widget(tail, tail_area);
+    }
 }
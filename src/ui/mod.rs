@@ -5,16 +5,88 @@ pub mod logs;
 pub mod help;
 pub mod image_list;
 pub mod image_details;
+pub mod compose;
+pub mod toast;
+pub mod viewer;
+pub mod hints;
+pub mod quick_info;
+pub mod node_list;
+pub mod top;
+pub mod service_list;
+pub mod stack_list;
+pub mod system;
+pub mod timeline;
+pub mod error_console;
+pub mod contexts;
+pub mod diagnostics;
+pub mod files;
+pub mod processes;
+pub mod privacy;
+pub mod exec;
+pub mod widgets;
 
 use ratatui::Frame;
-use crate::app::{App, View};
-use crate::ui::layout::{get_main_layout, get_right_pane_layout};
+use crate::app::{App, Modal, StateFilter, View};
+use crate::ui::layout::{centered_rect_fixed_height, get_main_layout, get_right_pane_layout};
+use ratatui::style::Color;
 use crate::ui::container_details::{render_container_details, render_health_log_dialog};
-use crate::ui::container_list::render_container_list;
+use crate::ui::container_list::{render_bulk_command, render_bulk_command_confirm, render_container_list, render_container_search, render_remove_marked_confirm};
 use crate::ui::logs::render_container_logs;
 use crate::ui::help::render_help;
 use crate::ui::image_list::render_image_list;
 use crate::ui::image_details::{render_image_details, render_pull_dialog, render_image_context, render_delete_confirm};
+use crate::ui::compose::render_project_menu;
+use crate::ui::toast::{render_quit_confirm, render_toast};
+use crate::ui::viewer::render_text_viewer;
+use crate::ui::timeline::render_timeline;
+use crate::ui::error_console::render_error_console;
+use crate::ui::contexts::{render_context_picker, render_workspace_picker};
+use crate::ui::diagnostics::render_diagnostics;
+use crate::ui::files::{render_file_browser, render_fs_diff, render_upload_overwrite_confirm};
+use crate::ui::processes::render_processes;
+use crate::ui::privacy::render_privacy_cover;
+use crate::ui::exec::render_exec_chooser;
+use crate::types::PrivacyMode;
+use crate::ui::hints::hint_spans;
+use crate::ui::quick_info::render_quick_info;
+use crate::ui::node_list::{render_drain_node_confirm, render_node_list};
+use crate::ui::top::render_top;
+use crate::ui::service_list::render_service_list;
+use crate::ui::stack_list::{render_remove_stack_confirm, render_stack_list};
+use crate::ui::system::{render_cleanup_volumes_confirm, render_prune_wizard, render_system};
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Running/stopped/paused/unhealthy counts as clickable badges, the active
+/// filter's underlined. Stopped containers are only counted once listed.
+fn count_badges(app: &App) -> Vec<(ratatui::text::Span<'static>, StateFilter)> {
+    use ratatui::style::{Modifier, Style};
+    let containers = app.containers.read().unwrap();
+    let health = app.container_health.read().unwrap();
+    let count = |state: &[&str]| containers.iter().filter(|c| state.contains(&c.state.as_str())).count().to_string();
+    let stopped = if app.show_all.load(std::sync::atomic::Ordering::Relaxed) { count(&["exited", "created", "dead"]) } else { "?".to_string() };
+    let unhealthy = containers.iter()
+        .filter(|c| c.state == "running" && health.get(&c.id).is_some_and(|h| h.status == crate::types::HealthStatus::Unhealthy))
+        .count();
+
+    let active = |filter: StateFilter| match filter {
+        StateFilter::Unhealthy => app.health_filter == crate::app::HealthFilter::Unhealthy,
+        _ => app.state_filter == Some(filter),
+    };
+    StateFilter::ALL.into_iter().map(|filter| {
+        let (text, bg) = match filter {
+            StateFilter::Running => (format!(" ● {} ", count(&["running"])), Color::Green),
+            StateFilter::Stopped => (format!(" ■ {} ", stopped), Color::DarkGray),
+            StateFilter::Paused => (format!(" ‖ {} ", count(&["paused"])), Color::Yellow),
+            StateFilter::Unhealthy => (format!(" ✗ {} ", unhealthy), if unhealthy > 0 { Color::Red } else { Color::DarkGray }),
+        };
+        let mut style = Style::default().fg(Color::Black).bg(bg);
+        if active(filter) {
+            style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        (ratatui::text::Span::styled(text, style), filter)
+    }).collect()
+}
 
 pub fn draw(f: &mut Frame<'_>, app: &mut App) {
     let area = f.area();
@@ -30,6 +102,7 @@ pub fn draw(f: &mut Frame<'_>, app: &mut App) {
     
     let main_area = chunks[0];
     let status_area = chunks[1];
+    let covered = app.privacy_cover.then(|| app.config.read().unwrap().privacy.mode);
     
     match app.current_view {
         View::Containers => {
@@ -39,10 +112,14 @@ pub fn draw(f: &mut Frame<'_>, app: &mut App) {
             render_container_details(f, left, app);
             render_container_list(f, top_right, app);
             render_container_logs(f, bottom_right, app);
-            
-            // Modal
-            render_health_log_dialog(f, main_area, app);
+            if covered.is_some() {
+                render_privacy_cover(f, left);
+                render_privacy_cover(f, bottom_right);
+            } else {
+                render_quick_info(f, main_area, app);
+            }
         },
+        View::Top => render_top(f, main_area, app),
         View::Images => {
              let (left, right) = get_main_layout(main_area);
              let (top_right, bottom_right) = get_right_pane_layout(right);
@@ -50,10 +127,46 @@ pub fn draw(f: &mut Frame<'_>, app: &mut App) {
              render_image_details(f, left, app);
              render_image_list(f, top_right, app);
              render_image_context(f, bottom_right, app);
-             
-             // Modals
-             render_pull_dialog(f, main_area, app);
-             render_delete_confirm(f, main_area, app);
+             if covered.is_some() {
+                 render_privacy_cover(f, left);
+             }
+        }
+        View::Services => {
+            let chunks = ratatui::layout::Layout::default()
+                .direction(ratatui::layout::Direction::Vertical)
+                .constraints([
+                    ratatui::layout::Constraint::Percentage(60),
+                    ratatui::layout::Constraint::Percentage(40),
+                ])
+                .split(main_area);
+
+            render_service_list(f, chunks[0], app);
+            render_image_context(f, chunks[1], app);
+        }
+        View::Stacks => {
+            let chunks = ratatui::layout::Layout::default()
+                .direction(ratatui::layout::Direction::Vertical)
+                .constraints([
+                    ratatui::layout::Constraint::Percentage(60),
+                    ratatui::layout::Constraint::Percentage(40),
+                ])
+                .split(main_area);
+
+            render_stack_list(f, chunks[0], app);
+            render_image_context(f, chunks[1], app);
+        }
+        View::Nodes => render_node_list(f, main_area, app),
+        View::System => {
+            let chunks = ratatui::layout::Layout::default()
+                .direction(ratatui::layout::Direction::Vertical)
+                .constraints([
+                    ratatui::layout::Constraint::Min(0),
+                    ratatui::layout::Constraint::Length(8),
+                ])
+                .split(main_area);
+
+            render_system(f, chunks[0], app);
+            render_image_context(f, chunks[1], app);
         }
     }
     
@@ -84,23 +197,144 @@ pub fn draw(f: &mut Frame<'_>, app: &mut App) {
     
     let perf_span = ratatui::text::Span::styled(perf_text, ratatui::style::Style::default().fg(ratatui::style::Color::Yellow).bg(ratatui::style::Color::Blue));
 
-    let help_text = match app.current_view {
-        View::Containers => " Shift+Tab/v: Images | ?: Help | q: Quit | ↑/↓: Select | s: Stop | S: Start | r: Restart | d: Remove | T: Turbo | [/]: Refresh Rate",
-        View::Images => " Shift+Tab/v: Containers | ?: Help | q: Quit | ↑/↓: Select | p: Pull | d: Remove | Enter: Details",
+    let context_span = ratatui::text::Span::styled(
+        match &app.workspace {
+            Some(workspace) => format!(" ws: {} · ctx: {}{} ", workspace, app.docker_context, if app.docker.podman { " (podman)" } else { "" }),
+            None => format!(" ctx: {}{} ", app.docker_context, if app.docker.podman { " (podman)" } else { "" }),
+        },
+        ratatui::style::Style::default().fg(ratatui::style::Color::Black).bg(ratatui::style::Color::Cyan)
+    );
+
+    // Manual refresh state of the current view: spinner while in flight, otherwise when it last ran
+    let view_refresh = app.view_refresh.read().unwrap().get(&app.current_view).copied().unwrap_or_default();
+    let refreshed_text = match (view_refresh.started, view_refresh.finished_at) {
+        (Some(started), _) => {
+            let frame = (started.elapsed().as_millis() / 100) as usize % SPINNER_FRAMES.len();
+            format!(" {} refreshing ", SPINNER_FRAMES[frame])
+        }
+        (None, Some(ts)) => chrono::DateTime::from_timestamp(ts, 0)
+            .map(|t| format!(" refreshed {} ", t.with_timezone(&chrono::Local).format("%H:%M:%S")))
+            .unwrap_or_default(),
+        (None, None) => String::new(),
     };
-    
-    let status_line = ratatui::text::Line::from(vec![
-        mode_indicator,
-        refresh_info,
-        perf_span,
-        ratatui::text::Span::raw(help_text),
-    ]);
+    let refreshed_span = ratatui::text::Span::styled(refreshed_text, ratatui::style::Style::default().fg(ratatui::style::Color::Gray).bg(ratatui::style::Color::Blue));
+
+    let mut status_spans = vec![mode_indicator];
+    if app.read_only {
+        status_spans.push(ratatui::text::Span::styled(
+            " 🔒 READ-ONLY ",
+            ratatui::style::Style::default().fg(ratatui::style::Color::Black).bg(ratatui::style::Color::Yellow).add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+    }
+    status_spans.extend([refresh_info, context_span]);
+    app.status_badges.clear();
+    app.status_bar_row = status_area.y;
+    if app.current_view == View::Containers {
+        for (badge, filter) in count_badges(app) {
+            let start = status_area.x + status_spans.iter().map(|s| s.width() as u16).sum::<u16>();
+            app.status_badges.push((start..start + badge.width() as u16, filter));
+            status_spans.push(badge);
+        }
+    }
+    status_spans.extend([refreshed_span, perf_span]);
+    if let Some(limit) = *app.hub_rate_limit.read().unwrap() {
+        let warn_below = app.config.read().unwrap().hub_rate_limit.warn_below;
+        let color = if limit.remaining < warn_below { ratatui::style::Color::Red } else { ratatui::style::Color::DarkGray };
+        status_spans.push(ratatui::text::Span::styled(
+            format!(" hub: {}/{} pulls ", limit.remaining, limit.limit),
+            ratatui::style::Style::default().fg(ratatui::style::Color::White).bg(color),
+        ));
+    }
+    let unread = app.unread_errors();
+    if unread > 0 && !app.show_error_console {
+        status_spans.push(ratatui::text::Span::styled(
+            format!(" ! {} error{} ", unread, if unread == 1 { "" } else { "s" }),
+            ratatui::style::Style::default().fg(ratatui::style::Color::White).bg(ratatui::style::Color::Red).add_modifier(ratatui::style::Modifier::BOLD),
+        ));
+    }
+    status_spans.extend(hint_spans(app));
+    let status_line = ratatui::text::Line::from(status_spans);
 
     let status_bar = ratatui::widgets::Paragraph::new(status_line)
         .style(ratatui::style::Style::default().bg(ratatui::style::Color::Blue).fg(ratatui::style::Color::White));
     f.render_widget(status_bar, status_area);
 
-    if app.show_help {
-        render_help(f, area, app);
+    render_disconnected_banner(f, main_area, app);
+
+    match covered {
+        Some(PrivacyMode::Hide) => {
+            render_privacy_cover(f, area);
+            return;
+        }
+        // Dialogs show env vars, logs and file contents, so none of them stay up
+        Some(PrivacyMode::Blur) => {
+            render_toast(f, main_area, app);
+            return;
+        }
+        None => {}
+    }
+
+    if app.show_error_console {
+        render_error_console(f, main_area, app);
     }
+
+    // Modals, bottom of the stack first so the top one is drawn last
+    for modal in app.modals.clone() {
+        match modal {
+            Modal::Help => render_help(f, area, app),
+            Modal::PullImage => render_pull_dialog(f, main_area, app),
+            Modal::DeleteImageConfirm { force } => render_delete_confirm(f, main_area, force),
+            Modal::RemoveStackConfirm(name) => render_remove_stack_confirm(f, main_area, &name),
+            Modal::DrainNodeConfirm(node) => render_drain_node_confirm(f, main_area, &node.hostname),
+            Modal::CleanupVolumesConfirm(names) => render_cleanup_volumes_confirm(f, main_area, &names),
+            Modal::RemoveMarkedConfirm(count) => render_remove_marked_confirm(f, main_area, count),
+            Modal::QuitConfirm(count) => render_quit_confirm(f, main_area, count),
+            Modal::PruneWizard { step, estimates, volumes } => render_prune_wizard(f, main_area, &step, &estimates, &volumes),
+            Modal::HealthLog(content) => render_health_log_dialog(f, main_area, &content),
+            Modal::ProjectMenu => render_project_menu(f, main_area, app),
+            Modal::TextViewer { title, content, syntax } => render_text_viewer(f, main_area, app, &title, &content, syntax),
+            Modal::Timeline { container_id, container_name } => render_timeline(f, main_area, app, &container_id, &container_name),
+            Modal::ContextPicker { contexts, selected } => render_context_picker(f, main_area, &contexts, selected, &app.docker_context),
+            Modal::WorkspacePicker { workspaces, selected } => render_workspace_picker(f, main_area, &workspaces, selected, app.workspace.as_deref()),
+            Modal::Diagnostics(report) => render_diagnostics(f, main_area, app, &report),
+            Modal::Files { container_name, path, entries, selected, .. } => render_file_browser(f, main_area, &container_name, &path, &entries, selected),
+            Modal::Processes { container_name, list, selected, .. } => render_processes(f, main_area, &container_name, list.read().unwrap().as_ref(), selected),
+            Modal::FsDiff { container_name, changes, filter, selected, .. } => render_fs_diff(f, main_area, &container_name, &changes, filter, selected),
+            Modal::UploadOverwriteConfirm { container_name, source, dest, .. } => render_upload_overwrite_confirm(f, main_area, &container_name, &source, &dest),
+            Modal::ExecChooser { container_name, commands, selected, .. } => render_exec_chooser(f, main_area, &container_name, &commands, selected),
+            Modal::ContainerSearch => render_container_search(f, app),
+            Modal::BulkCommand => render_bulk_command(f, app),
+            Modal::BulkCommandConfirm { command, action, targets } => render_bulk_command_confirm(f, main_area, &command, action, &targets),
+            Modal::ScaleService { form, .. } | Modal::UpdateService { form, .. } | Modal::ExportStats { form, .. } | Modal::PruneBuildCache { form } | Modal::CreateFromTemplate { form } | Modal::SendSignal { form, .. } | Modal::SendStdin { form, .. } | Modal::EditLabels { form, .. } | Modal::Duplicate { form, .. } | Modal::Rename { form, .. } | Modal::Commit { form, .. } | Modal::Netem { form, .. } | Modal::Chaos { form, .. } | Modal::ExportFilesystem { form, .. } | Modal::IncidentReport { form, .. } | Modal::Upload { form, .. } | Modal::ExecCustom { form, .. } | Modal::Migrate { form, .. } | Modal::EditContainer { form, .. } | Modal::JumpToTime { form } | Modal::GroupBy { form } => {
+                let area = centered_rect_fixed_height(50, form.content_height() + 2, main_area);
+                form.render(f, area, Color::Yellow);
+            }
+        }
+    }
+
+    render_toast(f, main_area, app);
+    if app.is_flashing() {
+        f.buffer_mut().set_style(area, ratatui::style::Style::default().add_modifier(ratatui::style::Modifier::REVERSED));
+    }
+}
+
+/// Persistent line across the top of the view while the daemon is unreachable,
+/// so the data below is not mistaken for live.
+fn render_disconnected_banner(f: &mut Frame<'_>, area: ratatui::layout::Rect, app: &App) {
+    let state = *app.connection.read().unwrap();
+    let Some(since) = state.down_since else { return };
+    let now = chrono::Utc::now().timestamp();
+    let text = format!(
+        " ⚠ Disconnected from {} for {}s — retrying in {}s (attempt {}) ",
+        app.docker_context,
+        now - since,
+        (state.next_retry - now).max(0),
+        state.attempts,
+    );
+    let area = ratatui::layout::Rect { height: 1.min(area.height), ..area };
+    let banner = ratatui::widgets::Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .style(ratatui::style::Style::default().fg(Color::White).bg(Color::Red).add_modifier(ratatui::style::Modifier::BOLD));
+    f.render_widget(ratatui::widgets::Clear, area);
+    f.render_widget(banner, area);
 }
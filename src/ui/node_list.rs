@@ -0,0 +1,89 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+use crate::app::{format_bytes, App};
+use crate::types::NodeAvailability;
+use crate::ui::layout::centered_rect;
+
+pub fn render_node_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Swarm Nodes ({}) ", app.nodes.len()))
+        .border_style(Style::default().fg(Color::Magenta));
+
+    if let Some(error) = &app.nodes_error {
+        let message = Paragraph::new(format!("Swarm nodes unavailable: {}\n\nNodes can only be listed on a swarm manager.", error))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(block);
+        f.render_widget(message, area);
+        return;
+    }
+
+    let header_cells = ["HOSTNAME", "ROLE", "STATUS", "AVAILABILITY", "ENGINE", "CPUS", "MEMORY"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()));
+    let header = Row::new(header_cells).height(1);
+
+    let rows = app.nodes.iter().map(|n| {
+        let role = if n.is_leader { format!("{} (leader)", n.role) } else { n.role.clone() };
+        let state_color = if n.state == "ready" { Color::Green } else { Color::Red };
+        let availability_color = match n.availability {
+            NodeAvailability::Active => Color::Green,
+            NodeAvailability::Pause => Color::Yellow,
+            NodeAvailability::Drain => Color::Red,
+        };
+
+        let cells = vec![
+            Cell::from(n.hostname.clone()).style(Style::default().fg(Color::Cyan)),
+            Cell::from(role),
+            Cell::from(n.state.clone()).style(Style::default().fg(state_color)),
+            Cell::from(n.availability.label()).style(Style::default().fg(availability_color)),
+            Cell::from(n.engine_version.clone()),
+            Cell::from(format!("{:.1}", n.nano_cpus as f64 / 1e9)),
+            Cell::from(format_bytes(n.memory_bytes.max(0) as u64)),
+        ];
+        Row::new(cells).height(1)
+    });
+
+    let widths = [
+        Constraint::Percentage(25),
+        Constraint::Percentage(15),
+        Constraint::Percentage(10),
+        Constraint::Percentage(14),
+        Constraint::Percentage(14),
+        Constraint::Percentage(8),
+        Constraint::Percentage(14),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(table, area, &mut app.table_state_nodes);
+}
+
+pub fn render_drain_node_confirm(f: &mut Frame<'_>, area: Rect, hostname: &str) {
+    let area = centered_rect(40, 10, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(" Confirm Node Drain ");
+
+    let text = format!("Drain node '{}'? Swarm stops all its tasks and reschedules them on other nodes.\nPress 'y' to confirm, 'n' or Esc to cancel.", hostname);
+    let p = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    f.render_widget(p, area);
+}
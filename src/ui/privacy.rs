@@ -0,0 +1,25 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Blank panel drawn over a pane while the idle privacy cover is up.
+pub fn render_privacy_cover(f: &mut Frame<'_>, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    let text = Paragraph::new(vec![
+        Line::styled("Hidden while idle", Style::default().fg(Color::DarkGray).add_modifier(Modifier::BOLD)),
+        Line::styled("Press any key to show", Style::default().fg(Color::DarkGray)),
+    ])
+    .alignment(Alignment::Center);
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    let middle = Rect { y: inner.y + inner.height.saturating_sub(2) / 2, height: inner.height.min(2), ..inner };
+    f.render_widget(text, middle);
+}
@@ -0,0 +1,58 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use crate::app::format_updated;
+use crate::types::ProcessList;
+use crate::ui::layout::centered_rect;
+
+pub fn render_processes(f: &mut Frame<'_>, area: Rect, container_name: &str, list: Option<&Result<ProcessList, String>>, selected: usize) {
+    let area = centered_rect(85, 75, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title_bottom(" r: Refresh | Esc: Close ");
+
+    let list = match list {
+        Some(Ok(list)) => list,
+        Some(Err(e)) => {
+            let p = Paragraph::new(format!("Failed to list processes: {}", e))
+                .style(Style::default().fg(Color::Red))
+                .block(block.title(format!(" {} processes ", container_name)))
+                .wrap(Wrap { trim: true });
+            f.render_widget(p, area);
+            return;
+        }
+        None => {
+            let p = Paragraph::new("Loading...")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block.title(format!(" {} processes ", container_name)));
+            f.render_widget(p, area);
+            return;
+        }
+    };
+
+    // The command is last and takes whatever width is left
+    let widths: Vec<Constraint> = list.titles.iter().enumerate()
+        .map(|(i, title)| {
+            if i + 1 == list.titles.len() {
+                Constraint::Min(10)
+            } else {
+                let widest = list.processes.iter().filter_map(|p| p.get(i)).map(|v| v.chars().count()).max().unwrap_or(0);
+                Constraint::Length(widest.max(title.len()).min(20) as u16)
+            }
+        })
+        .collect();
+    let rows = list.processes.iter().map(|p| Row::new(p.iter().map(|v| Cell::from(v.as_str()))));
+    let title = format!(" {}: {} processes ({}) ", container_name, list.processes.len(), format_updated(list.updated));
+    let table = Table::new(rows, widths)
+        .header(Row::new(list.titles.iter().map(|t| Cell::from(t.as_str()))).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(block.title(title))
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    let mut state = TableState::default().with_selected(Some(selected));
+    f.render_stateful_widget(table, area, &mut state);
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::types::HealthStatus;
+
+pub fn render_quick_info(f: &mut Frame<'_>, frame_area: Rect, app: &App) {
+    let Some(preview) = &app.preview else {
+        return;
+    };
+    let Some(container) = app.container_at_row(preview.row) else {
+        return;
+    };
+
+    let label_style = Style::default().fg(Color::DarkGray);
+    let health = app.container_health.read().unwrap()
+        .get(&container.id)
+        .map(|h| match h.status {
+            HealthStatus::Healthy => ("healthy".to_string(), Color::Green),
+            HealthStatus::Unhealthy => (format!("unhealthy (streak {})", h.failing_streak), Color::Red),
+            HealthStatus::Starting => ("starting".to_string(), Color::Yellow),
+            HealthStatus::NoHealthCheck => ("none".to_string(), Color::DarkGray),
+            HealthStatus::Unknown => ("unknown".to_string(), Color::Magenta),
+        })
+        .unwrap_or_else(|| ("-".to_string(), Color::DarkGray));
+
+    let mut lines = vec![
+        Line::from(vec![Span::styled("Image:  ", label_style), Span::raw(container.image.clone())]),
+        Line::from(vec![Span::styled("Status: ", label_style), Span::raw(container.status.clone())]),
+        Line::from(vec![
+            Span::styled("Ports:  ", label_style),
+            Span::raw(if container.ports.is_empty() { "-".to_string() } else { container.ports.clone() }),
+        ]),
+        Line::from(vec![Span::styled("Health: ", label_style), Span::styled(health.0, Style::default().fg(health.1))]),
+        Line::from(Span::styled("Recent logs:", label_style)),
+    ];
+    let logs = preview.logs.read().unwrap();
+    if logs.is_empty() {
+        lines.push(Line::from(Span::styled("  (none)", label_style)));
+    }
+    for log in logs.iter() {
+        lines.push(Line::from(format!("  {}", log)));
+    }
+
+    // Anchor just below the previewed row, clamped to the frame
+    let list = app.container_list_area;
+    let row_y = list.y + 2 + preview.row.saturating_sub(app.table_state.offset()) as u16;
+    let width = 70.min(frame_area.width);
+    let height = (lines.len() as u16 + 2).min(frame_area.height);
+    let x = (list.x + 4).min(frame_area.width.saturating_sub(width));
+    let y = if row_y + 1 + height <= frame_area.height { row_y + 1 } else { row_y.saturating_sub(height) };
+    let area = Rect { x, y, width, height };
+
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(format!(" {} ", container.name), Style::default().add_modifier(Modifier::BOLD)));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
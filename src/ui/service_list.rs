@@ -0,0 +1,64 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+use crate::app::App;
+
+pub fn render_service_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Swarm Services ({}) ", app.services.len()))
+        .border_style(Style::default().fg(Color::Magenta));
+
+    if let Some(error) = &app.services_error {
+        let message = Paragraph::new(format!("Swarm services unavailable: {}\n\nServices can only be listed on a swarm manager.", error))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(block);
+        f.render_widget(message, area);
+        return;
+    }
+
+    let header_cells = ["NAME", "MODE", "REPLICAS", "IMAGE", "UPDATE"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()));
+    let header = Row::new(header_cells).height(1);
+
+    let rows = app.services.iter().map(|s| {
+        let replicas_color = if s.running_tasks >= s.desired_tasks { Color::Green } else { Color::Yellow };
+        let update = s.update_state.clone().unwrap_or_default();
+        let update_color = if update.contains("paused") { Color::Red } else { Color::Gray };
+
+        let cells = vec![
+            Cell::from(s.name.clone()).style(Style::default().fg(Color::Cyan)),
+            Cell::from(s.mode.clone()),
+            Cell::from(format!("{}/{}", s.running_tasks, s.desired_tasks)).style(Style::default().fg(replicas_color)),
+            Cell::from(s.image_ref().to_string()),
+            Cell::from(update).style(Style::default().fg(update_color)),
+        ];
+        Row::new(cells).height(1)
+    });
+
+    let widths = [
+        Constraint::Percentage(25),
+        Constraint::Percentage(10),
+        Constraint::Percentage(10),
+        Constraint::Percentage(40),
+        Constraint::Percentage(15),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(table, area, &mut app.table_state_services);
+}
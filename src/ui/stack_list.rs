@@ -0,0 +1,91 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+use crate::app::App;
+use crate::ui::layout::centered_rect;
+
+pub fn render_stack_list(f: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Swarm Stacks ({}) ", app.stacks.len()))
+        .border_style(Style::default().fg(Color::Magenta));
+
+    if let Some(error) = &app.stacks_error {
+        let message = Paragraph::new(format!("Swarm stacks unavailable: {}\n\nStacks can only be listed on a swarm manager.", error))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(block);
+        f.render_widget(message, area);
+        return;
+    }
+
+    let header_cells = ["STACK", "SERVICES", "TASKS", "HEALTH"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()));
+    let header = Row::new(header_cells).height(1);
+
+    let rows = app.stacks.iter().map(|s| {
+        let (health, health_color) = if s.is_healthy() {
+            ("healthy".to_string(), Color::Green)
+        } else {
+            let mut parts = Vec::new();
+            if s.failed_tasks > 0 {
+                parts.push(format!("{} failed", s.failed_tasks));
+            }
+            if s.starting_tasks > 0 {
+                parts.push(format!("{} starting", s.starting_tasks));
+            }
+            if parts.is_empty() {
+                parts.push(format!("{} missing", s.desired_tasks.saturating_sub(s.running_tasks)));
+            }
+            let color = if s.failed_tasks > 0 { Color::Red } else { Color::Yellow };
+            (parts.join(", "), color)
+        };
+
+        let cells = vec![
+            Cell::from(s.name.clone()).style(Style::default().fg(Color::Cyan)),
+            Cell::from(s.services.to_string()),
+            Cell::from(format!("{}/{}", s.running_tasks, s.desired_tasks)),
+            Cell::from(health).style(Style::default().fg(health_color)),
+        ];
+        Row::new(cells).height(1)
+    });
+
+    let widths = [
+        Constraint::Percentage(35),
+        Constraint::Percentage(15),
+        Constraint::Percentage(15),
+        Constraint::Percentage(35),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(table, area, &mut app.table_state_stacks);
+}
+
+pub fn render_remove_stack_confirm(f: &mut Frame<'_>, area: Rect, name: &str) {
+    let area = centered_rect(40, 10, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(" Confirm Stack Removal ");
+
+    let text = format!("Remove stack '{}' with all its services, networks, secrets and configs?\nPress 'y' to confirm, 'n' or Esc to cancel.", name);
+    let p = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    f.render_widget(p, area);
+}
@@ -0,0 +1,249 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+use crate::app::{format_bytes, App, PruneStep};
+use crate::types::{BuildCacheEntry, CleanupKind, DaemonInfo};
+use chrono::Utc;
+use crate::ui::layout::centered_rect_fixed_height;
+
+pub fn render_system(f: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(10), Constraint::Length(7), Constraint::Min(0)])
+        .split(area);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[0]);
+
+    let report = app.system_report.read().unwrap().clone();
+    let error = app.system_error.read().unwrap().clone();
+
+    let Some(report) = report else {
+        let text = match error {
+            Some(e) => format!("Failed to analyze host: {}", e),
+            None => "Analyzing host...".to_string(),
+        };
+        let paragraph = Paragraph::new(text)
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL).title(" System ").border_style(Style::default().fg(Color::Magenta)));
+        f.render_widget(paragraph, area);
+        return;
+    };
+
+    // Disk usage summary
+    let header_cells = ["TYPE", "TOTAL", "ACTIVE", "SIZE", "RECLAIMABLE"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()));
+    let header = Row::new(header_cells).height(1);
+    let rows = report.usage.iter().map(|u| {
+        Row::new(vec![
+            Cell::from(u.kind).style(Style::default().fg(Color::Cyan)),
+            Cell::from(u.total.to_string()),
+            Cell::from(u.active.to_string()),
+            Cell::from(format_bytes(u.size)),
+            Cell::from(format_bytes(u.reclaimable)).style(Style::default().fg(if u.reclaimable > 0 { Color::Yellow } else { Color::Gray })),
+        ])
+    });
+    let widths = [
+        Constraint::Percentage(25),
+        Constraint::Percentage(15),
+        Constraint::Percentage(15),
+        Constraint::Percentage(20),
+        Constraint::Percentage(25),
+    ];
+    let title = match error {
+        Some(e) => format!(" Disk Usage (last refresh failed: {}) ", e),
+        None => " Disk Usage ".to_string(),
+    };
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title).border_style(Style::default().fg(Color::Magenta)));
+    f.render_widget(table, top[0]);
+    render_daemon_info(f, top[1], &report.daemon);
+
+    // Cleanup checklist
+    let items: Vec<ListItem> = report.items.iter().map(|item| {
+        let (mark, color) = if item.count == 0 { ("[✓]", Color::Green) } else { ("[ ]", Color::Yellow) };
+        let mut spans = vec![
+            Span::styled(format!("{} ", mark), Style::default().fg(color)),
+            Span::styled(format!("{: <26}", item.kind.label()), Style::default().add_modifier(Modifier::BOLD)),
+        ];
+        if item.count == 0 {
+            spans.push(Span::styled("nothing to clean", Style::default().fg(Color::DarkGray)));
+        } else {
+            spans.push(Span::raw(format!("{} found, {} reclaimable", item.count, format_bytes(item.reclaimable))));
+            if !item.detail.is_empty() {
+                spans.push(Span::styled(format!(" ({})", item.detail), Style::default().fg(Color::Gray)));
+            }
+            spans.push(Span::styled(format!("  → Enter: {}", item.kind.action()), Style::default().fg(Color::DarkGray)));
+        }
+        ListItem::new(Line::from(spans))
+    }).collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(" Cleanup Advisor ").border_style(Style::default().fg(Color::Yellow)))
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+        .highlight_symbol("▶ ");
+
+    if app.cleanup_state.selected().is_none() && !report.items.is_empty() {
+        app.cleanup_state.select(Some(0));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut app.cleanup_state);
+
+    render_build_cache(f, chunks[2], &report.build_cache);
+}
+
+fn render_daemon_info(f: &mut Frame<'_>, area: Rect, daemon: &DaemonInfo) {
+    let row = |label: &str, value: String| Line::from(vec![
+        Span::styled(format!("{: <10}", label), Style::default().fg(Color::Cyan)),
+        Span::raw(value),
+    ]);
+    let mut lines = vec![
+        row("Engine", format!("{} (API {})", daemon.engine_version, daemon.api_version)),
+        row("Host", format!("{} · {} · {}", daemon.name, daemon.os, daemon.kernel)),
+        row("Resources", format!("{} CPUs · {}", daemon.cpus, format_bytes(daemon.memory))),
+        row("Storage", daemon.storage_driver.clone()),
+        row("Cgroups", format!("{} (v{})", daemon.cgroup_driver, daemon.cgroup_version)),
+        row("Logging", format!("{} · runtime {}", daemon.logging_driver, daemon.default_runtime)),
+        row("Swarm", daemon.swarm_state.clone()),
+    ];
+    // Only room for one line; the title carries the count
+    if let Some(warning) = daemon.warnings.first() {
+        lines.push(Line::from(Span::styled(format!("⚠ {}", warning), Style::default().fg(Color::Yellow))));
+    }
+    let title = match daemon.warnings.len() {
+        0 => " Daemon ".to_string(),
+        n => format!(" Daemon ({} warnings) ", n),
+    };
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title).border_style(Style::default().fg(Color::Magenta)));
+    f.render_widget(paragraph, area);
+}
+
+fn render_build_cache(f: &mut Frame<'_>, area: Rect, entries: &[BuildCacheEntry]) {
+    let header_cells = ["ID", "TYPE", "SIZE", "LAST USED", "DESCRIPTION"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()));
+    let header = Row::new(header_cells).height(1);
+    let now = Utc::now().timestamp();
+    let rows = entries.iter().map(|b| {
+        let last_used = match b.last_used {
+            Some(ts) => {
+                let secs = (now - ts).max(0);
+                if secs >= 86_400 { format!("{}d ago", secs / 86_400) }
+                else if secs >= 3_600 { format!("{}h ago", secs / 3_600) }
+                else { format!("{}m ago", secs / 60) }
+            }
+            None => "never".to_string(),
+        };
+        let style = if b.in_use || b.shared { Style::default().fg(Color::DarkGray) } else { Style::default() };
+        Row::new(vec![
+            Cell::from(b.id.clone()),
+            Cell::from(b.kind.clone()),
+            Cell::from(format_bytes(b.size)),
+            Cell::from(last_used),
+            Cell::from(b.description.clone()),
+        ]).style(style)
+    });
+    let widths = [
+        Constraint::Length(13),
+        Constraint::Length(14),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Min(0),
+    ];
+    let total: u64 = entries.iter().map(|b| b.size).sum();
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Build Cache ({} records, {}) - c: prune ", entries.len(), format_bytes(total)))
+            .border_style(Style::default().fg(Color::Magenta)));
+    f.render_widget(table, area);
+}
+
+/// Volumes listed before the cleanup checklist removes them; beyond this many the rest are counted.
+const MAX_LISTED_VOLUMES: usize = 15;
+
+pub fn render_cleanup_volumes_confirm(f: &mut Frame<'_>, area: Rect, names: &[String]) {
+    let mut lines = vec![Line::from("These volumes are not used by any container and will be permanently removed, named ones included:"), Line::from("")];
+    for name in names.iter().take(MAX_LISTED_VOLUMES) {
+        lines.push(Line::from(format!("  • {}", name)));
+    }
+    if names.len() > MAX_LISTED_VOLUMES {
+        lines.push(Line::from(format!("  ... and {} more", names.len() - MAX_LISTED_VOLUMES)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("Press 'y' to remove them, 'n' or Esc to cancel."));
+
+    let area = centered_rect_fixed_height(50, lines.len() as u16 + 3, area);
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(" Confirm Volume Cleanup ");
+    f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+}
+
+pub fn render_prune_wizard(f: &mut Frame<'_>, area: Rect, step: &PruneStep, estimates: &[(CleanupKind, u64)], volumes: &[String]) {
+    match step {
+        PruneStep::Select(form) => {
+            let total: u64 = estimates.iter()
+                .filter(|(kind, _)| form.checkbox_value(kind.key()))
+                .map(|(_, bytes)| bytes)
+                .sum();
+            let area = centered_rect_fixed_height(50, form.content_height() + 4, area);
+            form.render(f, area, Color::Yellow);
+            let footer = Rect { x: area.x + 2, y: area.y + area.height.saturating_sub(2), width: area.width.saturating_sub(4), height: 1 };
+            f.render_widget(
+                Paragraph::new(format!("Estimated to reclaim: {}", format_bytes(total))).style(Style::default().fg(Color::Cyan)),
+                footer,
+            );
+        }
+        PruneStep::Confirm(kinds) => {
+            let total: u64 = estimates.iter().filter(|(kind, _)| kinds.contains(kind)).map(|(_, bytes)| bytes).sum();
+            let mut lines = vec![Line::from("The following will be permanently removed:"), Line::from("")];
+            for kind in kinds {
+                lines.push(Line::from(format!("  • {} ({})", kind.label(), kind.action())));
+                if *kind == CleanupKind::UnusedVolumes {
+                    lines.push(Line::from(Span::styled("    Named volumes are included, and may hold data:", Style::default().fg(Color::Red))));
+                    for name in volumes.iter().take(MAX_LISTED_VOLUMES) {
+                        lines.push(Line::from(format!("      {}", name)));
+                    }
+                    if volumes.len() > MAX_LISTED_VOLUMES {
+                        lines.push(Line::from(format!("      ... and {} more", volumes.len() - MAX_LISTED_VOLUMES)));
+                    }
+                }
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(format!("Estimated to reclaim: {}", format_bytes(total)), Style::default().fg(Color::Cyan))));
+            lines.push(Line::from("Press 'y' to prune, 'n' or Esc to cancel."));
+
+            let area = centered_rect_fixed_height(50, lines.len() as u16 + 2, area);
+            f.render_widget(Clear, area);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(" Confirm Prune ");
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        }
+        PruneStep::Summary(summary) => {
+            let summary = summary.read().unwrap().clone()
+                .unwrap_or_else(|| vec!["Pruning...".to_string()]);
+            let height = (summary.len() as u16 + 2).min(area.height);
+            let area = centered_rect_fixed_height(60, height, area);
+            f.render_widget(Clear, area);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
+                .title(" Prune Summary (Enter to close) ");
+            let lines: Vec<Line> = summary.iter().map(|l| Line::from(l.as_str())).collect();
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        }
+    }
+}
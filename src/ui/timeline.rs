@@ -0,0 +1,75 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use chrono::{DateTime, Local};
+use crate::app::{App, TIMELINE_BACKFILL_HOURS};
+use crate::types::LifecycleEvent;
+use crate::ui::layout::centered_rect;
+
+pub fn event_color(event: &LifecycleEvent) -> Color {
+    match (event.action.as_str(), event.detail.as_str()) {
+        ("start" | "unpause", _) | ("health_status", "healthy") => Color::Green,
+        ("oom" | "kill", _) | ("health_status", "unhealthy") => Color::Red,
+        ("die", detail) if detail != "exit code 0" => Color::Red,
+        ("restart" | "pause", _) => Color::Yellow,
+        _ => Color::Gray,
+    }
+}
+
+pub fn render_timeline(f: &mut Frame<'_>, area: Rect, app: &App, container_id: &str, container_name: &str) {
+    let area = centered_rect(70, 70, area);
+    f.render_widget(Clear, area);
+
+    let events: Vec<LifecycleEvent> = app.container_timelines.read().unwrap()
+        .get(container_id)
+        .map(|t| t.iter().rev().cloned().collect())
+        .unwrap_or_default();
+
+    let count = |action: &str| events.iter().filter(|e| e.action == action).count();
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{} starts", count("start")), Style::default().fg(Color::Green)),
+            Span::raw(" · "),
+            Span::styled(format!("{} restarts", count("restart")), Style::default().fg(Color::Yellow)),
+            Span::raw(" · "),
+            Span::styled(format!("{} exits", count("die")), Style::default().fg(Color::Gray)),
+            Span::raw(" · "),
+            Span::styled(format!("{} OOM kills", count("oom")), Style::default().fg(Color::Red)),
+        ]),
+        Line::from(""),
+    ];
+
+    if events.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("No lifecycle events in the last {}h", TIMELINE_BACKFILL_HOURS),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for event in &events {
+        let time = DateTime::from_timestamp(event.time, 0)
+            .map(|t| t.with_timezone(&Local).format("%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        let color = event_color(event);
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}  ", time), Style::default().fg(Color::DarkGray)),
+            Span::styled("● ", Style::default().fg(color)),
+            Span::styled(format!("{: <14}", event.action.replace('_', " ")), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::raw(event.detail.clone()),
+        ]));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Timeline: {} (newest first) ", container_name))
+        .title_bottom(" ↑/↓: Scroll | Esc: Close ");
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((app.viewer_scroll, 0));
+    f.render_widget(paragraph, area);
+}
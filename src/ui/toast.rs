@@ -0,0 +1,61 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::app::App;
+use crate::ui::layout::centered_rect;
+
+pub fn render_toast(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let toast = {
+        let mut lock = app.toast.write().unwrap();
+        if lock.as_ref().is_some_and(|t| t.is_expired()) {
+            *lock = None;
+        }
+        lock.clone()
+    };
+    let Some(toast) = toast else {
+        return;
+    };
+
+    // Bottom-right corner, just above the status bar
+    let width = (toast.message.chars().count() as u16 + 4).min(area.width).max(20.min(area.width));
+    let height = 3.min(area.height);
+    let toast_area = Rect {
+        x: area.x + area.width.saturating_sub(width),
+        y: area.y + area.height.saturating_sub(height),
+        width,
+        height,
+    };
+
+    let color = if toast.is_error { Color::Red } else { Color::Green };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+
+    f.render_widget(Clear, toast_area);
+    f.render_widget(
+        Paragraph::new(toast.message).block(block).wrap(Wrap { trim: true }),
+        toast_area,
+    );
+}
+
+pub fn render_quit_confirm(f: &mut Frame<'_>, area: Rect, count: usize) {
+    let area = centered_rect(44, 12, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(" Quit ");
+
+    let text = format!(
+        "{} queued action{} will not run if you quit now.\nPress 'y' to quit and cancel them, 'r' to run them now, 'n' or Esc to stay.",
+        count,
+        if count == 1 { "" } else { "s" },
+    );
+    let p = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+
+    f.render_widget(p, area);
+}
@@ -0,0 +1,134 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use crate::app::{format_bytes, App, TopSort};
+
+const BAR_WIDTH: usize = 20;
+const HISTORY_WIDTH: usize = 24;
+const LEVELS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+fn usage_color(fraction: f64) -> Color {
+    match fraction {
+        f if f < 0.6 => Color::Green,
+        f if f < 0.8 => Color::Yellow,
+        f if f < 0.95 => Color::LightRed,
+        _ => Color::Red,
+    }
+}
+
+fn bar(fraction: f64) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    format!("{}{}", "█".repeat(filled), "·".repeat(BAR_WIDTH - filled))
+}
+
+/// The last samples of `history` as block characters, averaged into
+/// `HISTORY_WIDTH` buckets so a long history still fits.
+fn sparkline(history: &[u64], scale: f64) -> String {
+    if history.is_empty() || scale <= 0.0 {
+        return String::new();
+    }
+    let per_bucket = history.len().div_ceil(HISTORY_WIDTH);
+    history.chunks(per_bucket)
+        .map(|bucket| {
+            let average = bucket.iter().sum::<u64>() as f64 / bucket.len() as f64;
+            let level = ((average / scale).clamp(0.0, 1.0) * (LEVELS.len() - 1) as f64).round() as usize;
+            LEVELS[level]
+        })
+        .collect()
+}
+
+/// Running containers ranked by CPU or memory, for triage.
+pub fn render_top(f: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let rows = app.top_rows();
+    let by = match app.top_sort {
+        TopSort::Cpu => "CPU",
+        TopSort::Memory => "memory",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Top: {} running, by {} ", rows.len(), by))
+        .border_style(Style::default().fg(Color::Red));
+
+    if rows.is_empty() {
+        let message = Paragraph::new("No running containers with stats yet.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(message, area);
+        return;
+    }
+
+    // The selection follows its container as the ranking changes
+    let selected = app.top_selected.as_ref().and_then(|id| rows.iter().position(|(c, _)| &c.id == id));
+    let selected = selected.unwrap_or_else(|| {
+        app.top_selected = Some(rows[0].0.id.clone());
+        0
+    });
+
+    // CPU can exceed 100% on several cores; bars scale to the busiest container
+    let cpu_scale = rows.iter().map(|(_, s)| s.cpu_percent).fold(100.0, f64::max);
+
+    let sort_header = |label: &'static str, sort: TopSort| {
+        let style = Style::default().fg(Color::Black).bg(Color::Cyan).bold();
+        Cell::from(if app.top_sort == sort { format!("{} ▼", label) } else { label.to_string() })
+            .style(if app.top_sort == sort { style.add_modifier(Modifier::UNDERLINED) } else { style })
+    };
+    let header = Row::new(vec![
+        Cell::from("NAME").style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()),
+        sort_header("CPU", TopSort::Cpu),
+        sort_header("MEMORY", TopSort::Memory),
+        Cell::from("HISTORY").style(Style::default().fg(Color::Black).bg(Color::Cyan).bold()),
+    ]).height(1);
+
+    let table_rows = rows.iter().map(|(c, s)| {
+        let name = match &c.host {
+            Some(host) => format!("{}/{}", host, c.name),
+            None => c.name.clone(),
+        };
+        let cpu_fraction = s.cpu_percent / cpu_scale;
+        let mem_fraction = if s.memory_limit > 0 { s.memory_usage as f64 / s.memory_limit as f64 } else { 0.0 };
+        let cpu = Line::from(vec![
+            Span::styled(bar(cpu_fraction), Style::default().fg(usage_color(s.cpu_percent / 100.0))),
+            Span::raw(format!(" {:>6.1}%", s.cpu_percent)),
+        ]);
+        let memory = Line::from(vec![
+            Span::styled(bar(mem_fraction), Style::default().fg(usage_color(mem_fraction))),
+            Span::raw(format!(" {:>9} / {}", format_bytes(s.memory_usage), format_bytes(s.memory_limit))),
+        ]);
+        // cpu_history holds hundredths of a percent
+        let history = match app.top_sort {
+            TopSort::Cpu => sparkline(&s.cpu_history, cpu_scale * 100.0),
+            TopSort::Memory => sparkline(&s.memory_history, s.memory_limit as f64),
+        };
+        Row::new(vec![
+            Cell::from(name).style(Style::default().fg(Color::Cyan)),
+            Cell::from(cpu),
+            Cell::from(memory),
+            Cell::from(history).style(Style::default().fg(Color::Magenta)),
+        ]).height(1)
+    });
+
+    let widths = [
+        Constraint::Min(16),
+        Constraint::Length(BAR_WIDTH as u16 + 9),
+        Constraint::Length(BAR_WIDTH as u16 + 24),
+        Constraint::Length(HISTORY_WIDTH as u16),
+    ];
+
+    let table = Table::new(table_rows, widths)
+        .header(header)
+        .block(block)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    let mut state = TableState::default().with_selected(Some(selected));
+    f.render_stateful_widget(table, area, &mut state);
+}
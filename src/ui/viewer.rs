@@ -0,0 +1,72 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::types::Syntax;
+use crate::ui::layout::centered_rect;
+
+fn highlight_yaml(line: &str) -> Line<'_> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if trimmed.starts_with('#') {
+        return Line::from(Span::styled(line, Style::default().fg(Color::DarkGray)));
+    }
+
+    let mut spans = vec![Span::raw(indent)];
+    let mut rest = trimmed;
+    if let Some(after) = rest.strip_prefix("- ") {
+        spans.push(Span::styled("- ", Style::default().fg(Color::Yellow)));
+        rest = after;
+    }
+
+    // `key: value` — only treat it as a key if the colon comes before any quote
+    let key_end = rest.find(": ").or_else(|| rest.strip_suffix(':').map(|k| k.len()));
+    let is_key = key_end.is_some_and(|i| !rest[..i].contains(['"', '\'']));
+    if let (true, Some(i)) = (is_key, key_end) {
+        spans.push(Span::styled(&rest[..i], Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        spans.push(Span::raw(":"));
+        rest = &rest[(i + 1).min(rest.len())..];
+    }
+
+    if !rest.is_empty() {
+        let value = rest.trim_start();
+        let style = if value.starts_with('"') || value.starts_with('\'') {
+            Style::default().fg(Color::Green)
+        } else if value.parse::<f64>().is_ok() || matches!(value, "true" | "false" | "null") {
+            Style::default().fg(Color::Magenta)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        spans.push(Span::styled(rest, style));
+    }
+    Line::from(spans)
+}
+
+pub fn render_text_viewer(f: &mut Frame<'_>, area: Rect, app: &App, title: &str, content: &str, syntax: Syntax) {
+    let area = centered_rect(80, 80, area);
+    f.render_widget(Clear, area);
+
+    let lines: Vec<Line> = content
+        .lines()
+        .map(|l| match syntax {
+            Syntax::Yaml => highlight_yaml(l),
+            Syntax::Plain => Line::from(l),
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" {} ", title))
+        .title_bottom(" ↑/↓ PgUp/PgDn: Scroll | Esc: Close ");
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((app.viewer_scroll, 0));
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,277 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::ui::widgets::text_input::TextInput;
+
+#[derive(Debug, Clone)]
+pub enum FieldKind {
+    /// Static, non-focusable text (hints, warnings, section titles)
+    Label,
+    Text(TextInput),
+    Select { options: Vec<String>, selected: usize },
+    Checkbox(bool),
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub key: &'static str,
+    pub label: String,
+    pub kind: FieldKind,
+}
+
+impl Field {
+    fn is_focusable(&self) -> bool {
+        !matches!(self.kind, FieldKind::Label)
+    }
+
+    fn height(&self) -> u16 {
+        match &self.kind {
+            // Label line + input line + validation line
+            FieldKind::Text(_) => 3,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormOutcome {
+    Pending,
+    Submitted,
+    Cancelled,
+}
+
+/// A vertical stack of labelled fields with Tab/Shift+Tab focus order.
+/// Enter submits once every text field validates, Esc cancels.
+#[derive(Debug, Clone)]
+pub struct Form {
+    pub title: String,
+    fields: Vec<Field>,
+    focused: usize,
+}
+
+impl Form {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            fields: Vec::new(),
+            focused: 0,
+        }
+    }
+
+    pub fn label(mut self, text: impl Into<String>) -> Self {
+        self.push(Field { key: "", label: text.into(), kind: FieldKind::Label });
+        self
+    }
+
+    /// A label the owner rewrites as the form changes, e.g. an inline warning.
+    /// Shown in yellow, and as a blank line while empty.
+    pub fn note(mut self, key: &'static str, text: impl Into<String>) -> Self {
+        self.push(Field { key, label: text.into(), kind: FieldKind::Label });
+        self
+    }
+
+    pub fn set_note(&mut self, key: &str, text: impl Into<String>) {
+        if let Some(field) = self.fields.iter_mut().find(|f| !f.key.is_empty() && f.key == key) {
+            field.label = text.into();
+        }
+    }
+
+    pub fn text(mut self, key: &'static str, label: impl Into<String>, input: TextInput) -> Self {
+        self.push(Field { key, label: label.into(), kind: FieldKind::Text(input) });
+        self
+    }
+
+    pub fn select(mut self, key: &'static str, label: impl Into<String>, options: Vec<String>, selected: usize) -> Self {
+        let selected = selected.min(options.len().saturating_sub(1));
+        self.push(Field { key, label: label.into(), kind: FieldKind::Select { options, selected } });
+        self
+    }
+
+    pub fn checkbox(mut self, key: &'static str, label: impl Into<String>, checked: bool) -> Self {
+        self.push(Field { key, label: label.into(), kind: FieldKind::Checkbox(checked) });
+        self
+    }
+
+    fn push(&mut self, field: Field) {
+        // Focus starts on the first focusable field
+        if !self.fields.get(self.focused).is_some_and(|f| f.is_focusable()) && field.is_focusable() {
+            self.focused = self.fields.len();
+        }
+        self.fields.push(field);
+    }
+
+    fn field(&self, key: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| !f.key.is_empty() && f.key == key)
+    }
+
+    pub fn text_value(&self, key: &str) -> String {
+        match self.field(key).map(|f| &f.kind) {
+            Some(FieldKind::Text(input)) => input.value().to_string(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn select_value(&self, key: &str) -> Option<String> {
+        match self.field(key).map(|f| &f.kind) {
+            Some(FieldKind::Select { options, selected }) => options.get(*selected).cloned(),
+            _ => None,
+        }
+    }
+
+    pub fn checkbox_value(&self, key: &str) -> bool {
+        matches!(self.field(key).map(|f| &f.kind), Some(FieldKind::Checkbox(true)))
+    }
+
+    pub fn text_input_mut(&mut self, key: &str) -> Option<&mut TextInput> {
+        self.fields.iter_mut()
+            .find(|f| f.key == key)
+            .and_then(|f| match &mut f.kind {
+                FieldKind::Text(input) => Some(input),
+                _ => None,
+            })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.fields.iter().all(|f| match &f.kind {
+            FieldKind::Text(input) => input.is_valid(),
+            _ => true,
+        })
+    }
+
+    /// Total rows needed to render every field, excluding borders.
+    pub fn content_height(&self) -> u16 {
+        self.fields.iter().map(Field::height).sum::<u16>() + 1
+    }
+
+    fn focus_next(&mut self, forward: bool) {
+        let len = self.fields.len();
+        if len == 0 {
+            return;
+        }
+        let mut idx = self.focused;
+        for _ in 0..len {
+            idx = if forward { (idx + 1) % len } else { (idx + len - 1) % len };
+            if self.fields[idx].is_focusable() {
+                self.focused = idx;
+                return;
+            }
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormOutcome {
+        match key.code {
+            KeyCode::Esc => return FormOutcome::Cancelled,
+            KeyCode::Enter => {
+                return if self.is_valid() { FormOutcome::Submitted } else { FormOutcome::Pending };
+            }
+            KeyCode::Tab => {
+                self.focus_next(true);
+                return FormOutcome::Pending;
+            }
+            KeyCode::BackTab => {
+                self.focus_next(false);
+                return FormOutcome::Pending;
+            }
+            _ => {}
+        }
+
+        let Some(field) = self.fields.get_mut(self.focused) else {
+            return FormOutcome::Pending;
+        };
+        let handled = match &mut field.kind {
+            FieldKind::Text(input) => input.handle_key(key),
+            FieldKind::Select { options, selected } => match key.code {
+                KeyCode::Left | KeyCode::Char('h') => {
+                    *selected = (*selected + options.len().max(1) - 1) % options.len().max(1);
+                    true
+                }
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                    *selected = (*selected + 1) % options.len().max(1);
+                    true
+                }
+                _ => false,
+            },
+            FieldKind::Checkbox(checked) => match key.code {
+                KeyCode::Char(' ') | KeyCode::Char('x') => {
+                    *checked = !*checked;
+                    true
+                }
+                _ => false,
+            },
+            FieldKind::Label => false,
+        };
+
+        // Up/Down move focus when the field itself doesn't use them
+        if !handled {
+            match key.code {
+                KeyCode::Down | KeyCode::Char('j') => self.focus_next(true),
+                KeyCode::Up | KeyCode::Char('k') => self.focus_next(false),
+                _ => {}
+            }
+        }
+        FormOutcome::Pending
+    }
+
+    pub fn render(&self, f: &mut Frame<'_>, area: Rect, border_color: Color) {
+        f.render_widget(Clear, area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title(format!(" {} (Tab: next field, Enter: submit, Esc: cancel) ", self.title));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let mut constraints: Vec<Constraint> = self.fields.iter().map(|fd| Constraint::Length(fd.height())).collect();
+        constraints.push(Constraint::Min(0));
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+
+        for (i, field) in self.fields.iter().enumerate() {
+            let focused = i == self.focused;
+            let label_style = if focused {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let area = chunks[i];
+
+            match &field.kind {
+                FieldKind::Label => {
+                    let color = if field.key.is_empty() { Color::DarkGray } else { Color::Yellow };
+                    f.render_widget(Paragraph::new(field.label.as_str()).style(Style::default().fg(color)), area);
+                }
+                FieldKind::Text(input) => {
+                    let rows = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Length(1), Constraint::Min(1)])
+                        .split(area);
+                    f.render_widget(Paragraph::new(Span::styled(field.label.as_str(), label_style)), rows[0]);
+                    input.render(f, rows[1], focused);
+                }
+                FieldKind::Select { options, selected } => {
+                    let value = options.get(*selected).map(|s| s.as_str()).unwrap_or("-");
+                    let line = Line::from(vec![
+                        Span::styled(format!("{}: ", field.label), label_style),
+                        Span::styled(format!("◀ {} ▶", value), if focused { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default() }),
+                    ]);
+                    f.render_widget(Paragraph::new(line), area);
+                }
+                FieldKind::Checkbox(checked) => {
+                    let mark = if *checked { "[x]" } else { "[ ]" };
+                    let line = Line::from(vec![
+                        Span::styled(format!("{} ", mark), if focused { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default() }),
+                        Span::styled(field.label.as_str(), label_style),
+                    ]);
+                    f.render_widget(Paragraph::new(line), area);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod text_input;
+pub mod form;
@@ -0,0 +1,383 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+pub type Validator = fn(&str) -> std::result::Result<(), String>;
+
+const MAX_HISTORY: usize = 50;
+
+/// Single-line text input with a cursor, readline-style editing, history and
+/// optional inline validation. Enter/Esc are left to the owning dialog.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    cursor: usize, // char index, not byte index
+    history: Vec<String>,
+    history_index: Option<usize>,
+    validator: Option<Validator>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_validator(validator: Validator) -> Self {
+        Self {
+            validator: Some(validator),
+            ..Self::default()
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.chars().count();
+        self.history_index = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_value(String::new());
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.validator.and_then(|v| v(&self.value).err())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error().is_none()
+    }
+
+    /// Records the current value in history (deduplicating consecutive entries).
+    pub fn commit_history(&mut self) {
+        if self.value.is_empty() || self.history.last() == Some(&self.value) {
+            return;
+        }
+        self.history.push(self.value.clone());
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history_index = None;
+    }
+
+    /// Returns true if the key was consumed by the input.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        // Windows reports AltGr as Ctrl+Alt; those keys type characters such as
+        // '@', '\' or '{' on many layouts instead of being shortcuts
+        let altgr = key.modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL) && !altgr;
+        let alt = key.modifiers.contains(KeyModifiers::ALT) && !altgr;
+
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.len(),
+            KeyCode::Char('u') if ctrl => {
+                let idx = self.byte_index(self.cursor);
+                self.value.replace_range(..idx, "");
+                self.cursor = 0;
+            }
+            KeyCode::Char('k') if ctrl => {
+                let idx = self.byte_index(self.cursor);
+                self.value.truncate(idx);
+            }
+            KeyCode::Char('w') if ctrl => self.delete_word_back(),
+            KeyCode::Backspace if alt || ctrl => self.delete_word_back(),
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_start_before(self.cursor),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_end_after(self.cursor),
+            KeyCode::Char(c) if !ctrl => {
+                let idx = self.byte_index(self.cursor);
+                self.value.insert(idx, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace => {
+                if self.cursor > 0 {
+                    let idx = self.byte_index(self.cursor - 1);
+                    self.value.remove(idx);
+                    self.cursor -= 1;
+                }
+            }
+            KeyCode::Delete => {
+                if self.cursor < self.len() {
+                    let idx = self.byte_index(self.cursor);
+                    self.value.remove(idx);
+                }
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len(),
+            KeyCode::Up => self.history_prev(),
+            KeyCode::Down => self.history_next(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Renders `> value` with a block cursor and, if there is room, the validation error below it.
+    pub fn render(&self, f: &mut Frame<'_>, area: Rect, focused: bool) {
+        let chars: Vec<char> = self.value.chars().collect();
+        let before: String = chars[..self.cursor].iter().collect();
+        let at: String = chars.get(self.cursor).map(|c| c.to_string()).unwrap_or_else(|| " ".to_string());
+        let after: String = chars.get(self.cursor + 1..).map(|c| c.iter().collect()).unwrap_or_default();
+
+        let cursor_style = if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Yellow)),
+            Span::raw(before),
+            Span::styled(at, cursor_style),
+            Span::raw(after),
+        ])];
+
+        if let Some(err) = self.error().filter(|_| !self.value.is_empty()) {
+            lines.push(Line::from(Span::styled(err, Style::default().fg(Color::Red))));
+        }
+
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self, char_idx: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_idx)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    fn word_start_before(&self, pos: usize) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut i = pos;
+        while i > 0 && !is_word_char(chars[i - 1]) {
+            i -= 1;
+        }
+        while i > 0 && is_word_char(chars[i - 1]) {
+            i -= 1;
+        }
+        i
+    }
+
+    fn word_end_after(&self, pos: usize) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut i = pos;
+        while i < chars.len() && !is_word_char(chars[i]) {
+            i += 1;
+        }
+        while i < chars.len() && is_word_char(chars[i]) {
+            i += 1;
+        }
+        i
+    }
+
+    fn delete_word_back(&mut self) {
+        let start = self.word_start_before(self.cursor);
+        let (from, to) = (self.byte_index(start), self.byte_index(self.cursor));
+        self.value.replace_range(from..to, "");
+        self.cursor = start;
+    }
+
+    fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let idx = match self.history_index {
+            Some(0) => 0,
+            Some(i) => i - 1,
+            None => self.history.len() - 1,
+        };
+        self.history_index = Some(idx);
+        self.value = self.history[idx].clone();
+        self.cursor = self.len();
+    }
+
+    fn history_next(&mut self) {
+        match self.history_index {
+            Some(i) if i + 1 < self.history.len() => {
+                self.history_index = Some(i + 1);
+                self.value = self.history[i + 1].clone();
+            }
+            Some(_) => {
+                self.history_index = None;
+                self.value.clear();
+            }
+            None => return,
+        }
+        self.cursor = self.len();
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Validator for image references such as `nginx`, `nginx:1.25` or `ghcr.io/org/app@sha256:...`.
+pub fn validate_image_ref(value: &str) -> std::result::Result<(), String> {
+    if value.trim().is_empty() {
+        return Err("Image name is required".to_string());
+    }
+    if value.chars().any(char::is_whitespace) {
+        return Err("Image name cannot contain spaces".to_string());
+    }
+    if let Some(c) = value.chars().find(|c| !(c.is_ascii_alphanumeric() || "._-/:@".contains(*c))) {
+        return Err(format!("Invalid character '{}'", c));
+    }
+    let repo = value.split('@').next().unwrap_or(value);
+    let repo = match repo.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => name,
+        _ => repo,
+    };
+    if repo.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err("Repository names must be lowercase".to_string());
+    }
+    Ok(())
+}
+
+/// Validator for replica counts (0-100).
+pub fn validate_replicas(value: &str) -> std::result::Result<(), String> {
+    match value.trim().parse::<u32>() {
+        Ok(n) if n <= 100 => Ok(()),
+        Ok(_) => Err("At most 100 replicas".to_string()),
+        Err(_) => Err("Enter a whole number".to_string()),
+    }
+}
+
+/// Validator for an optional signal name (`SIGHUP`, `HUP`, `SIGRTMIN+3`) or number.
+pub fn validate_signal(value: &str) -> std::result::Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() || value.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-') {
+        Ok(())
+    } else {
+        Err("Enter a signal name or number".to_string())
+    }
+}
+
+/// Validator for an optional whole number, e.g. a threshold that may be left blank.
+pub fn validate_optional_number(value: &str) -> std::result::Result<(), String> {
+    if value.trim().is_empty() || value.trim().parse::<u64>().is_ok() {
+        Ok(())
+    } else {
+        Err("Enter a whole number or leave blank".to_string())
+    }
+}
+
+/// Validator for an optional percentage (0-100, decimals allowed).
+pub fn validate_optional_percent(value: &str) -> std::result::Result<(), String> {
+    match value.trim() {
+        "" => Ok(()),
+        value => match value.parse::<f64>() {
+            Ok(n) if (0.0..=100.0).contains(&n) => Ok(()),
+            _ => Err("Enter a percentage from 0 to 100 or leave blank".to_string()),
+        },
+    }
+}
+
+/// Validator for a duration in seconds (1-3600).
+pub fn validate_seconds(value: &str) -> std::result::Result<(), String> {
+    match value.trim().parse::<u64>() {
+        Ok(n) if (1..=3600).contains(&n) => Ok(()),
+        Ok(_) => Err("Between 1 and 3600 seconds".to_string()),
+        Err(_) => Err("Enter a whole number".to_string()),
+    }
+}
+
+/// Validator for a time window in minutes (1-1440).
+pub fn validate_minutes(value: &str) -> std::result::Result<(), String> {
+    match value.trim().parse::<u32>() {
+        Ok(n) if (1..=1440).contains(&n) => Ok(()),
+        Ok(_) => Err("Between 1 and 1440 minutes".to_string()),
+        Err(_) => Err("Enter a whole number".to_string()),
+    }
+}
+
+/// `key=value` pairs separated by `;`, as entered in the label editor.
+pub fn parse_label_assignments(value: &str) -> Vec<(String, String)> {
+    value.split(';')
+        .filter_map(|entry| entry.trim().split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Validator for optional `key=value; key2=value2` label assignments.
+pub fn validate_label_assignments(value: &str) -> std::result::Result<(), String> {
+    for entry in value.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() && !key.trim().contains(char::is_whitespace) => {}
+            _ => return Err(format!("'{}' is not key=value", entry)),
+        }
+    }
+    Ok(())
+}
+
+/// Validator for a new container name, following the daemon's rules.
+pub fn validate_container_name(value: &str) -> std::result::Result<(), String> {
+    let value = value.trim();
+    let mut chars = value.chars();
+    match chars.next() {
+        None => Err("Name is required".to_string()),
+        Some(c) if !c.is_ascii_alphanumeric() => Err("Must start with a letter or digit".to_string()),
+        _ if chars.any(|c| !(c.is_ascii_alphanumeric() || "_.-".contains(c))) => {
+            Err("Only letters, digits, '_', '.' and '-'".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validator for a file or directory that exists on this machine.
+pub fn validate_local_path(value: &str) -> std::result::Result<(), String> {
+    match value.trim() {
+        "" => Err("Path is required".to_string()),
+        path if std::path::Path::new(path).exists() => Ok(()),
+        _ => Err("No such file or directory".to_string()),
+    }
+}
+
+/// Validator for an absolute path inside a container.
+pub fn validate_container_dir(value: &str) -> std::result::Result<(), String> {
+    if value.trim().starts_with('/') {
+        Ok(())
+    } else {
+        Err("Enter an absolute path".to_string())
+    }
+}
+
+/// Validator for a command line to exec, with shell-style quoting.
+pub fn validate_command(value: &str) -> std::result::Result<(), String> {
+    match crate::docker::exec::split_command(value)? {
+        args if args.is_empty() => Err("Command is required".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Validator for optional space-separated `[ip:]host:container[/proto]` port mappings.
+pub fn validate_port_specs(value: &str) -> std::result::Result<(), String> {
+    crate::docker::containers::parse_port_specs(value).map(|_| ())
+}
+
+/// Validator for a time to jump to in the logs pane.
+pub fn validate_log_time(value: &str) -> std::result::Result<(), String> {
+    crate::app::parse_log_time(value).map(|_| ())
+}
+
+/// Validator for a bulk command typed at the Containers view's `:` prompt.
+pub fn validate_bulk_command(value: &str) -> std::result::Result<(), String> {
+    crate::command::parse(value).map(|_| ())
+}